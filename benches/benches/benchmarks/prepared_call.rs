@@ -0,0 +1,34 @@
+//! Benchmark repeated calls to the same function, with and without preparing
+//! the call ahead of time.
+
+use criterion::Criterion;
+
+criterion::criterion_group!(benches, call, prepared_call);
+
+fn call(b: &mut Criterion) {
+    let mut vm = rune_vm! {
+        pub fn step(position, velocity) {
+            position + velocity
+        }
+    };
+
+    let entry = rune::Hash::type_hash(["step"]);
+
+    b.bench_function("call", |b| {
+        b.iter(|| vm.call(entry, (1, 2)).expect("failed call"));
+    });
+}
+
+fn prepared_call(b: &mut Criterion) {
+    let mut vm = rune_vm! {
+        pub fn step(position, velocity) {
+            position + velocity
+        }
+    };
+
+    let step = vm.prepare_call(["step"]).expect("failed to prepare call");
+
+    b.bench_function("prepared_call", |b| {
+        b.iter(|| step.call(&mut vm, (1, 2)).expect("failed call"));
+    });
+}
@@ -14,6 +14,7 @@ use crate::error::{CustomError, Error};
 use crate::iter::{TryExtend, TryFromIteratorIn};
 #[cfg(test)]
 use crate::testing::*;
+use crate::vec::Vec;
 
 use super::raw::{Bucket, RawDrain, RawIntoIter, RawIter, RawTable};
 use super::{Equivalent, ErrorOrInsertSlot, HasherFn};
@@ -208,9 +209,9 @@ pub struct HashMap<K, V, S = DefaultHashBuilder, A: Allocator = Global> {
 
 impl<K, V, S, A> TryClone for HashMap<K, V, S, A>
 where
-    K: TryClone,
+    K: TryClone + Hash,
     V: TryClone,
-    S: Clone,
+    S: BuildHasher + Clone,
     A: Allocator + Clone,
 {
     fn try_clone(&self) -> Result<Self, Error> {
@@ -221,7 +222,16 @@ where
     }
 
     fn try_clone_from(&mut self, source: &Self) -> Result<(), Error> {
-        self.table.try_clone_from(&source.table)?;
+        // NB: Elements are hashed with the hash builder of the source, since it
+        // replaces ours once all elements have been cloned. This allows the
+        // existing table to be reused if it has enough capacity.
+        let hasher = make_hasher::<K, S>(&source.hash_builder);
+
+        into_ok_try(self.table.clone_from_with_hasher(
+            &mut (),
+            &source.table,
+            hasher.into_tuple(),
+        ))?;
 
         // Update hash_builder only if we successfully cloned all elements.
         self.hash_builder.clone_from(&source.hash_builder);
@@ -232,9 +242,9 @@ where
 #[cfg(test)]
 impl<K, V, S, A> Clone for HashMap<K, V, S, A>
 where
-    K: TryClone,
+    K: TryClone + Hash,
     V: TryClone,
-    S: Clone,
+    S: BuildHasher + Clone,
     A: Allocator + Clone,
 {
     fn clone(&self) -> Self {
@@ -686,6 +696,29 @@ where
         self.table.capacity()
     }
 
+    /// Returns the number of bytes currently allocated by the map to store
+    /// its elements, including the control bytes used by the table.
+    ///
+    /// This doesn't include any memory allocated by the keys or values
+    /// themselves, and is `0` if the map hasn't allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashMap;
+    ///
+    /// let mut map: HashMap<i32, i32> = HashMap::new();
+    /// assert_eq!(map.allocation_size(), 0);
+    ///
+    /// map.try_insert(1, 2)?;
+    /// assert!(map.allocation_size() > 0);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn allocation_size(&self) -> usize {
+        self.table.allocation_info().1.size()
+    }
+
     /// An iterator visiting all keys in arbitrary order.
     /// The iterator element type is `&'a K`.
     ///
@@ -793,6 +826,67 @@ where
         }
     }
 
+    /// Partitions mutable references to all values of the map into up to `n`
+    /// disjoint batches of roughly equal size, in arbitrary order.
+    ///
+    /// Every value is part of exactly one batch, and no batch is empty. This
+    /// is useful for handing batches to different worker threads, in which
+    /// case `V` must be [`Send`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashMap;
+    ///
+    /// let mut map: HashMap<_, _> = [("a", 1), ("b", 2), ("c", 3)].try_into()?;
+    ///
+    /// let chunks = map.try_values_mut_chunks(2)?;
+    /// assert_eq!(chunks.len(), 2);
+    ///
+    /// for chunk in chunks {
+    ///     for value in chunk {
+    ///         *value *= 10;
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(map["a"], 10);
+    /// assert_eq!(map["b"], 20);
+    /// assert_eq!(map["c"], 30);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_values_mut_chunks(&mut self, n: usize) -> Result<Vec<Vec<&mut V>>, Error> {
+        assert!(n != 0, "number of chunks must be non-zero");
+
+        let len = self.len();
+        let n = n.min(len);
+        let mut chunks = Vec::try_with_capacity(n)?;
+
+        if n == 0 {
+            return Ok(chunks);
+        }
+
+        let size = len / n;
+        let extra = len % n;
+        let mut values = self.values_mut();
+
+        for index in 0..n {
+            let size = size + usize::from(index < extra);
+            let mut chunk = Vec::try_with_capacity(size)?;
+
+            for value in values.by_ref().take(size) {
+                chunk.try_push(value)?;
+            }
+
+            chunks.try_push(chunk)?;
+        }
+
+        Ok(chunks)
+    }
+
     /// An iterator visiting all key-value pairs in arbitrary order.
     /// The iterator element type is `(&'a K, &'a V)`.
     ///
@@ -1157,6 +1251,43 @@ where
     S: BuildHasher,
     A: Allocator,
 {
+    /// Clones the map into a new map which uses the given allocator.
+    ///
+    /// Unlike [`TryClone::try_clone`], which allocates the clone using the
+    /// allocator of the original map, this allows moving the contents of the
+    /// map into a different allocator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashMap;
+    /// use rune::alloc::alloc::Global;
+    ///
+    /// let mut a = HashMap::new();
+    /// a.try_insert(1, "a")?;
+    /// a.try_insert(2, "b")?;
+    ///
+    /// let b = a.try_clone_in(Global)?;
+    /// assert_eq!(a, b);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_clone_in<B>(&self, alloc: B) -> Result<HashMap<K, V, S, B>, Error>
+    where
+        K: TryClone,
+        V: TryClone,
+        S: Clone,
+        B: Allocator,
+    {
+        let mut map =
+            HashMap::try_with_capacity_and_hasher_in(self.len(), self.hash_builder.clone(), alloc)?;
+
+        for (k, v) in self.iter() {
+            map.try_insert_unique_unchecked(k.try_clone()?, v.try_clone()?)?;
+        }
+
+        Ok(map)
+    }
+
     /// Tries to reserve capacity for at least `additional` more elements to be inserted
     /// in the given `HashMap<K,V>`. The collection may reserve more space to avoid
     /// frequent reallocations.
@@ -1278,6 +1409,57 @@ where
         ))
     }
 
+    /// Computes the distribution of probe sequence lengths of the entries in
+    /// the map.
+    ///
+    /// The probe length of an entry is the number of groups of buckets which
+    /// have to be probed past the one its hash maps to before the entry is
+    /// found. Element `n` of the returned histogram is the number of entries
+    /// which have a probe length of `n`, so a good hasher produces a
+    /// histogram where most entries are counted at the start. Long tails
+    /// indicate that many keys hash to the same buckets.
+    ///
+    /// The histogram is empty if the map is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    ///
+    /// for n in 0..32 {
+    ///     map.try_insert(n, n)?;
+    /// }
+    ///
+    /// let histogram = map.try_probe_length_histogram()?;
+    /// assert_eq!(histogram.iter().sum::<usize>(), 32);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_probe_length_histogram(&self) -> Result<Vec<usize>, Error> {
+        let mut histogram = Vec::new();
+
+        // SAFETY: The iterator doesn't outlive the table, and the buckets it
+        // produces are full.
+        unsafe {
+            for bucket in self.table.iter() {
+                let (key, _) = bucket.as_ref();
+                let hash = make_hash::<K, S>(&self.hash_builder, key);
+                let length = self
+                    .table
+                    .probe_length(hash, self.table.bucket_index(&bucket));
+
+                if histogram.len() <= length {
+                    histogram.try_resize(length + 1, 0)?;
+                }
+
+                histogram[length] += 1;
+            }
+        }
+
+        Ok(histogram)
+    }
+
     /// Gets the given key's corresponding entry in the map for in-place manipulation.
     ///
     /// # Examples
@@ -1317,6 +1499,37 @@ where
         }
     }
 
+    /// Gets a mutable reference to the value corresponding to the given key,
+    /// inserting the default value of `V` if the key is not present.
+    ///
+    /// This is a shorthand for `map.entry(key).or_try_default()`, which only
+    /// hashes the key once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashMap;
+    ///
+    /// let mut letters = HashMap::<char, usize>::new();
+    ///
+    /// for ch in "a short treatise on fungi".chars() {
+    ///     *letters.try_entry_or_default(ch)? += 1;
+    /// }
+    ///
+    /// assert_eq!(letters[&'s'], 2);
+    /// assert_eq!(letters[&'t'], 3);
+    /// assert_eq!(letters[&'u'], 1);
+    /// assert_eq!(letters.get(&'y'), None);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_entry_or_default(&mut self, key: K) -> Result<&mut V, Error>
+    where
+        V: Default,
+    {
+        self.entry(key).or_try_default()
+    }
+
     /// Gets the given key's corresponding entry by reference in the map for in-place manipulation.
     ///
     /// # Examples
@@ -1599,6 +1812,44 @@ where
         self.get_many_mut_inner(ks).map(|res| res.map(|(_, v)| v))
     }
 
+    /// Attempts to get mutable references to the values of two distinct keys
+    /// in the map at once.
+    ///
+    /// This is a specialization of [`get_many_mut`] for two keys. `None` is
+    /// returned if either key is missing, or if both keys refer to the same
+    /// entry.
+    ///
+    /// [`get_many_mut`]: HashMap::get_many_mut
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashMap;
+    ///
+    /// let mut balances = HashMap::new();
+    /// balances.try_insert("alice", 10)?;
+    /// balances.try_insert("bob", 20)?;
+    ///
+    /// if let Some((a, b)) = balances.get_pair_mut("alice", "bob") {
+    ///     core::mem::swap(a, b);
+    /// }
+    ///
+    /// assert_eq!(balances.get("alice"), Some(&20));
+    /// assert_eq!(balances.get("bob"), Some(&10));
+    ///
+    /// // Missing or identical keys result in None.
+    /// assert_eq!(balances.get_pair_mut("alice", "carol"), None);
+    /// assert_eq!(balances.get_pair_mut("alice", "alice"), None);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn get_pair_mut<Q>(&mut self, a: &Q, b: &Q) -> Option<(&'_ mut V, &'_ mut V)>
+    where
+        Q: ?Sized + Hash + Equivalent<K>,
+    {
+        let [a, b] = self.get_many_mut([a, b])?;
+        Some((a, b))
+    }
+
     /// Attempts to get mutable references to `N` values in the map at once, without validating that
     /// the values are unique.
     ///
@@ -1973,6 +2224,56 @@ where
         }
     }
 
+    /// Extends the map by cloning every key-value pair in `other`.
+    ///
+    /// Capacity for `other.len()` entries is reserved up front. If a key in
+    /// `other` is already present in this map, its value is replaced by the
+    /// cloned value from `other`.
+    ///
+    /// # Errors
+    ///
+    /// If reserving capacity or cloning a key or value fails, an error is
+    /// returned. Entries which were cloned before the failure remain in the
+    /// map, which is otherwise left in a valid state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::{HashMap, String};
+    /// use rune::alloc::prelude::*;
+    ///
+    /// let mut a = HashMap::new();
+    /// a.try_insert(String::try_from("a")?, 1)?;
+    /// a.try_insert(String::try_from("b")?, 2)?;
+    ///
+    /// let mut b = HashMap::new();
+    /// b.try_insert(String::try_from("b")?, 20)?;
+    /// b.try_insert(String::try_from("c")?, 30)?;
+    ///
+    /// a.try_extend_ref(&b)?;
+    ///
+    /// assert_eq!(a.len(), 3);
+    /// assert_eq!(a["a"], 1);
+    /// assert_eq!(a["b"], 20);
+    /// assert_eq!(a["c"], 30);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_extend_ref(&mut self, other: &HashMap<K, V, S, A>) -> Result<(), Error>
+    where
+        K: TryClone,
+        V: TryClone,
+    {
+        self.try_reserve(other.len())?;
+
+        for (k, v) in other {
+            let k = k.try_clone()?;
+            let v = v.try_clone()?;
+            self.try_insert(k, v)?;
+        }
+
+        Ok(())
+    }
+
     /// Removes a key from the map, returning the value at the key if the key
     /// was previously in the map. Keeps the allocated memory for reuse.
     ///
@@ -2049,6 +2350,39 @@ where
         let hash = make_hash::<Q, S>(&self.hash_builder, k);
         into_ok(self.table.remove_entry(&mut (), hash, equivalent_key(k)))
     }
+
+    /// Removes every key produced by the given iterator from the map,
+    /// returning the number of keys which were present. Keeps the allocated
+    /// memory for reuse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashMap;
+    ///
+    /// let mut map: HashMap<i32, &str> = [(1, "a"), (2, "b"), (3, "c")].try_into()?;
+    ///
+    /// assert_eq!(map.try_remove_all([1, 3, 5]), 2);
+    /// assert_eq!(map.len(), 1);
+    /// assert!(map.contains_key(&2));
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_remove_all<I, Q>(&mut self, keys: I) -> usize
+    where
+        I: IntoIterator<Item = Q>,
+        Q: Hash + Equivalent<K>,
+    {
+        let mut removed = 0;
+
+        for key in keys {
+            if self.remove_entry(&key).is_some() {
+                removed += 1;
+            }
+        }
+
+        removed
+    }
 }
 
 impl<K, V, S, A> HashMap<K, V, S, A>
@@ -2936,7 +3270,12 @@ where
     }
 }
 
-impl<K, V, F> FusedIterator for ExtractIf<'_, K, V, F> where F: FnMut(&K, &mut V) -> bool {}
+impl<K, V, F, A> FusedIterator for ExtractIf<'_, K, V, F, A>
+where
+    F: FnMut(&K, &mut V) -> bool,
+    A: Allocator,
+{
+}
 
 /// Portions of `ExtractIf` shared with `set::ExtractIf`
 pub(super) struct ExtractIfInner<'a, K, V, A>
@@ -7356,6 +7695,7 @@ mod test_map {
     use crate::alloc::{AllocError, Allocator, Global};
     use crate::clone::TryClone;
     use crate::error::Error;
+    use crate::hashbrown::capacity_for;
     use crate::iter::TryExtend;
     use crate::testing::*;
 
@@ -7393,6 +7733,28 @@ mod test_map {
         assert_eq!(m.capacity(), 0);
     }
 
+    #[test]
+    fn test_capacity_for() {
+        let mut last = 0;
+
+        for n in 0..1024 {
+            let capacity = capacity_for(n);
+            assert!(capacity >= n);
+            assert!(capacity >= last);
+            last = capacity;
+
+            let m = HashMap::<i32, i32>::try_with_capacity(n).unwrap();
+            assert!(m.capacity() >= capacity);
+        }
+
+    }
+
+    #[test]
+    #[should_panic = "Hash table capacity overflow"]
+    fn test_capacity_for_overflow() {
+        capacity_for(usize::MAX);
+    }
+
     #[test]
     fn test_create_capacity_zero() {
         let mut m = HashMap::with_capacity(0);
@@ -7780,6 +8142,55 @@ mod test_map {
         assert_eq!(m.remove(&1), None);
     }
 
+    #[test]
+    fn test_try_values_mut_chunks() {
+        let mut m: HashMap<i32, i32> = (0..10).map(|x| (x, x * 10)).collect();
+
+        let chunks = m.try_values_mut_chunks(3).unwrap();
+        let sizes = chunks.iter().map(|c| c.len()).collect::<Vec<_>>();
+        assert_eq!(sizes, [4, 3, 3]);
+
+        thread::scope(|s| {
+            for chunk in chunks {
+                s.spawn(move || {
+                    for value in chunk {
+                        *value += 1;
+                    }
+                });
+            }
+        });
+
+        for (k, v) in &m {
+            assert_eq!(*v, k * 10 + 1);
+        }
+
+        let chunks = m.try_values_mut_chunks(100).unwrap();
+        assert_eq!(chunks.len(), 10);
+        assert!(chunks.iter().all(|c| c.len() == 1));
+
+        let mut empty: HashMap<i32, i32> = HashMap::new();
+        assert!(empty.try_values_mut_chunks(4).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_try_remove_all() {
+        let mut m: HashMap<i32, i32> = (0..8).map(|x| (x, x * 10)).collect();
+
+        assert_eq!(m.try_remove_all([1, 3, 3, 10, 5, -1]), 3);
+        assert_eq!(m.len(), 5);
+
+        for k in [0, 2, 4, 6, 7] {
+            assert!(m.contains_key(&k));
+        }
+
+        for k in [1, 3, 5] {
+            assert!(!m.contains_key(&k));
+        }
+
+        assert_eq!(m.try_remove_all(core::iter::empty::<i32>()), 0);
+        assert_eq!(m.len(), 5);
+    }
+
     #[test]
     fn test_remove_entry() {
         let mut m = HashMap::new();
@@ -7865,6 +8276,34 @@ mod test_map {
         assert!(values.contains(&'c'));
     }
 
+    #[test]
+    fn test_into_keys_and_values_drop_other_half() {
+        let value = Arc::new(());
+        let mut map = HashMap::new();
+
+        for n in 0..10 {
+            map.try_insert(n, value.clone()).abort();
+        }
+
+        let mut keys = map.into_keys().collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(keys, (0..10).collect::<Vec<_>>());
+        assert_eq!(Arc::strong_count(&value), 1);
+
+        let key = Arc::new(());
+        let mut map = HashMap::new();
+
+        for n in 0..10 {
+            map.try_insert((n, key.clone()), n).abort();
+        }
+
+        // Values which aren't consumed are dropped together with the keys.
+        let mut values = map.into_values();
+        assert!(values.next().is_some());
+        drop(values);
+        assert_eq!(Arc::strong_count(&key), 1);
+    }
+
     #[test]
     fn test_find() {
         let mut m = HashMap::new();
@@ -8336,6 +8775,25 @@ mod test_map {
         }
     }
 
+    #[test]
+    fn test_try_extend_ref() {
+        let mut a = HashMap::new();
+        a.insert(1, "one".to_string());
+        a.insert(2, "two".to_string());
+
+        let mut b = HashMap::new();
+        b.insert(2, "deux".to_string());
+        b.insert(3, "trois".to_string());
+
+        a.try_extend_ref(&b).unwrap();
+
+        assert_eq!(a.len(), 3);
+        assert_eq!(a[&1], "one");
+        assert_eq!(a[&2], "deux");
+        assert_eq!(a[&3], "trois");
+        assert_eq!(b.len(), 2);
+    }
+
     #[test]
     fn test_capacity_not_less_than_len() {
         let mut a = HashMap::new();
@@ -8812,6 +9270,38 @@ mod test_map {
         }
     }
 
+    #[test]
+    fn test_extract_if_retained() {
+        let mut map: HashMap<i32, i32> = (0..8).map(|x| (x, x * 10)).collect();
+
+        // Values can be modified by the predicate, regardless of whether the
+        // entry is extracted or not.
+        let mut out = map
+            .extract_if(|&k, v| {
+                *v += 1;
+                k >= 4
+            })
+            .collect::<Vec<_>>();
+        out.sort_unstable();
+        assert_eq!(rust_alloc::vec![(4, 41), (5, 51), (6, 61), (7, 71)], out);
+
+        let mut retained = map.iter().map(|(&k, &v)| (k, v)).collect::<Vec<_>>();
+        retained.sort_unstable();
+        assert_eq!(
+            rust_alloc::vec![(0, 1), (1, 11), (2, 21), (3, 31)],
+            retained
+        );
+
+        // Dropping the iterator early retains every element which has not yet
+        // been visited.
+        let mut extract_if = map.extract_if(|_, _| true);
+        let (first, _) = extract_if.next().unwrap();
+        drop(extract_if);
+
+        assert_eq!(map.len(), 3);
+        assert!(!map.contains_key(&first));
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)] // FIXME: no OOM signalling (https://github.com/rust-lang/miri/issues/613)
     fn test_try_reserve() {
@@ -9070,6 +9560,99 @@ mod test_map {
         }
     }
 
+    #[test]
+    fn test_probe_length_histogram() {
+        #[derive(Clone)]
+        struct ConstantHasher;
+
+        impl BuildHasher for ConstantHasher {
+            type Hasher = ConstantHasher;
+
+            fn build_hasher(&self) -> ConstantHasher {
+                ConstantHasher
+            }
+        }
+
+        impl core::hash::Hasher for ConstantHasher {
+            fn finish(&self) -> u64 {
+                0
+            }
+
+            fn write(&mut self, _: &[u8]) {}
+        }
+
+        let mut good = HashMap::new();
+        let mut bad = HashMap::with_hasher(ConstantHasher);
+
+        assert!(good.try_probe_length_histogram().unwrap().is_empty());
+        assert!(bad.try_probe_length_histogram().unwrap().is_empty());
+
+        for n in 0..256u32 {
+            good.try_insert(n, n).unwrap();
+            bad.try_insert(n, n).unwrap();
+        }
+
+        let good = good.try_probe_length_histogram().unwrap();
+        let bad = bad.try_probe_length_histogram().unwrap();
+
+        assert_eq!(good.iter().sum::<usize>(), 256);
+        assert_eq!(bad.iter().sum::<usize>(), 256);
+
+        // With every key hashing to the same bucket, each group is filled in
+        // probe order so the entries are spread over many probe lengths.
+        assert!(bad.len() > good.len());
+        assert!(good[0] > bad[0]);
+
+        let group = bad[0];
+
+        for &count in &bad[..bad.len() - 1] {
+            assert_eq!(count, group);
+        }
+    }
+
+    #[test]
+    fn test_try_entry_or_default() {
+        let mut counts = HashMap::<&str, usize>::new();
+
+        for word in ["a", "b", "a", "c", "a", "b"] {
+            *counts.try_entry_or_default(word).unwrap() += 1;
+        }
+
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts["a"], 3);
+        assert_eq!(counts["b"], 2);
+        assert_eq!(counts["c"], 1);
+
+        // Existing values are left untouched.
+        assert_eq!(*counts.try_entry_or_default("a").unwrap(), 3);
+        assert_eq!(counts.len(), 3);
+    }
+
+    #[test]
+    fn test_try_clone_in() {
+        use crate::testing::counting_alloc::CountingAlloc;
+
+        let mut source = HashMap::new();
+
+        for n in 0..32 {
+            source.try_insert(n, n.to_string()).unwrap();
+        }
+
+        let alloc = CountingAlloc::new();
+        let cloned = source.try_clone_in(alloc.clone()).unwrap();
+
+        // The table of the clone is allocated once in the new allocator.
+        assert_eq!(alloc.allocations(), 1);
+        assert_eq!(cloned.len(), source.len());
+
+        for (k, v) in &source {
+            assert_eq!(cloned.get(k), Some(v));
+        }
+
+        // Cloning back into the global allocator round trips.
+        assert_eq!(cloned.try_clone_in(Global).unwrap(), source);
+    }
+
     #[test]
     fn test_const_with_hasher() {
         #[derive(Clone)]
@@ -9119,6 +9702,48 @@ mod test_map {
         assert_eq!(ys, None);
     }
 
+    #[test]
+    fn test_allocation_size() {
+        let mut map: HashMap<u64, u64> = HashMap::new();
+        assert_eq!(map.allocation_size(), 0);
+
+        map.try_insert(0, 0).unwrap();
+        let size = map.allocation_size();
+        let capacity = map.capacity();
+        assert!(size >= capacity * core::mem::size_of::<(u64, u64)>());
+
+        for n in 1..=capacity as u64 {
+            map.try_insert(n, n).unwrap();
+        }
+
+        // Inserting past the capacity resizes the table.
+        assert!(map.capacity() > capacity);
+        assert!(map.allocation_size() > size);
+
+        map.clear();
+        map.shrink_to_fit();
+        assert_eq!(map.allocation_size(), 0);
+    }
+
+    #[test]
+    fn test_get_pair_mut() {
+        let mut map = HashMap::new();
+        map.try_insert("foo".to_owned(), 0).unwrap();
+        map.try_insert("bar".to_owned(), 10).unwrap();
+        map.try_insert("baz".to_owned(), 20).unwrap();
+
+        let (a, b) = map.get_pair_mut("foo", "baz").unwrap();
+        core::mem::swap(a, b);
+
+        assert_eq!(map["foo"], 20);
+        assert_eq!(map["bar"], 10);
+        assert_eq!(map["baz"], 0);
+
+        assert_eq!(map.get_pair_mut("foo", "dud"), None);
+        assert_eq!(map.get_pair_mut("dud", "foo"), None);
+        assert_eq!(map.get_pair_mut("bar", "bar"), None);
+    }
+
     #[test]
     #[should_panic = "panic in drop"]
     fn test_clone_from_double_drop() {
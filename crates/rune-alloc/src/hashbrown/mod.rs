@@ -41,6 +41,44 @@ use core::marker::PhantomData;
 
 use crate::error::CustomError;
 
+/// Returns the capacity a [`HashMap`] or [`HashSet`] would actually have if it
+/// was constructed to hold at least `requested` elements, such as through
+/// [`HashMap::try_with_capacity`].
+///
+/// The underlying table rounds the number of buckets up to a power of two and
+/// keeps some of them empty, so the returned capacity is never less than
+/// `requested`.
+///
+/// # Panics
+///
+/// Panics if `requested` is so large that no table could hold it. Such a
+/// capacity would also cause [`HashMap::try_with_capacity`] to fail.
+///
+/// # Examples
+///
+/// ```
+/// use rune::alloc::HashMap;
+/// use rune_alloc::hashbrown::capacity_for;
+///
+/// assert_eq!(capacity_for(0), 0);
+/// assert_eq!(capacity_for(5), 7);
+///
+/// let map = HashMap::<u32, u32>::try_with_capacity(100)?;
+/// assert_eq!(capacity_for(100), map.capacity());
+/// # Ok::<_, rune::alloc::Error>(())
+/// ```
+pub fn capacity_for(requested: usize) -> usize {
+    if requested == 0 {
+        return 0;
+    }
+
+    let Some(buckets) = raw::capacity_to_buckets(requested) else {
+        panic!("Hash table capacity overflow");
+    };
+
+    raw::bucket_mask_to_capacity(buckets - 1)
+}
+
 /// Trait used to implement custom equality implementations which are not solely
 /// based on traits.
 pub trait EqFn<C: ?Sized, T: ?Sized, E> {
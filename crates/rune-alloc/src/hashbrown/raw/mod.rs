@@ -145,7 +145,7 @@ impl ProbeSeq {
 // Workaround for emscripten bug emscripten-core/emscripten-fastcomp#258
 #[cfg_attr(target_os = "emscripten", inline(never))]
 #[cfg_attr(not(target_os = "emscripten"), inline)]
-fn capacity_to_buckets(cap: usize) -> Option<usize> {
+pub(super) fn capacity_to_buckets(cap: usize) -> Option<usize> {
     debug_assert_ne!(cap, 0);
 
     // For small tables we require at least 1 empty bucket so that lookups are
@@ -172,7 +172,7 @@ fn capacity_to_buckets(cap: usize) -> Option<usize> {
 /// Returns the maximum effective capacity for the given bucket mask, taking
 /// the maximum load factor into account.
 #[inline]
-fn bucket_mask_to_capacity(bucket_mask: usize) -> usize {
+pub(super) fn bucket_mask_to_capacity(bucket_mask: usize) -> usize {
     if bucket_mask < 8 {
         // For tables with 1/2/4/8 buckets, we always reserve one empty slot.
         // Keep in mind that the bucket mask is one less than the bucket count.
@@ -1461,6 +1461,14 @@ where
         self.table.is_bucket_full(index)
     }
 
+    /// Returns the number of groups probed past the first one before the
+    /// bucket at `index` is reached when looking up an element with the given
+    /// `hash`.
+    #[inline]
+    pub fn probe_length(&self, hash: u64, index: usize) -> usize {
+        self.table.probe_length(hash, index)
+    }
+
     /// Returns an iterator over every element in the table. It is up to
     /// the caller to ensure that the `RawTable` outlives the `RawIter`.
     /// Because we cannot make the `next` method unsafe on the `RawIter`
@@ -2235,6 +2243,20 @@ impl RawTableInner {
         probe_index(i) == probe_index(new_i)
     }
 
+    #[inline]
+    fn probe_length(&self, hash: u64, index: usize) -> usize {
+        let mut probe_seq = self.probe_seq(hash);
+        let mut length = 0;
+
+        // NB: The probe sequence visits every group, so this terminates.
+        while index.wrapping_sub(probe_seq.pos) & self.bucket_mask >= Group::WIDTH {
+            probe_seq.move_next(self.bucket_mask);
+            length += 1;
+        }
+
+        length
+    }
+
     /// Sets a control byte to the hash, and possibly also the replicated control byte at
     /// the end of the array.
     ///
@@ -11,6 +11,7 @@ use crate::error::Error;
 use crate::iter::{TryExtend, TryFromIteratorIn};
 #[cfg(test)]
 use crate::testing::*;
+use crate::vec::Vec;
 
 use super::map::{self, DefaultHashBuilder, ExtractIfInner, HashMap, Keys};
 use super::raw::RawTable;
@@ -128,8 +129,8 @@ pub struct HashSet<T, S = DefaultHashBuilder, A: Allocator = Global> {
 
 impl<T, S, A: Allocator + Clone> TryClone for HashSet<T, S, A>
 where
-    T: TryClone,
-    S: Clone,
+    T: TryClone + Hash,
+    S: BuildHasher + Clone,
 {
     fn try_clone(&self) -> Result<Self, Error> {
         Ok(HashSet {
@@ -146,8 +147,8 @@ where
 #[cfg(test)]
 impl<T, S, A: Allocator + Clone> Clone for HashSet<T, S, A>
 where
-    T: TryClone,
-    S: Clone,
+    T: TryClone + Hash,
+    S: BuildHasher + Clone,
 {
     fn clone(&self) -> Self {
         self.try_clone().abort()
@@ -226,6 +227,55 @@ impl<T> HashSet<T, DefaultHashBuilder> {
     pub(crate) fn with_capacity(capacity: usize) -> Self {
         Self::try_with_capacity(capacity).abort()
     }
+
+    /// Creates a `HashSet` from an iterator of values which are sorted in
+    /// strictly increasing order, and are therefore unique.
+    ///
+    /// This is faster than collecting into a set, since values are inserted
+    /// through [`try_insert_unique_unchecked`] without checking if they are
+    /// already present.
+    ///
+    /// In debug builds the input is verified to be strictly increasing, which
+    /// panics if it is not. In release builds it is a logic error to provide
+    /// values which are not unique, which has the same unspecified but memory
+    /// safe consequences as [`try_insert_unique_unchecked`].
+    ///
+    /// [`try_insert_unique_unchecked`]: HashSet::try_insert_unique_unchecked
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashSet;
+    ///
+    /// let set = HashSet::try_from_sorted_unique_iter([1, 2, 3, 5, 8])?;
+    /// assert_eq!(set.len(), 5);
+    /// assert!(set.contains(&5));
+    /// assert!(!set.contains(&4));
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_from_sorted_unique_iter<I>(iter: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Eq + Hash + Ord,
+    {
+        let iter = iter.into_iter();
+        let mut set = Self::try_with_capacity(iter.size_hint().0)?;
+        let mut iter = iter.peekable();
+
+        while let Some(value) = iter.next() {
+            debug_assert!(
+                match iter.peek() {
+                    Some(next) => value < *next,
+                    None => true,
+                },
+                "input must be sorted in strictly increasing order"
+            );
+
+            set.try_insert_unique_unchecked(value)?;
+        }
+
+        Ok(set)
+    }
 }
 
 impl<T, A> HashSet<T, DefaultHashBuilder, A>
@@ -299,6 +349,40 @@ where
             map: HashMap::try_with_capacity_in(capacity, alloc)?,
         })
     }
+
+    /// Creates a `HashSet` from an iterator which is known to produce `len`
+    /// unique elements.
+    ///
+    /// Capacity for `len` elements is allocated up front, so unlike
+    /// collecting the iterator the set doesn't have to grow while the
+    /// elements are inserted. This is useful when the number of elements is
+    /// known ahead of time, such as when it's prefixed to serialized data. If
+    /// the iterator produces more unique elements than `len`, the set grows
+    /// as needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashSet;
+    /// use rune::alloc::alloc::Global;
+    ///
+    /// let set: HashSet<i32> = HashSet::try_from_iter_exact_in([1, 2, 3], 3, Global)?;
+    /// assert_eq!(set.len(), 3);
+    /// assert!(set.contains(&2));
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_from_iter_exact_in<I>(iter: I, len: usize, alloc: A) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut set = Self::try_with_capacity_in(len, alloc)?;
+
+        for value in iter {
+            set.try_insert(value)?;
+        }
+
+        Ok(set)
+    }
 }
 
 impl<T, S, A> HashSet<T, S, A>
@@ -321,9 +405,38 @@ where
         self.map.capacity()
     }
 
+    /// Returns the number of bytes currently allocated by the set to store
+    /// its elements, including the control bytes used by the table.
+    ///
+    /// This doesn't include any memory allocated by the elements themselves,
+    /// and is `0` if the set hasn't allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashSet;
+    ///
+    /// let mut set: HashSet<i32> = HashSet::new();
+    /// assert_eq!(set.allocation_size(), 0);
+    ///
+    /// set.try_insert(1)?;
+    /// assert!(set.allocation_size() > 0);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn allocation_size(&self) -> usize {
+        self.map.allocation_size()
+    }
+
     /// An iterator visiting all elements in arbitrary order.
     /// The iterator element type is `&'a T`.
     ///
+    /// The iterator only supports forward iteration, since the underlying
+    /// table is scanned one group of control bytes at a time from the start.
+    /// Since the order is arbitrary there's no meaningful reverse order, but
+    /// the elements can be collected first if they need to be consumed from
+    /// both ends.
+    ///
     /// # Examples
     ///
     /// ```
@@ -346,6 +459,46 @@ where
         }
     }
 
+    /// An iterator visiting all elements in sorted order, which is stable
+    /// regardless of the order in which elements were inserted.
+    /// The iterator element type is `&'a T`.
+    ///
+    /// This collects references to every element and sorts them, which
+    /// allocates and costs *O*(*n* \* log(*n*)) time. It is intended for
+    /// producing reproducible output such as when displaying or serializing
+    /// a set, and should be avoided in hot paths where [`iter`] is
+    /// preferable.
+    ///
+    /// [`iter`]: HashSet::iter
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashSet;
+    ///
+    /// let mut set = HashSet::new();
+    /// set.try_insert("c")?;
+    /// set.try_insert("a")?;
+    /// set.try_insert("b")?;
+    ///
+    /// let values = set.try_iter_stable()?.copied().collect::<Vec<_>>();
+    /// assert_eq!(values, ["a", "b", "c"]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_iter_stable(&self) -> Result<impl Iterator<Item = &'_ T>, Error>
+    where
+        T: Ord,
+    {
+        let mut values = Vec::try_with_capacity(self.len())?;
+
+        for value in self.iter() {
+            values.try_push(value)?;
+        }
+
+        values.sort_unstable();
+        Ok(values.into_iter())
+    }
+
     /// Returns the number of elements in the set.
     ///
     /// # Examples
@@ -696,6 +849,34 @@ where
     S: BuildHasher,
     A: Allocator,
 {
+    /// Clones the set into a new set which uses the given allocator.
+    ///
+    /// Unlike [`TryClone::try_clone`], which allocates the clone using the
+    /// allocator of the original set, this allows moving the contents of the
+    /// set into a different allocator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashSet;
+    /// use rune::alloc::alloc::Global;
+    ///
+    /// let a: HashSet<_> = HashSet::try_from([1, 2, 3])?;
+    /// let b = a.try_clone_in(Global)?;
+    /// assert_eq!(a, b);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_clone_in<B>(&self, alloc: B) -> Result<HashSet<T, S, B>, Error>
+    where
+        T: TryClone,
+        S: Clone,
+        B: Allocator,
+    {
+        Ok(HashSet {
+            map: self.map.try_clone_in(alloc)?,
+        })
+    }
+
     /// Tries to reserve capacity for at least `additional` more elements to be inserted
     /// in the given `HashSet<K,V>`. The collection may reserve more space to avoid
     /// frequent reallocations.
@@ -841,6 +1022,40 @@ where
         }
     }
 
+    /// Modifies `self` in place to contain the symmetric difference of
+    /// `self` and `other`, i.e., the values that were in `self` or in `other`
+    /// but not in both.
+    ///
+    /// Values which are in both sets are removed from `self`, and values
+    /// which are only in `other` are cloned into it. This makes it possible
+    /// to accumulate a symmetric difference without constructing a new set
+    /// each time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashSet;
+    ///
+    /// let mut a: HashSet<_> = HashSet::try_from([1, 2, 3])?;
+    /// let b: HashSet<_> = HashSet::try_from([4, 2, 3, 4])?;
+    ///
+    /// a.try_symmetric_difference_into(&b)?;
+    /// assert_eq!(a, HashSet::try_from([1, 4])?);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_symmetric_difference_into(&mut self, other: &Self) -> Result<(), Error>
+    where
+        T: TryClone,
+    {
+        for value in other {
+            if !self.remove(value) {
+                self.try_insert(value.try_clone()?)?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Visits the values representing the intersection,
     /// i.e., the values that are both in `self` and `other`.
     ///
@@ -875,6 +1090,67 @@ where
         }
     }
 
+    /// Returns an upper bound on the number of values in the intersection
+    /// of `self` and `other`, which is the length of the smaller set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashSet;
+    ///
+    /// let a: HashSet<_> = HashSet::try_from([1, 2, 3])?;
+    /// let b: HashSet<_> = HashSet::try_from([2, 3, 4, 5])?;
+    ///
+    /// assert_eq!(a.intersection_upper_bound(&b), 3);
+    /// assert!(a.intersection(&b).count() <= a.intersection_upper_bound(&b));
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn intersection_upper_bound(&self, other: &Self) -> usize {
+        self.len().min(other.len())
+    }
+
+    /// Constructs a new set containing clones of the values which are both
+    /// in `self` and `other`.
+    ///
+    /// Capacity for [`intersection_upper_bound`] values is reserved up
+    /// front, so the set is never reallocated while it's being populated.
+    ///
+    /// [`intersection_upper_bound`]: HashSet::intersection_upper_bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashSet;
+    ///
+    /// let a: HashSet<_> = HashSet::try_from([1, 2, 3])?;
+    /// let b: HashSet<_> = HashSet::try_from([4, 2, 3, 4])?;
+    ///
+    /// let intersection = a.try_intersection_set(&b)?;
+    /// assert_eq!(intersection, HashSet::try_from([2, 3])?);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_intersection_set(&self, other: &Self) -> Result<Self, Error>
+    where
+        T: TryClone,
+        S: Clone,
+        A: Clone,
+    {
+        let mut set = Self::try_with_capacity_and_hasher_in(
+            self.intersection_upper_bound(other),
+            self.hasher().clone(),
+            self.allocator().clone(),
+        )?;
+
+        for value in self.intersection(other) {
+            // Values in the intersection are unique, since they come from
+            // one set.
+            set.try_insert_unique_unchecked(value.try_clone()?)?;
+        }
+
+        Ok(set)
+    }
+
     /// Visits the values representing the union,
     /// i.e., all the values in `self` or `other`, without duplicates.
     ///
@@ -1322,6 +1598,30 @@ where
             None => None,
         }
     }
+
+    /// Removes every value produced by the given iterator from the set,
+    /// returning the number of values which were present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::HashSet;
+    ///
+    /// let mut set: HashSet<i32> = HashSet::try_from([1, 2, 3])?;
+    ///
+    /// assert_eq!(set.try_remove_all([1, 3, 5]), 2);
+    /// assert_eq!(set.len(), 1);
+    /// assert!(set.contains(&2));
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn try_remove_all<I, Q>(&mut self, values: I) -> usize
+    where
+        I: IntoIterator<Item = Q>,
+        Q: Hash + Equivalent<T>,
+    {
+        self.map.try_remove_all(values)
+    }
 }
 
 impl<T, S, A> HashSet<T, S, A>
@@ -2534,6 +2834,8 @@ mod test_set {
     use rust_alloc::vec::Vec;
     use rust_alloc::{format, vec};
 
+    use crate::testing::counting_alloc::CountingAlloc;
+
     #[test]
     fn test_zero_capacities() {
         type HS = HashSet<i32>;
@@ -2709,6 +3011,35 @@ mod test_set {
         assert_eq!(i, expected.len());
     }
 
+    #[test]
+    fn test_try_symmetric_difference_into() {
+        use crate::clone::TryClone;
+        use crate::iter::IteratorExt;
+
+        let a: HashSet<i32> = HashSet::try_from([1, 3, 5, 9, 11]).unwrap();
+        let b: HashSet<i32> = HashSet::try_from([-2, 3, 9, 14, 22]).unwrap();
+        let c: HashSet<i32> = HashSet::try_from([1, 14, 30]).unwrap();
+
+        let expected: HashSet<i32> = a.symmetric_difference(&b).copied().try_collect().unwrap();
+
+        let mut acc = a.try_clone().unwrap();
+        acc.try_symmetric_difference_into(&b).unwrap();
+        assert_eq!(acc, expected);
+
+        let expected: HashSet<i32> = expected
+            .symmetric_difference(&c)
+            .copied()
+            .try_collect()
+            .unwrap();
+
+        acc.try_symmetric_difference_into(&c).unwrap();
+        assert_eq!(acc, expected);
+
+        // The symmetric difference of a set with itself is empty.
+        acc.try_symmetric_difference_into(&expected).unwrap();
+        assert!(acc.is_empty());
+    }
+
     #[test]
     fn test_union() {
         let mut a = HashSet::new();
@@ -2928,6 +3259,185 @@ mod test_set {
         assert!(set.contains(&6));
     }
 
+    #[test]
+    fn test_try_from_sorted_unique_iter() {
+        let values = (0..100).map(|x| x * 3).collect::<Vec<_>>();
+
+        let sorted = HashSet::try_from_sorted_unique_iter(values.iter().copied()).unwrap();
+        let normal: HashSet<i32> = values.iter().copied().collect();
+
+        assert_eq!(sorted.len(), normal.len());
+        assert_eq!(sorted, normal);
+
+        for x in -10..310 {
+            assert_eq!(sorted.contains(&x), normal.contains(&x), "{x}");
+        }
+
+        let empty = HashSet::<i32>::try_from_sorted_unique_iter([]).unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic = "input must be sorted in strictly increasing order"]
+    fn test_try_from_sorted_unique_iter_unsorted() {
+        let _ = HashSet::try_from_sorted_unique_iter([1, 3, 3, 4]);
+    }
+
+    #[test]
+    fn test_try_remove_all() {
+        let mut set: HashSet<i32> = (0..8).collect();
+        assert_eq!(set.try_remove_all([0, 2, 4, 8, 16, 2]), 3);
+        assert_eq!(set.len(), 5);
+        assert!(!set.contains(&0));
+        assert!(!set.contains(&2));
+        assert!(!set.contains(&4));
+        assert!(set.contains(&1));
+        assert!(set.contains(&6));
+    }
+
+    #[test]
+    fn test_try_clone_from_reuses_allocation() {
+        use crate::clone::TryClone;
+
+        let alloc = CountingAlloc::new();
+
+        let mut source = HashSet::new_in(alloc.clone());
+        let mut target = HashSet::try_with_capacity_in(64, alloc.clone()).unwrap();
+        let capacity = target.capacity();
+
+        for frame in 0..4 {
+            source.clear();
+
+            for n in 0..8 {
+                source.try_insert(frame * 10 + n).unwrap();
+            }
+
+            let before = alloc.allocations();
+            target.try_clone_from(&source).unwrap();
+
+            assert_eq!(alloc.allocations(), before, "target should not reallocate");
+            assert_eq!(target.capacity(), capacity);
+            assert_eq!(target, source);
+        }
+
+        // A target which is too small has to allocate.
+        let mut small = HashSet::new_in(alloc.clone());
+        let before = alloc.allocations();
+        small.try_clone_from(&source).unwrap();
+        assert!(alloc.allocations() > before);
+        assert_eq!(small, source);
+    }
+
+    #[test]
+    fn test_try_clone_in() {
+        use crate::alloc::Global;
+
+        let mut source = HashSet::new();
+
+        for n in 0..32 {
+            source.try_insert(n).unwrap();
+        }
+
+        let alloc = CountingAlloc::new();
+        let cloned = source.try_clone_in(alloc.clone()).unwrap();
+
+        assert_eq!(alloc.allocations(), 1);
+        assert_eq!(cloned.len(), source.len());
+        assert!(source.iter().all(|value| cloned.contains(value)));
+
+        assert_eq!(cloned.try_clone_in(Global).unwrap(), source);
+    }
+
+    #[test]
+    fn test_try_from_iter_exact_in() {
+        let alloc = CountingAlloc::new();
+
+        for len in [0, 1, 7, 100, 1000] {
+            alloc.reset();
+
+            let set = HashSet::try_from_iter_exact_in(0..len, len, alloc.clone()).unwrap();
+
+            assert_eq!(set.len(), len);
+            assert!((0..len).all(|n| set.contains(&n)));
+            assert_eq!(alloc.allocations(), usize::from(len > 0), "len {len}");
+        }
+
+        // Producing more elements than expected grows the set.
+        alloc.reset();
+        let set = HashSet::try_from_iter_exact_in(0..100, 1, alloc.clone()).unwrap();
+        assert_eq!(set.len(), 100);
+        assert!(alloc.allocations() > 1);
+    }
+
+    #[test]
+    fn test_allocation_size() {
+        let mut set: HashSet<u64> = HashSet::new();
+        assert_eq!(set.allocation_size(), 0);
+
+        set.try_insert(0).unwrap();
+        let size = set.allocation_size();
+        let capacity = set.capacity();
+
+        for n in 1..=capacity as u64 {
+            set.try_insert(n).unwrap();
+        }
+
+        assert!(set.allocation_size() > size);
+    }
+
+    #[test]
+    fn test_intersection_upper_bound() {
+        use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+        let mut rng = SmallRng::seed_from_u64(0x1234_5678);
+
+        for _ in 0..100 {
+            let mut a = HashSet::new();
+            let mut b = HashSet::new();
+
+            for _ in 0..rng.random_range(0..32) {
+                a.try_insert(rng.random_range(0..64)).unwrap();
+            }
+
+            for _ in 0..rng.random_range(0..32) {
+                b.try_insert(rng.random_range(0..64)).unwrap();
+            }
+
+            let bound = a.intersection_upper_bound(&b);
+            assert_eq!(bound, b.intersection_upper_bound(&a));
+
+            let intersection = a.try_intersection_set(&b).unwrap();
+            assert!(intersection.len() <= bound);
+            assert!(intersection.capacity() >= bound);
+            assert_eq!(intersection.len(), a.intersection(&b).count());
+            assert!(intersection.iter().all(|x| a.contains(x) && b.contains(x)));
+        }
+    }
+
+    #[test]
+    fn test_try_iter_stable() {
+        let mut a = HashSet::new();
+        let mut b = HashSet::new();
+
+        for x in 0..64 {
+            a.try_insert(x * 7 % 64).unwrap();
+        }
+
+        for x in (0..64).rev() {
+            b.try_insert(x).unwrap();
+        }
+
+        let a = a.try_iter_stable().unwrap().copied().collect::<Vec<_>>();
+        let b = b.try_iter_stable().unwrap().copied().collect::<Vec<_>>();
+
+        assert_eq!(a, b);
+        assert_eq!(a, (0..64).collect::<Vec<_>>());
+
+        let empty = HashSet::<i32>::new();
+        assert_eq!(empty.try_iter_stable().unwrap().next(), None);
+    }
+
     #[test]
     fn test_extract_if() {
         {
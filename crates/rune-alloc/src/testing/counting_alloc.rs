@@ -0,0 +1,55 @@
+use core::alloc::Layout;
+use core::cell::Cell;
+use core::ptr::NonNull;
+
+use rust_alloc::rc::Rc;
+
+use crate::alloc::{AllocError, Allocator, Global};
+
+/// An allocator which counts the number of allocations and deallocations made
+/// through it, shared between all of its clones.
+#[derive(Default, Clone)]
+pub struct CountingAlloc {
+    allocations: Rc<Cell<usize>>,
+    deallocations: Rc<Cell<usize>>,
+}
+
+impl CountingAlloc {
+    /// Construct a new allocator without any recorded calls.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of allocations made.
+    pub fn allocations(&self) -> usize {
+        self.allocations.get()
+    }
+
+    /// The number of deallocations made.
+    pub fn deallocations(&self) -> usize {
+        self.deallocations.get()
+    }
+
+    /// The number of calls made to the allocator.
+    pub fn calls(&self) -> usize {
+        self.allocations() + self.deallocations()
+    }
+
+    /// Reset all counts.
+    pub fn reset(&self) {
+        self.allocations.set(0);
+        self.deallocations.set(0);
+    }
+}
+
+unsafe impl Allocator for CountingAlloc {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.allocations.set(self.allocations.get() + 1);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.deallocations.set(self.deallocations.get() + 1);
+        Global.deallocate(ptr, layout)
+    }
+}
@@ -7,6 +7,7 @@
 
 #![allow(clippy::redundant_closure)]
 
+use core::array;
 use core::cmp::{self, Ordering};
 use core::fmt;
 use core::hash::{Hash, Hasher};
@@ -22,6 +23,7 @@ use core::slice;
 use core::mem;
 
 use crate::alloc::{Allocator, Global, SizedTypeProperties};
+use crate::boxed::Box;
 use crate::clone::TryClone;
 use crate::error::Error;
 use crate::iter::{TryExtend, TryFromIteratorIn};
@@ -52,6 +54,10 @@ pub use self::raw_iter::RawIter;
 
 mod raw_iter;
 
+pub use self::windows::Windows;
+
+mod windows;
+
 /// A double-ended queue implemented with a growable ring buffer.
 ///
 /// The "default" usage of this type as a queue is to use [`try_push_back`] to add to
@@ -209,12 +215,6 @@ where
         }
     }
 
-    /// Returns `true` if the buffer is at full capacity.
-    #[inline]
-    fn is_full(&self) -> bool {
-        self.len == self.capacity()
-    }
-
     /// Returns the index in the underlying buffer for a given logical element
     /// index + addend.
     #[inline]
@@ -508,17 +508,46 @@ impl<T> VecDeque<T> {
 
     /// Creates an empty deque with space for at least `capacity` elements.
     ///
+    /// Exactly `capacity` elements are requested from the allocator, unlike
+    /// when the deque grows where the capacity is amortized. So for types
+    /// which aren't zero-sized the capacity of the returned deque is exactly
+    /// `capacity`.
+    ///
     /// # Examples
     ///
     /// ```
     /// use rune::alloc::VecDeque;
     ///
     /// let deque: VecDeque<u32> = VecDeque::try_with_capacity(10)?;
+    /// assert_eq!(deque.capacity(), 10);
     /// # Ok::<_, rune::alloc::Error>(())
     /// ```
     pub fn try_with_capacity(capacity: usize) -> Result<Self, Error> {
         Self::try_with_capacity_in(capacity, Global)
     }
+
+    /// Creates a deque by consuming the given iterator from its back, so that
+    /// the deque holds the elements of the iterator in reverse order.
+    ///
+    /// Space is preallocated from the size hint of the iterator, which makes
+    /// this useful for reversing a stream of values without collecting them
+    /// into a temporary buffer first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let deque = VecDeque::try_from_back_iter(1..=4)?;
+    /// assert_eq!(deque, [4, 3, 2, 1]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_from_back_iter<I>(iter: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = T, IntoIter: DoubleEndedIterator>,
+    {
+        Self::try_from_back_iter_in(iter, Global)
+    }
 }
 
 impl<T, A> VecDeque<T, A>
@@ -562,6 +591,37 @@ where
         })
     }
 
+    /// Creates a deque in the provided allocator by consuming the given
+    /// iterator from its back, so that the deque holds the elements of the
+    /// iterator in reverse order.
+    ///
+    /// Space is preallocated from the size hint of the iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    /// use rune::alloc::alloc::Global;
+    ///
+    /// let deque = VecDeque::try_from_back_iter_in(['a', 'b', 'c'], Global)?;
+    /// assert_eq!(deque, ['c', 'b', 'a']);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_from_back_iter_in<I>(iter: I, alloc: A) -> Result<VecDeque<T, A>, Error>
+    where
+        I: IntoIterator<Item = T, IntoIter: DoubleEndedIterator>,
+    {
+        let mut iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        let mut this = VecDeque::try_with_capacity_in(lower, alloc)?;
+
+        while let Some(value) = iter.next_back() {
+            this.try_push_back(value)?;
+        }
+
+        Ok(this)
+    }
+
     /// Provides a reference to the element at the given index.
     ///
     /// Element at index 0 is the front of the queue.
@@ -625,6 +685,54 @@ where
         }
     }
 
+    /// Provides a reference to the element at the given index counted from
+    /// the back of the queue.
+    ///
+    /// Element at index 0 is the back of the queue. Returns `None` if `index`
+    /// is out of bounds, which includes every index if the deque is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let buf: VecDeque<_> = [3, 4, 5, 6].try_into()?;
+    ///
+    /// assert_eq!(buf.get_back(0), Some(&6));
+    /// assert_eq!(buf.get_back(1), Some(&5));
+    /// assert_eq!(buf.get_back(4), None);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn get_back(&self, index: usize) -> Option<&T> {
+        let index = self.len.checked_sub(index)?.checked_sub(1)?;
+        self.get(index)
+    }
+
+    /// Provides a mutable reference to the element at the given index counted
+    /// from the back of the queue.
+    ///
+    /// Element at index 0 is the back of the queue. Returns `None` if `index`
+    /// is out of bounds, which includes every index if the deque is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut buf: VecDeque<_> = [3, 4, 5, 6].try_into()?;
+    ///
+    /// if let Some(elem) = buf.get_back_mut(1) {
+    ///     *elem = 7;
+    /// }
+    ///
+    /// assert_eq!(buf, [3, 4, 7, 6]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn get_back_mut(&mut self, index: usize) -> Option<&mut T> {
+        let index = self.len.checked_sub(index)?.checked_sub(1)?;
+        self.get_mut(index)
+    }
+
     /// Swaps elements at indices `i` and `j`.
     ///
     /// `i` and `j` may be equal.
@@ -661,6 +769,47 @@ where
         unsafe { ptr::swap(self.ptr().add(ri), self.ptr().add(rj)) }
     }
 
+    /// Swaps the elements of the ranges `a` and `b`.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either range is out of bounds, if the ranges have different
+    /// lengths, or if they overlap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut buf: VecDeque<_> = [1, 2, 3, 4, 5, 6, 7].try_into()?;
+    ///
+    /// buf.swap_ranges(0..2, 4..6);
+    ///
+    /// assert_eq!(buf, [5, 6, 3, 4, 1, 2, 7]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn swap_ranges(&mut self, a: Range<usize>, b: Range<usize>) {
+        assert!(
+            a.start <= a.end && a.end <= self.len,
+            "range a out of bounds"
+        );
+        assert!(
+            b.start <= b.end && b.end <= self.len,
+            "range b out of bounds"
+        );
+        assert_eq!(a.len(), b.len(), "ranges must have the same length");
+        assert!(
+            a.is_empty() || a.end <= b.start || b.end <= a.start,
+            "ranges must not overlap"
+        );
+
+        for (i, j) in a.zip(b) {
+            self.swap(i, j);
+        }
+    }
+
     /// Returns the number of elements the deque can hold without reallocating.
     ///
     /// # Examples
@@ -681,6 +830,117 @@ where
         }
     }
 
+    /// Returns a raw pointer to the start of the deque's ring buffer.
+    ///
+    /// The front element of the deque is stored at offset
+    /// [`physical_head`], and the following elements are stored after it.
+    /// Note that the elements wrap around to the start of the buffer once
+    /// they reach its [`capacity`], so they're not necessarily contiguous.
+    /// Use [`as_slices`] to access the two halves safely, or
+    /// [`make_contiguous`] to ensure that the elements aren't split.
+    ///
+    /// The caller must ensure that the deque outlives the pointer this
+    /// function returns, or else it will end up pointing to garbage.
+    /// Modifying the deque may cause its buffer to be reallocated, which
+    /// would also make any pointers to it invalid.
+    ///
+    /// [`physical_head`]: VecDeque::physical_head
+    /// [`capacity`]: VecDeque::capacity
+    /// [`as_slices`]: VecDeque::as_slices
+    /// [`make_contiguous`]: VecDeque::make_contiguous
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut buf = VecDeque::try_with_capacity(4)?;
+    /// buf.try_push_back(1)?;
+    /// buf.try_push_back(2)?;
+    /// buf.pop_front();
+    ///
+    /// let front = unsafe { *buf.as_ptr().add(buf.physical_head()) };
+    /// assert_eq!(front, 2);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr()
+    }
+
+    /// Returns a raw mutable pointer to the start of the deque's ring buffer.
+    ///
+    /// See [`as_ptr`] for how the elements are laid out in the buffer.
+    ///
+    /// [`as_ptr`]: VecDeque::as_ptr
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut buf: VecDeque<_> = [1, 2, 3].try_into()?;
+    /// let head = buf.physical_head();
+    ///
+    /// unsafe {
+    ///     *buf.as_mut_ptr().add(head) = 4;
+    /// }
+    ///
+    /// assert_eq!(buf, [4, 2, 3]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.ptr()
+    }
+
+    /// Returns the index in the ring buffer where the front element of the
+    /// deque is stored.
+    ///
+    /// See [`as_ptr`] for how the elements are laid out in the buffer.
+    ///
+    /// [`as_ptr`]: VecDeque::as_ptr
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut buf = VecDeque::try_with_capacity(4)?;
+    /// buf.try_push_back(1)?;
+    /// assert_eq!(buf.physical_head(), 0);
+    ///
+    /// buf.try_push_front(0)?;
+    /// assert_eq!(buf.physical_head(), buf.capacity() - 1);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn physical_head(&self) -> usize {
+        self.head
+    }
+
+    /// Returns the number of elements which can be pushed onto the deque
+    /// without reallocating.
+    ///
+    /// For zero-sized types the capacity of the deque is `usize::MAX`, so
+    /// this is the number of elements that can be added before the length
+    /// overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut buf: VecDeque<i32> = VecDeque::try_with_capacity(10)?;
+    /// buf.try_push_back(1)?;
+    /// assert_eq!(buf.remaining_capacity(), buf.capacity() - 1);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn remaining_capacity(&self) -> usize {
+        self.capacity() - self.len
+    }
+
     /// Tries to reserve the minimum capacity for at least `additional` more elements to
     /// be inserted in the given deque. After calling `try_reserve_exact`,
     /// capacity will be greater than or equal to `self.len() + additional` if
@@ -789,6 +1049,9 @@ where
     /// It will drop down as close as possible to the length but the allocator may still inform the
     /// deque that there is space for a few more elements.
     ///
+    /// If the capacity of the deque already matches its length, this does
+    /// nothing and the allocator is not called.
+    ///
     /// # Examples
     ///
     /// ```
@@ -806,6 +1069,50 @@ where
         self.try_shrink_to(0)
     }
 
+    /// Shrinks the capacity of the deque as much as possible, storing all
+    /// elements contiguously from the start of the buffer.
+    ///
+    /// Unlike [`try_shrink_to_fit`], which might leave the elements wrapped
+    /// around the end of the buffer, this guarantees that all elements are
+    /// in the first slice returned by [`as_slices`] afterwards.
+    ///
+    /// [`try_shrink_to_fit`]: VecDeque::try_shrink_to_fit
+    /// [`as_slices`]: VecDeque::as_slices
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut buf = VecDeque::try_with_capacity(15)?;
+    ///
+    /// buf.try_push_back(2)?;
+    /// buf.try_push_back(3)?;
+    /// buf.try_push_front(1)?;
+    ///
+    /// buf.try_shrink_to_fit_contiguous()?;
+    /// assert!(buf.capacity() >= 3);
+    /// assert_eq!(buf.as_slices(), (&[1, 2, 3][..], &[][..]));
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_shrink_to_fit_contiguous(&mut self) -> Result<(), Error> {
+        self.make_contiguous();
+
+        if self.head != 0 {
+            unsafe {
+                // can't use `copy_nonoverlapping()` here because the elements
+                // might overlap with their new location.
+                self.copy(self.head, 0, self.len);
+            }
+
+            self.head = 0;
+        }
+
+        // With the head at the start of the buffer no elements are moved
+        // while shrinking.
+        self.try_shrink_to_fit()
+    }
+
     /// Shrinks the capacity of the deque with a lower bound.
     ///
     /// The capacity will remain at least as large as both the length
@@ -974,6 +1281,42 @@ where
         }
     }
 
+    /// Shortens the deque, keeping the first `len` elements and passing the
+    /// rest by value to `on_drop` in order from front to back.
+    ///
+    /// If `len` is greater than or equal to the deque's current length, this
+    /// has no effect.
+    ///
+    /// If `on_drop` panics, the elements which have not yet been passed to it
+    /// are dropped and the deque is still shortened to `len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut buf: VecDeque<_> = [5, 10, 15, 20].try_into()?;
+    /// let mut dropped = Vec::new();
+    ///
+    /// buf.truncate_with(2, |value| dropped.push(value));
+    ///
+    /// assert_eq!(buf, [5, 10]);
+    /// assert_eq!(dropped, [15, 20]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn truncate_with<F>(&mut self, len: usize, on_drop: F)
+    where
+        F: FnMut(T),
+    {
+        if len >= self.len {
+            return;
+        }
+
+        // NB: If `on_drop` panics, dropping the drain drops the remaining
+        // elements and restores the deque.
+        self.drain(len..).for_each(on_drop);
+    }
+
     /// Returns a reference to the underlying allocator.
     #[inline]
     pub fn allocator(&self) -> &A {
@@ -1035,6 +1378,48 @@ where
         IterMut::new(a.iter_mut(), b.iter_mut())
     }
 
+    /// Returns an iterator over all overlapping windows of length `size` in
+    /// logical order.
+    ///
+    /// Since the contents of the deque might wrap around the end of its
+    /// buffer, each window is yielded as a pair of slices. The window consists
+    /// of the first slice followed by the second, which is empty unless the
+    /// window straddles the wrap point. If a single slice is needed for every
+    /// window, call [`make_contiguous`] first, in which case the second slice
+    /// is always empty.
+    ///
+    /// If the deque is shorter than `size`, the iterator returns no values.
+    ///
+    /// [`make_contiguous`]: VecDeque::make_contiguous
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut deque = VecDeque::try_with_capacity(8)?;
+    /// deque.try_push_back(3)?;
+    /// deque.try_push_back(4)?;
+    /// deque.try_push_front(2)?;
+    /// deque.try_push_front(1)?;
+    ///
+    /// let mut it = deque.windows(2);
+    /// assert_eq!(it.next(), Some((&[1, 2][..], &[][..])));
+    /// assert_eq!(it.next(), Some((&[2][..], &[3][..])));
+    /// assert_eq!(it.next(), Some((&[3, 4][..], &[][..])));
+    /// assert_eq!(it.next(), None);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn windows(&self, size: usize) -> Windows<'_, T> {
+        assert!(size != 0, "window size must be non-zero");
+        let (a, b) = self.as_slices();
+        Windows::new(a, b, size)
+    }
+
     /// Returns a pair of slices which contain, in order, the contents of the
     /// deque.
     ///
@@ -1109,7 +1494,12 @@ where
         }
     }
 
-    /// Returns the number of elements in the deque.
+    /// Returns the first `n` elements of the deque as a pair of slices which
+    /// contain them in order, or `None` if the deque has fewer than `n`
+    /// elements.
+    ///
+    /// This allows for looking ahead at the front of the deque without
+    /// removing any elements.
     ///
     /// # Examples
     ///
@@ -1117,16 +1507,35 @@ where
     /// use rune::alloc::VecDeque;
     ///
     /// let mut deque = VecDeque::new();
-    /// assert_eq!(deque.len(), 0);
-    /// deque.try_push_back(1)?;
-    /// assert_eq!(deque.len(), 1);
+    ///
+    /// deque.try_push_back(3)?;
+    /// deque.try_push_back(4)?;
+    /// deque.try_push_front(2)?;
+    /// deque.try_push_front(1)?;
+    ///
+    /// let (a, b) = deque.front_slice(3).unwrap();
+    /// assert!(a.iter().chain(b).eq(&[1, 2, 3]));
+    /// assert_eq!(deque.front_slice(5), None);
     /// # Ok::<_, rune::alloc::Error>(())
     /// ```
-    pub fn len(&self) -> usize {
-        self.len
+    #[inline]
+    pub fn front_slice(&self, n: usize) -> Option<(&[T], &[T])> {
+        if n > self.len {
+            return None;
+        }
+
+        let (a_range, b_range) = self.slice_ranges(..n, self.len);
+        // SAFETY: `slice_ranges` always returns valid ranges into
+        // the physical buffer.
+        unsafe { Some((&*self.buffer_range(a_range), &*self.buffer_range(b_range))) }
     }
 
-    /// Returns `true` if the deque is empty.
+    /// Returns the last `n` elements of the deque as a pair of slices which
+    /// contain them in order, or `None` if the deque has fewer than `n`
+    /// elements.
+    ///
+    /// This allows for looking ahead at the back of the deque without
+    /// removing any elements.
     ///
     /// # Examples
     ///
@@ -1134,26 +1543,129 @@ where
     /// use rune::alloc::VecDeque;
     ///
     /// let mut deque = VecDeque::new();
-    /// assert!(deque.is_empty());
+    ///
+    /// deque.try_push_back(3)?;
+    /// deque.try_push_back(4)?;
+    /// deque.try_push_front(2)?;
     /// deque.try_push_front(1)?;
-    /// assert!(!deque.is_empty());
+    ///
+    /// let (a, b) = deque.back_slice(3).unwrap();
+    /// assert!(a.iter().chain(b).eq(&[2, 3, 4]));
+    /// assert_eq!(deque.back_slice(5), None);
     /// # Ok::<_, rune::alloc::Error>(())
     /// ```
-    pub fn is_empty(&self) -> bool {
-        self.len == 0
+    #[inline]
+    pub fn back_slice(&self, n: usize) -> Option<(&[T], &[T])> {
+        if n > self.len {
+            return None;
+        }
+
+        let (a_range, b_range) = self.slice_ranges(self.len - n.., self.len);
+        // SAFETY: `slice_ranges` always returns valid ranges into
+        // the physical buffer.
+        unsafe { Some((&*self.buffer_range(a_range), &*self.buffer_range(b_range))) }
     }
 
-    /// Given a range into the logical buffer of the deque, this function
-    /// return two ranges into the physical buffer that correspond to
-    /// the given range. The `len` parameter should usually just be `self.len`;
-    /// the reason it's passed explicitly is that if the deque is wrapped in a
-    /// `Drain`, then `self.len` is not actually the length of the deque.
+    /// Rearranges the deque so that its elements are contiguous and returns
+    /// them as a single mutable slice.
     ///
-    /// # Safety
+    /// This is an alias for [`make_contiguous`]. After calling it, the second
+    /// slice returned by [`as_slices`] and [`as_mut_slices`] is guaranteed to
+    /// be empty until the deque is modified again.
     ///
-    /// This function is always safe to call. For the resulting ranges to be
-    /// valid ranges into the physical buffer, the caller must ensure that the
-    /// result of calling `slice::range(range, ..len)` represents a valid range
+    /// [`make_contiguous`]: VecDeque::make_contiguous
+    /// [`as_slices`]: VecDeque::as_slices
+    /// [`as_mut_slices`]: VecDeque::as_mut_slices
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut deque = VecDeque::new();
+    ///
+    /// deque.try_push_back(1)?;
+    /// deque.try_push_back(2)?;
+    /// deque.try_push_front(0)?;
+    ///
+    /// let slice = deque.as_mut_slices_contiguous();
+    /// slice[0] = 10;
+    /// assert_eq!(slice, &[10, 1, 2]);
+    /// assert_eq!(deque.as_slices(), (&[10, 1, 2][..], &[][..]));
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn as_mut_slices_contiguous(&mut self) -> &mut [T] {
+        self.make_contiguous()
+    }
+
+    /// Returns the number of elements in the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut deque = VecDeque::new();
+    /// assert_eq!(deque.len(), 0);
+    /// deque.try_push_back(1)?;
+    /// assert_eq!(deque.len(), 1);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the deque is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut deque = VecDeque::new();
+    /// assert!(deque.is_empty());
+    /// deque.try_push_front(1)?;
+    /// assert!(!deque.is_empty());
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if the deque is at full capacity, in which case pushing
+    /// another element requires it to grow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut deque = VecDeque::try_with_capacity(2)?;
+    ///
+    /// while !deque.is_full() {
+    ///     deque.try_push_back(1)?;
+    /// }
+    ///
+    /// assert_eq!(deque.len(), deque.capacity());
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    /// Given a range into the logical buffer of the deque, this function
+    /// return two ranges into the physical buffer that correspond to
+    /// the given range. The `len` parameter should usually just be `self.len`;
+    /// the reason it's passed explicitly is that if the deque is wrapped in a
+    /// `Drain`, then `self.len` is not actually the length of the deque.
+    ///
+    /// # Safety
+    ///
+    /// This function is always safe to call. For the resulting ranges to be
+    /// valid ranges into the physical buffer, the caller must ensure that the
+    /// result of calling `slice::range(range, ..len)` represents a valid range
     /// into the logical buffer, and that all elements in that range are
     /// initialized.
     fn slice_ranges<R>(&self, range: R, len: usize) -> (Range<usize>, Range<usize>)
@@ -1340,6 +1852,78 @@ where
         unsafe { Drain::new(self, drain_start, drain_len) }
     }
 
+    /// Removes all elements from `from` to the end of the deque in bulk,
+    /// returning them as an iterator.
+    ///
+    /// This is equivalent to `drain(from..)`. Since nothing follows the drained
+    /// range, the elements which are kept never have to be moved and the
+    /// operation is proportional to the number of removed elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` is greater than the length of the deque.
+    ///
+    /// # Leaking
+    ///
+    /// If the returned iterator goes out of scope without being dropped (due to
+    /// [`mem::forget`], for example), the deque may have lost and leaked
+    /// elements arbitrarily, including elements before `from`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    /// use rune::alloc::prelude::*;
+    ///
+    /// let mut deque: VecDeque<_> = [1, 2, 3, 4].try_into()?;
+    /// let drained = deque.drain_tail(1).try_collect::<VecDeque<_>>()?;
+    /// assert_eq!(drained, [2, 3, 4]);
+    /// assert_eq!(deque, [1]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn drain_tail(&mut self, from: usize) -> Drain<'_, T, A> {
+        assert!(from <= self.len, "drain start out of bounds");
+        self.drain(from..)
+    }
+
+    /// Removes the specified range from the deque, dropping the removed
+    /// elements.
+    ///
+    /// This is equivalent to dropping the iterator returned by [`drain`]
+    /// without consuming it. Whichever side of the range is shorter is moved
+    /// to close the gap, and every removed element is dropped exactly once.
+    ///
+    /// [`drain`]: VecDeque::drain
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut deque: VecDeque<_> = [1, 2, 3, 4, 5].try_into()?;
+    /// deque.remove_range(1..3);
+    /// assert_eq!(deque, [1, 4, 5]);
+    ///
+    /// deque.remove_range(..);
+    /// assert!(deque.is_empty());
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn remove_range<R>(&mut self, range: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        // NB: The destructor of `Drain` drops any elements which have not
+        // been consumed and moves the shorter side of the deque into place.
+        drop(self.drain(range));
+    }
+
     /// Clears the deque, removing all values.
     ///
     /// # Examples
@@ -1360,6 +1944,35 @@ where
         self.head = 0;
     }
 
+    /// Clears the deque, removing all values, and shrinks its capacity down
+    /// to `keep_capacity`.
+    ///
+    /// This is equivalent to calling [`clear`] followed by
+    /// [`try_shrink_to`], which is useful to release memory after processing
+    /// a large burst of values. If the current capacity is less than
+    /// `keep_capacity` the capacity is left as is.
+    ///
+    /// [`clear`]: VecDeque::clear
+    /// [`try_shrink_to`]: VecDeque::try_shrink_to
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    /// use rune::alloc::prelude::*;
+    ///
+    /// let mut deque = VecDeque::new();
+    /// deque.try_extend(0..100)?;
+    /// deque.try_clear_and_shrink(8)?;
+    /// assert!(deque.is_empty());
+    /// assert!(deque.capacity() >= 8 && deque.capacity() < 100);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_clear_and_shrink(&mut self, keep_capacity: usize) -> Result<(), Error> {
+        self.clear();
+        self.try_shrink_to(keep_capacity)
+    }
+
     /// Returns `true` if the deque contains an element equal to the
     /// given value.
     ///
@@ -1479,6 +2092,155 @@ where
         self.get_mut(self.len.wrapping_sub(1))
     }
 
+    /// Returns references to the first `N` elements of the deque, or `None`
+    /// if it has fewer than `N` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut d = VecDeque::new();
+    /// d.try_push_back(2)?;
+    /// d.try_push_back(3)?;
+    /// d.try_push_front(1)?;
+    ///
+    /// assert_eq!(d.first_chunk::<2>(), Some([&1, &2]));
+    /// assert_eq!(d.first_chunk::<4>(), None);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn first_chunk<const N: usize>(&self) -> Option<[&T; N]> {
+        if self.len < N {
+            return None;
+        }
+
+        let mut iter = self.iter();
+        Some(array::from_fn(|_| iter.next().unwrap()))
+    }
+
+    /// Returns mutable references to the first `N` elements of the deque, or
+    /// `None` if it has fewer than `N` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut d: VecDeque<_> = [1, 2, 3].try_into()?;
+    ///
+    /// if let Some([a, b]) = d.first_chunk_mut::<2>() {
+    ///     *a = 10;
+    ///     *b = 20;
+    /// }
+    ///
+    /// assert_eq!(d, [10, 20, 3]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn first_chunk_mut<const N: usize>(&mut self) -> Option<[&mut T; N]> {
+        if self.len < N {
+            return None;
+        }
+
+        let mut iter = self.iter_mut();
+        Some(array::from_fn(|_| iter.next().unwrap()))
+    }
+
+    /// Returns references to the last `N` elements of the deque, or `None` if
+    /// it has fewer than `N` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut d = VecDeque::new();
+    /// d.try_push_back(2)?;
+    /// d.try_push_back(3)?;
+    /// d.try_push_front(1)?;
+    ///
+    /// assert_eq!(d.last_chunk::<2>(), Some([&2, &3]));
+    /// assert_eq!(d.last_chunk::<4>(), None);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn last_chunk<const N: usize>(&self) -> Option<[&T; N]> {
+        let start = self.len.checked_sub(N)?;
+        let mut iter = self.range(start..);
+        Some(array::from_fn(|_| iter.next().unwrap()))
+    }
+
+    /// Returns mutable references to the last `N` elements of the deque, or
+    /// `None` if it has fewer than `N` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut d: VecDeque<_> = [1, 2, 3].try_into()?;
+    ///
+    /// if let Some([a, b]) = d.last_chunk_mut::<2>() {
+    ///     *a = 20;
+    ///     *b = 30;
+    /// }
+    ///
+    /// assert_eq!(d, [1, 20, 30]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn last_chunk_mut<const N: usize>(&mut self) -> Option<[&mut T; N]> {
+        let start = self.len.checked_sub(N)?;
+        let mut iter = self.range_mut(start..);
+        Some(array::from_fn(|_| iter.next().unwrap()))
+    }
+
+    /// Removes the first `N` elements of the deque and returns them as an
+    /// array, or `None` if it has fewer than `N` elements.
+    ///
+    /// The deque is left untouched if it has fewer than `N` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut d: VecDeque<_> = [1, 2, 3].try_into()?;
+    ///
+    /// assert_eq!(d.pop_first_chunk::<2>(), Some([1, 2]));
+    /// assert_eq!(d.pop_first_chunk::<2>(), None);
+    /// assert_eq!(d, [3]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn pop_first_chunk<const N: usize>(&mut self) -> Option<[T; N]> {
+        if self.len < N {
+            return None;
+        }
+
+        let mut iter = self.drain(..N);
+        Some(array::from_fn(|_| iter.next().unwrap()))
+    }
+
+    /// Removes the last `N` elements of the deque and returns them as an
+    /// array, or `None` if it has fewer than `N` elements.
+    ///
+    /// The deque is left untouched if it has fewer than `N` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut d: VecDeque<_> = [1, 2, 3].try_into()?;
+    ///
+    /// assert_eq!(d.pop_last_chunk::<2>(), Some([2, 3]));
+    /// assert_eq!(d.pop_last_chunk::<2>(), None);
+    /// assert_eq!(d, [1]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn pop_last_chunk<const N: usize>(&mut self) -> Option<[T; N]> {
+        let start = self.len.checked_sub(N)?;
+        let mut iter = self.drain(start..);
+        Some(array::from_fn(|_| iter.next().unwrap()))
+    }
+
     /// Removes the first element and returns it, or `None` if the deque is
     /// empty.
     ///
@@ -1839,6 +2601,45 @@ where
         Ok(other)
     }
 
+    /// Splits the deque into two at the first element which satisfies the
+    /// given predicate.
+    ///
+    /// Returns a newly allocated `VecDeque` containing the matching element
+    /// and every element after it, leaving the elements before it in `self`.
+    /// If no element matches, `self` is left as is and `None` is returned.
+    ///
+    /// This is equivalent to finding the index of the matching element with
+    /// [`Iterator::position`] and splitting the deque at it with
+    /// [`try_split_off`].
+    ///
+    /// [`try_split_off`]: VecDeque::try_split_off
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut buf: VecDeque<_> = [1, 2, 0, 3, 0].try_into()?;
+    /// let rest = buf.try_split_when(|&x| x == 0)?;
+    /// assert_eq!(buf, [1, 2]);
+    /// assert_eq!(rest, Some(VecDeque::try_from([0, 3, 0])?));
+    ///
+    /// assert!(buf.try_split_when(|&x| x == 0)?.is_none());
+    /// assert_eq!(buf, [1, 2]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_split_when<P>(&mut self, pred: P) -> Result<Option<Self>, Error>
+    where
+        P: FnMut(&T) -> bool,
+        A: Clone,
+    {
+        let Some(at) = self.iter().position(pred) else {
+            return Ok(None);
+        };
+
+        Ok(Some(self.try_split_off(at)?))
+    }
+
     /// Moves all the elements of `other` into `self`, leaving `other` empty.
     ///
     /// # Panics
@@ -1887,22 +2688,143 @@ where
         Ok(())
     }
 
-    /// Retains only the elements specified by the predicate.
+    /// Moves all the elements of `other` into `self`, leaving `other` empty.
     ///
-    /// In other words, remove all elements `e` for which `f(&e)` returns false.
-    /// This method operates in place, visiting each element exactly once in the
-    /// original order, and preserves the order of the retained elements.
+    /// This behaves like [`try_append`], except that if `self` is empty its
+    /// buffer is swapped with the one in `other` in *O*(1) instead of copying
+    /// the elements over.
+    ///
+    /// Since a buffer is owned by the allocator it was allocated with, the
+    /// allocators are swapped along with the buffers. This is only
+    /// appropriate if the allocators of the two deques are compatible, such
+    /// as when they are both [`Global`], since `self` will keep using the
+    /// allocator of `other` afterwards and vice versa.
+    ///
+    /// [`try_append`]: VecDeque::try_append
     ///
     /// # Examples
     ///
     /// ```
     /// use rune::alloc::VecDeque;
-    /// use rune::alloc::prelude::*;
     ///
     /// let mut buf = VecDeque::new();
-    /// buf.try_extend(1..5)?;
-    /// buf.retain(|&x| x % 2 == 0);
-    /// assert_eq!(buf, [2, 4]);
+    /// let mut buf2: VecDeque<_> = [1, 2].try_into()?;
+    /// let ptr = buf2.as_slices().0.as_ptr();
+    ///
+    /// buf.try_append_or_adopt(&mut buf2)?;
+    /// assert_eq!(buf, [1, 2]);
+    /// assert_eq!(buf.as_slices().0.as_ptr(), ptr);
+    /// assert!(buf2.is_empty());
+    ///
+    /// let mut buf3: VecDeque<_> = [3, 4].try_into()?;
+    /// buf.try_append_or_adopt(&mut buf3)?;
+    /// assert_eq!(buf, [1, 2, 3, 4]);
+    /// assert!(buf3.is_empty());
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn try_append_or_adopt(&mut self, other: &mut Self) -> Result<(), Error> {
+        if self.is_empty() {
+            mem::swap(self, other);
+            return Ok(());
+        }
+
+        self.try_append(other)
+    }
+
+    /// Merges the elements of a sorted iterator into the sorted deque, so
+    /// that the deque remains sorted.
+    ///
+    /// Both the deque and the iterator are assumed to be sorted in ascending
+    /// order, otherwise the order of the resulting elements is unspecified.
+    /// The merge is stable, so elements in the deque are placed before equal
+    /// elements from the iterator.
+    ///
+    /// This performs a single merge pass which moves each element in the
+    /// deque once, rather than inserting each element individually.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the deque can't grow to fit the merged elements. The deque
+    /// remains sorted and keeps all elements which were merged before the
+    /// error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut buf: VecDeque<_> = [1, 3, 5, 7].try_into()?;
+    /// buf.try_merge_sorted([2, 3, 4, 8, 9])?;
+    /// assert_eq!(buf, [1, 2, 3, 3, 4, 5, 7, 8, 9]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_merge_sorted<I>(&mut self, iter: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Ord,
+    {
+        let iter = iter.into_iter();
+
+        let (lower, _) = iter.size_hint();
+        self.try_reserve(lower)?;
+
+        let mut iter = iter.peekable();
+
+        // The number of elements from the deque which haven't been merged
+        // yet. These are kept at the front of the deque while the merged
+        // elements are pushed to the back.
+        let mut remaining = self.len;
+
+        while remaining > 0 {
+            let Some(value) = iter.peek() else {
+                break;
+            };
+
+            if self[0] <= *value {
+                self.rotate_left(1);
+                remaining -= 1;
+                continue;
+            }
+
+            if let Some(value) = iter.next() {
+                if let Err(error) = self.try_push_back(value) {
+                    self.rotate_left(remaining);
+                    return Err(error);
+                }
+            }
+        }
+
+        if remaining > 0 {
+            // The iterator is exhausted, so the elements which remain are
+            // larger than every merged element.
+            self.rotate_left(remaining);
+            return Ok(());
+        }
+
+        for value in iter {
+            self.try_push_back(value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all elements `e` for which `f(&e)` returns false.
+    /// This method operates in place, visiting each element exactly once in the
+    /// original order, and preserves the order of the retained elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    /// use rune::alloc::prelude::*;
+    ///
+    /// let mut buf = VecDeque::new();
+    /// buf.try_extend(1..5)?;
+    /// buf.retain(|&x| x % 2 == 0);
+    /// assert_eq!(buf, [2, 4]);
     /// # Ok::<_, rune::alloc::Error>(())
     /// ```
     ///
@@ -1986,6 +2908,224 @@ where
         }
     }
 
+    /// Retains only the elements in the given range which are specified by
+    /// the predicate.
+    ///
+    /// This is like [`retain`], except that only the elements in the range
+    /// are visited and elements outside of it are left untouched. The
+    /// remaining elements are shifted to close the gap left by the removed
+    /// ones, and the order of all retained elements is preserved.
+    ///
+    /// [`retain`]: VecDeque::retain
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    /// use rune::alloc::prelude::*;
+    ///
+    /// let mut buf = VecDeque::new();
+    /// buf.try_extend(1..10)?;
+    /// buf.retain_range(2..7, |&x| x % 2 == 0);
+    /// assert_eq!(buf, [1, 2, 4, 6, 8, 9]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn retain_range<R, F>(&mut self, range: R, mut f: F)
+    where
+        R: RangeBounds<usize>,
+        F: FnMut(&T) -> bool,
+    {
+        let Range { start, end } = slice_range(range, ..self.len);
+        let mut idx = start;
+        let mut cur = start;
+
+        // Stage 1: All values are retained.
+        while cur < end {
+            if !f(&self[cur]) {
+                cur += 1;
+                break;
+            }
+            cur += 1;
+            idx += 1;
+        }
+        // Stage 2: Swap retained value into current idx.
+        while cur < end {
+            if !f(&self[cur]) {
+                cur += 1;
+                continue;
+            }
+
+            self.swap(idx, cur);
+            cur += 1;
+            idx += 1;
+        }
+        // Stage 3: Remove the values between idx and the end of the range,
+        // which shifts whichever side of the deque is shorter.
+        if cur != idx {
+            self.drain(idx..end);
+        }
+    }
+
+    /// Removes all but the first of consecutive elements in the deque
+    /// satisfying a given equality relation.
+    ///
+    /// The `same_bucket` function is passed references to two elements from
+    /// the deque and must determine if the elements compare equal. The
+    /// elements are passed in opposite order from their order in the deque,
+    /// so if `same_bucket(a, b)` returns `true`, `a` is removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    /// use rune::alloc::prelude::*;
+    ///
+    /// let mut buf: VecDeque<_> = ["foo", "bar", "Bar", "baz", "bar"].try_into()?;
+    /// buf.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    /// assert_eq!(buf, ["foo", "bar", "baz", "bar"]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let len = self.len;
+
+        if len <= 1 {
+            return;
+        }
+
+        // The element at `idx - 1` is the last one which has been retained.
+        let mut idx = 1;
+
+        for cur in 1..len {
+            // SAFETY: Both indexes are in bounds and `idx - 1 < cur`, so the
+            // references don't alias.
+            let same = unsafe {
+                let a = &mut *self.ptr().add(self.to_physical_idx(cur));
+                let b = &mut *self.ptr().add(self.to_physical_idx(idx - 1));
+                same_bucket(a, b)
+            };
+
+            if same {
+                continue;
+            }
+
+            if idx != cur {
+                self.swap(idx, cur);
+            }
+
+            idx += 1;
+        }
+
+        self.truncate(idx);
+    }
+
+    /// Removes all but the first of consecutive elements in the deque that
+    /// resolve to the same key.
+    ///
+    /// If the deque is sorted by the key, this removes all duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    /// use rune::alloc::prelude::*;
+    ///
+    /// let mut buf: VecDeque<_> = [10, 20, 21, 30, 20].try_into()?;
+    /// buf.dedup_by_key(|x| *x / 10);
+    /// assert_eq!(buf, [10, 20, 30, 20]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        F: FnMut(&mut T) -> K,
+        K: PartialEq,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    /// Retains only the elements specified by a fallible predicate.
+    ///
+    /// In other words, remove all elements `e` for which `f(&mut e)` returns
+    /// `Ok(false)`. This method operates in place, visiting each element
+    /// exactly once in the original order, and preserves the order of the
+    /// retained elements.
+    ///
+    /// If the predicate returns an error, iteration stops and the error is
+    /// returned. Elements which were rejected before the error are removed,
+    /// while the element for which the predicate failed and all elements which
+    /// were not yet visited are retained in their original order. No element
+    /// is ever lost or dropped more than once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    /// use rune::alloc::prelude::*;
+    ///
+    /// let mut buf = VecDeque::new();
+    /// buf.try_extend(1..7)?;
+    ///
+    /// let result = buf.try_retain(|x| match *x {
+    ///     5 => Err("five"),
+    ///     x => Ok(x % 2 == 0),
+    /// });
+    ///
+    /// assert_eq!(result, Err("five"));
+    /// assert_eq!(buf, [2, 4, 5, 6]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_retain<F, E>(&mut self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(&mut T) -> Result<bool, E>,
+    {
+        let len = self.len;
+        let mut idx = 0;
+        let mut cur = 0;
+        let mut result = Ok(());
+
+        // Stage 1: Swap retained values into current idx until the predicate
+        // errors.
+        while cur < len {
+            match f(&mut self[cur]) {
+                Ok(true) => {
+                    if cur != idx {
+                        self.swap(idx, cur);
+                    }
+
+                    idx += 1;
+                }
+                Ok(false) => {}
+                Err(error) => {
+                    result = Err(error);
+                    break;
+                }
+            }
+
+            cur += 1;
+        }
+
+        // Stage 2: Swap any values which were not visited into current idx.
+        while cur < len {
+            self.swap(idx, cur);
+            cur += 1;
+            idx += 1;
+        }
+
+        // Stage 3: Truncate all values after idx.
+        if cur != idx {
+            self.truncate(idx);
+        }
+
+        result
+    }
+
     // Double the buffer size. This method is inline(never), so we expect it to only
     // be called in cold paths.
     // This may panic or abort
@@ -2230,6 +3370,43 @@ where
         unsafe { slice::from_raw_parts_mut(ptr.add(self.head), self.len) }
     }
 
+    /// Calls [`make_contiguous`] only if `predicate` returns `true`.
+    ///
+    /// The predicate is called with the length of the deque and the physical
+    /// index of its first element in the underlying buffer, allowing callers
+    /// to encode their own heuristic for when rearranging the contents of the
+    /// deque is worth the cost. Returns the result of the predicate.
+    ///
+    /// [`make_contiguous`]: VecDeque::make_contiguous
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut buf = VecDeque::try_with_capacity(8)?;
+    /// buf.try_push_back(2)?;
+    /// buf.try_push_back(3)?;
+    /// buf.try_push_front(1)?;
+    ///
+    /// // Only contiguize if the head is in the back half of the buffer.
+    /// let cap = buf.capacity();
+    /// assert!(buf.ensure_contiguous_if(|_, head| head > cap / 2));
+    /// assert_eq!(buf.as_slices(), (&[1, 2, 3][..], &[][..]));
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn ensure_contiguous_if<P>(&mut self, predicate: P) -> bool
+    where
+        P: FnOnce(usize, usize) -> bool,
+    {
+        if !predicate(self.len, self.head) {
+            return false;
+        }
+
+        self.make_contiguous();
+        true
+    }
+
     /// Rotates the double-ended queue `mid` places to the left.
     ///
     /// Equivalently,
@@ -2274,6 +3451,53 @@ where
         }
     }
 
+    /// Rotates the double-ended queue `mid` places to the left like
+    /// [`rotate_left`], returning clones of the `mid` items which were moved
+    /// from the front to the back.
+    ///
+    /// The returned items can be used to log or revert the rotation. If
+    /// cloning fails the deque is left unmodified.
+    ///
+    /// [`rotate_left`]: VecDeque::rotate_left
+    ///
+    /// # Panics
+    ///
+    /// If `mid` is greater than `len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    /// use rune::alloc::prelude::*;
+    ///
+    /// let mut buf: VecDeque<_> = (0..6).try_collect()?;
+    ///
+    /// let moved = buf.try_rotate_left_collecting(2)?;
+    /// assert_eq!(buf, [2, 3, 4, 5, 0, 1]);
+    /// assert_eq!(moved, [0, 1]);
+    ///
+    /// // Undo the rotation.
+    /// buf.rotate_right(moved.len());
+    /// assert_eq!(buf, [0, 1, 2, 3, 4, 5]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_rotate_left_collecting(&mut self, mid: usize) -> Result<Vec<T, A>, Error>
+    where
+        T: TryClone,
+        A: Clone,
+    {
+        assert!(mid <= self.len());
+
+        let mut moved = Vec::try_with_capacity_in(mid, self.allocator().clone())?;
+
+        for value in self.range(..mid) {
+            moved.try_push(value.try_clone()?)?;
+        }
+
+        self.rotate_left(mid);
+        Ok(moved)
+    }
+
     /// Rotates the double-ended queue `k` places to the right.
     ///
     /// Equivalently,
@@ -2318,6 +3542,44 @@ where
         }
     }
 
+    /// Rotates the double-ended queue to the left so that its minimum element
+    /// is at the front.
+    ///
+    /// If several elements are equally minimum, the first one is moved to the
+    /// front. For cyclic sequences with a unique minimum this is a canonical
+    /// rotation, so different rotations of the same cycle compare equal
+    /// afterwards.
+    ///
+    /// # Complexity
+    ///
+    /// Takes `*O*(len())` time and no extra space.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut a: VecDeque<_> = [3, 1, 4, 2].try_into()?;
+    /// let mut b: VecDeque<_> = [4, 2, 3, 1].try_into()?;
+    ///
+    /// a.rotate_to_min();
+    /// b.rotate_to_min();
+    ///
+    /// assert_eq!(a, [1, 4, 2, 3]);
+    /// assert_eq!(a, b);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn rotate_to_min(&mut self)
+    where
+        T: Ord,
+    {
+        let Some((mid, _)) = self.iter().enumerate().min_by(|a, b| a.1.cmp(b.1)) else {
+            return;
+        };
+
+        self.rotate_left(mid);
+    }
+
     // SAFETY: the following two methods require that the rotation amount
     // be less than half the length of the deque.
     //
@@ -2399,6 +3661,153 @@ where
         self.binary_search_by(|e| e.cmp(x))
     }
 
+    /// Binary searches this sorted `VecDeque` for the first index at which `x`
+    /// could be inserted while maintaining sorted order.
+    ///
+    /// In contrast to [`binary_search`], this is unambiguous in the presence
+    /// of duplicates: every element before the returned index is less than
+    /// `x`, and every element at or after it is greater than or equal to `x`.
+    /// If `x` is present, the returned index is that of its first occurrence.
+    ///
+    /// This is implemented using [`partition_point`]. If the `VecDeque` is not
+    /// sorted, the returned result is unspecified and meaningless.
+    ///
+    /// See also [`binary_search_upper_bound`].
+    ///
+    /// [`binary_search`]: VecDeque::binary_search
+    /// [`partition_point`]: VecDeque::partition_point
+    /// [`binary_search_upper_bound`]: VecDeque::binary_search_upper_bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let deque: VecDeque<_> = [0, 1, 1, 1, 1, 2, 3, 5, 8].try_into()?;
+    ///
+    /// assert_eq!(deque.binary_search_lower_bound(&1), 1);
+    /// assert_eq!(deque.binary_search_lower_bound(&4), 7);
+    /// assert_eq!(deque.binary_search_lower_bound(&100), 9);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn binary_search_lower_bound(&self, x: &T) -> usize
+    where
+        T: Ord,
+    {
+        self.partition_point(|e| e < x)
+    }
+
+    /// Binary searches this sorted `VecDeque` for the last index at which `x`
+    /// could be inserted while maintaining sorted order.
+    ///
+    /// In contrast to [`binary_search`], this is unambiguous in the presence
+    /// of duplicates: every element before the returned index is less than or
+    /// equal to `x`, and every element at or after it is greater than `x`. If
+    /// `x` is present, the returned index is one past its last occurrence.
+    ///
+    /// This is implemented using [`partition_point`]. If the `VecDeque` is not
+    /// sorted, the returned result is unspecified and meaningless.
+    ///
+    /// See also [`binary_search_lower_bound`].
+    ///
+    /// [`binary_search`]: VecDeque::binary_search
+    /// [`partition_point`]: VecDeque::partition_point
+    /// [`binary_search_lower_bound`]: VecDeque::binary_search_lower_bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let deque: VecDeque<_> = [0, 1, 1, 1, 1, 2, 3, 5, 8].try_into()?;
+    ///
+    /// assert_eq!(deque.binary_search_upper_bound(&1), 5);
+    /// assert_eq!(deque.binary_search_upper_bound(&4), 7);
+    /// assert_eq!(deque.binary_search_upper_bound(&-1), 0);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn binary_search_upper_bound(&self, x: &T) -> usize
+    where
+        T: Ord,
+    {
+        self.partition_point(|e| e <= x)
+    }
+
+    /// Binary searches this sorted `VecDeque` for `value`, and inserts it
+    /// where it maintains sorted order if it isn't already present.
+    ///
+    /// If the value is found then [`Result::Ok`] is returned, containing the
+    /// index of the matching element, and the deque is left unchanged. If
+    /// there are multiple matches, then any one of the matches could be
+    /// returned. If the value is not found it is inserted, and
+    /// [`Result::Err`] is returned containing the index it was inserted at.
+    ///
+    /// This is a convenient way to maintain a sorted deque of unique values.
+    /// If the `VecDeque` is not sorted, the position the value is inserted at
+    /// is unspecified.
+    ///
+    /// See also [`binary_search`].
+    ///
+    /// [`binary_search`]: VecDeque::binary_search
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut deque: VecDeque<_> = [1, 3, 5].try_into()?;
+    ///
+    /// assert_eq!(deque.try_binary_insert(4)?, Err(2));
+    /// assert_eq!(deque.try_binary_insert(3)?, Ok(1));
+    /// assert_eq!(deque, [1, 3, 4, 5]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_binary_insert(&mut self, value: T) -> Result<Result<usize, usize>, Error>
+    where
+        T: Ord,
+    {
+        match self.binary_search(&value) {
+            Ok(index) => Ok(Ok(index)),
+            Err(index) => {
+                self.try_insert(index, value)?;
+                Ok(Err(index))
+            }
+        }
+    }
+
+    /// Binary searches this `VecDeque` for a given element, assuming that it
+    /// is sorted in descending order.
+    ///
+    /// This behaves like [`binary_search`], except that the comparison is
+    /// reversed. The returned insertion point on [`Result::Err`] maintains
+    /// descending order. If the `VecDeque` is not sorted in descending order,
+    /// the returned result is unspecified and meaningless.
+    ///
+    /// [`binary_search`]: VecDeque::binary_search
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let deque: VecDeque<_> = [55, 34, 21, 13, 8, 5, 3, 2, 1, 0].try_into()?;
+    ///
+    /// assert_eq!(deque.binary_search_desc(&13), Ok(3));
+    /// assert_eq!(deque.binary_search_desc(&4), Err(6));
+    /// assert_eq!(deque.binary_search_desc(&100), Err(0));
+    /// assert_eq!(deque.binary_search_desc(&-1), Err(10));
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn binary_search_desc(&self, x: &T) -> Result<usize, usize>
+    where
+        T: Ord,
+    {
+        self.binary_search_by(|e| x.cmp(e))
+    }
+
     /// Binary searches this `VecDeque` with a comparator function.
     ///
     /// The comparator function should return an order code that indicates
@@ -2563,6 +3972,185 @@ where
             front.partition_point(pred)
         }
     }
+
+    /// Converts the deque into a [`Vec`].
+    ///
+    /// This is the same as [`Vec::from`], and never needs to re-allocate, but
+    /// does need to do *O*(*n*) data movement if the circular buffer doesn't
+    /// happen to be at the beginning of the allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    /// use rune::alloc::prelude::*;
+    ///
+    /// // This one is *O*(1).
+    /// let deque: VecDeque<_> = (1..5).try_collect()?;
+    /// let ptr = deque.as_slices().0.as_ptr();
+    /// let vec = deque.into_vec();
+    /// assert_eq!(vec, [1, 2, 3, 4]);
+    /// assert_eq!(vec.as_ptr(), ptr);
+    ///
+    /// // This one needs data rearranging.
+    /// let mut deque: VecDeque<_> = (1..5).try_collect()?;
+    /// deque.try_push_front(9)?;
+    /// deque.try_push_front(8)?;
+    /// let vec = deque.into_vec();
+    /// assert_eq!(vec, [8, 9, 1, 2, 3, 4]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn into_vec(self) -> Vec<T, A> {
+        Vec::from(self)
+    }
+
+    /// Converts the deque into a boxed slice.
+    ///
+    /// The elements are made contiguous as in [`into_vec`], after which any
+    /// excess capacity is removed, which might re-allocate.
+    ///
+    /// [`into_vec`]: VecDeque::into_vec
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut deque = VecDeque::try_with_capacity(10)?;
+    /// deque.try_push_back(2)?;
+    /// deque.try_push_back(3)?;
+    /// deque.try_push_front(1)?;
+    ///
+    /// let slice = deque.try_into_boxed_slice()?;
+    /// assert_eq!(&*slice, [1, 2, 3]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_into_boxed_slice(self) -> Result<Box<[T], A>, Error> {
+        self.into_vec().try_into_boxed_slice()
+    }
+
+    /// Converts a boxed slice into a deque.
+    ///
+    /// This is guaranteed to run in *O*(1) time and to not re-allocate the
+    /// slice or allocate any additional memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::{Box, VecDeque};
+    ///
+    /// let slice: Box<[i32]> = Box::try_from([1, 2, 3])?;
+    /// let deque = VecDeque::from_boxed_slice(slice);
+    /// assert_eq!(deque, [1, 2, 3]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    #[inline]
+    pub fn from_boxed_slice(slice: Box<[T], A>) -> Self {
+        Self::from(Vec::from(slice))
+    }
+
+    /// Converts the deque into an array, if its length exactly matches that
+    /// of the requested array.
+    ///
+    /// The elements are moved into the array in the order they're stored in
+    /// the deque, which might require the deque to be made contiguous first.
+    /// If the length doesn't match, the deque is returned intact in `Err`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut deque = VecDeque::new();
+    /// deque.try_push_back(2)?;
+    /// deque.try_push_back(3)?;
+    /// deque.try_push_front(1)?;
+    ///
+    /// let deque = match deque.try_into_array::<4>() {
+    ///     Ok(..) => panic!("deque has three elements"),
+    ///     Err(deque) => deque,
+    /// };
+    ///
+    /// assert_eq!(deque, [1, 2, 3]);
+    /// assert_eq!(deque.try_into_array::<3>().ok(), Some([1, 2, 3]));
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn try_into_array<const N: usize>(mut self) -> Result<[T; N], Self> {
+        if self.len != N {
+            return Err(self);
+        }
+
+        self.make_contiguous();
+
+        // SAFETY: The deque is contiguous and has exactly `N` initialized
+        // elements starting at `head`. The length is cleared so that dropping
+        // the deque only releases its buffer and not the moved out elements.
+        unsafe {
+            let array = ptr::read(self.ptr().add(self.head).cast::<[T; N]>());
+            self.len = 0;
+            Ok(array)
+        }
+    }
+
+    /// Consumes the deque, sorting its elements with a comparator function
+    /// and returning them as a [`Vec`].
+    ///
+    /// This makes the deque contiguous before sorting it, so the elements
+    /// are sorted in place and the buffer of the deque is reused.
+    ///
+    /// This sort is stable (i.e., does not reorder equal elements).
+    ///
+    /// # Errors
+    ///
+    /// Errors if the temporary allocation used while sorting fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::alloc::VecDeque;
+    ///
+    /// let mut deque = VecDeque::new();
+    /// deque.try_push_back((2, 'a'))?;
+    /// deque.try_push_back((1, 'b'))?;
+    /// deque.try_push_front((2, 'c'))?;
+    ///
+    /// let vec = deque.into_sorted_by(|a, b| a.0.cmp(&b.0))?;
+    /// assert_eq!(vec, [(1, 'b'), (2, 'c'), (2, 'a')]);
+    /// # Ok::<_, rune::alloc::Error>(())
+    /// ```
+    pub fn into_sorted_by<F>(mut self, mut compare: F) -> Result<Vec<T, A>, Error>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let slice = self.make_contiguous();
+
+        // Sort a permutation of indexes instead of the elements, using the
+        // original index to break ties so that the sort is stable.
+        let mut indexes = Vec::<usize>::try_with_capacity(slice.len())?;
+
+        for index in 0..slice.len() {
+            indexes.try_push(index)?;
+        }
+
+        indexes.sort_unstable_by(|&a, &b| compare(&slice[a], &slice[b]).then(a.cmp(&b)));
+
+        // Apply the permutation by following each cycle, marking visited
+        // positions by pointing them to themselves.
+        for start in 0..indexes.len() {
+            let mut current = start;
+
+            while indexes[current] != start {
+                let next = mem::replace(&mut indexes[current], current);
+                slice.swap(current, next);
+                current = next;
+            }
+
+            indexes[current] = current;
+        }
+
+        Ok(self.into_vec())
+    }
 }
 
 impl<T, A> VecDeque<T, A>
@@ -2717,6 +4305,18 @@ where
         //
         // Hasher only guarantees equivalence for the exact same
         // set of calls to its methods.
+        let (front, back) = self.as_slices();
+
+        // Fast path for contiguous deques, which avoids the overhead of
+        // iterating over two slices while hashing each element the same way.
+        if back.is_empty() {
+            for elem in front {
+                elem.hash(state);
+            }
+
+            return;
+        }
+
         self.iter().for_each(|elem| elem.hash(state));
     }
 }
@@ -2901,6 +4501,14 @@ where
 {
     #[inline]
     fn try_extend<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), Error> {
+        let iter = iter.into_iter();
+
+        // The lower bound is reserved up front, which for iterators of an
+        // exact size is everything they produce. Anything beyond it is grown
+        // into as needed.
+        let (lower, _) = iter.size_hint();
+        self.try_reserve(lower)?;
+
         for value in iter {
             self.try_push_back(value)?;
         }
@@ -2908,3 +4516,6 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,1020 @@
+use rust_alloc::rc::Rc;
+use rust_alloc::vec::Vec as StdVec;
+
+use crate::alloc::Global;
+use crate::testing::*;
+
+use super::VecDeque;
+
+/// Construct a deque with capacity `cap` whose contents wrap around the end
+/// of its buffer, with `front` stored at the end of the buffer and `back` at
+/// the start.
+fn wrapped(cap: usize, front: &[i32], back: &[i32]) -> VecDeque<i32> {
+    let mut deque = VecDeque::try_with_capacity(cap).abort();
+
+    for &value in back {
+        deque.try_push_back(value).abort();
+    }
+
+    for &value in front.iter().rev() {
+        deque.try_push_front(value).abort();
+    }
+
+    assert_eq!(deque.as_slices(), (front, back));
+    deque
+}
+
+fn flatten<'a>(windows: impl Iterator<Item = (&'a [i32], &'a [i32])>) -> StdVec<StdVec<i32>> {
+    windows
+        .map(|(a, b)| a.iter().chain(b).copied().collect())
+        .collect()
+}
+
+#[test]
+fn windows_contiguous() {
+    let deque = VecDeque::try_from([1, 2, 3, 4]).abort();
+
+    for (a, b) in deque.windows(2) {
+        assert!(b.is_empty());
+        assert_eq!(a.len(), 2);
+    }
+
+    assert_eq!(
+        flatten(deque.windows(2)),
+        [[1, 2], [2, 3], [3, 4]].map(StdVec::from)
+    );
+    assert_eq!(deque.windows(4).len(), 1);
+    assert_eq!(deque.windows(5).len(), 0);
+}
+
+#[test]
+fn windows_wrapped() {
+    let deque = wrapped(8, &[1, 2, 3], &[4, 5]);
+
+    let windows = deque.windows(3).collect::<StdVec<_>>();
+
+    assert_eq!(
+        windows,
+        [
+            (&[1, 2, 3][..], &[][..]),
+            (&[2, 3][..], &[4][..]),
+            (&[3][..], &[4, 5][..]),
+        ]
+    );
+
+    assert_eq!(
+        flatten(deque.windows(3).rev()),
+        [[3, 4, 5], [2, 3, 4], [1, 2, 3]].map(StdVec::from)
+    );
+
+    assert_eq!(
+        flatten(deque.windows(1)),
+        [[1], [2], [3], [4], [5]].map(StdVec::from)
+    );
+}
+
+#[test]
+#[should_panic]
+fn windows_zero() {
+    let deque = VecDeque::try_from([1, 2, 3]).abort();
+    let _ = deque.windows(0);
+}
+
+#[test]
+fn ensure_contiguous_if() {
+    let mut deque = wrapped(8, &[1, 2], &[3, 4]);
+
+    let mut seen = None;
+    assert!(!deque.ensure_contiguous_if(|len, head| {
+        seen = Some((len, head));
+        false
+    }));
+
+    assert_eq!(seen, Some((4, 6)));
+    assert_eq!(deque.as_slices(), (&[1, 2][..], &[3, 4][..]));
+
+    assert!(deque.ensure_contiguous_if(|_, head| head > 4));
+    assert_eq!(deque.as_slices(), (&[1, 2, 3, 4][..], &[][..]));
+}
+
+#[test]
+fn as_mut_slices_contiguous() {
+    let mut deque = wrapped(8, &[1, 2, 3], &[4, 5]);
+
+    let slice = deque.as_mut_slices_contiguous();
+    assert_eq!(slice, &[1, 2, 3, 4, 5]);
+    slice.reverse();
+
+    let (front, back) = deque.as_mut_slices();
+    assert_eq!(front, &[5, 4, 3, 2, 1]);
+    assert!(back.is_empty());
+}
+
+#[test]
+fn try_retain() {
+    let values = (1..=8).map(Rc::new).collect::<StdVec<_>>();
+
+    let mut deque = VecDeque::try_with_capacity(8).abort();
+
+    for value in &values[4..] {
+        deque.try_push_back(value.clone()).abort();
+    }
+
+    for value in values[..4].iter().rev() {
+        deque.try_push_front(value.clone()).abort();
+    }
+
+    assert!(!deque.as_slices().1.is_empty());
+
+    let result = deque.try_retain(|value| match **value {
+        6 => Err(6),
+        value => Ok(value % 2 == 0),
+    });
+
+    assert_eq!(result, Err(6));
+    assert_eq!(deque, [2, 4, 6, 7, 8].map(Rc::new));
+    assert_eq!(deque.len(), 5);
+
+    // Rejected elements are dropped exactly once, everything else is still
+    // owned by the deque.
+    for value in &values {
+        let expected = if [1, 3, 5].contains(&**value) { 1 } else { 2 };
+        assert_eq!(Rc::strong_count(value), expected, "{value}");
+    }
+
+    assert_eq!(deque.try_retain(|value| Ok::<_, ()>(**value > 4)), Ok(()));
+    assert_eq!(deque, [6, 7, 8].map(Rc::new));
+}
+
+#[test]
+fn binary_search_bounds() {
+    let deque = wrapped(16, &[1, 1, 2, 2, 2], &[2, 3, 5, 5]);
+
+    for (x, lower, upper) in [
+        (0, 0, 0),
+        (1, 0, 2),
+        (2, 2, 6),
+        (3, 6, 7),
+        (4, 7, 7),
+        (5, 7, 9),
+        (6, 9, 9),
+    ] {
+        assert_eq!(deque.binary_search_lower_bound(&x), lower, "lower {x}");
+        assert_eq!(deque.binary_search_upper_bound(&x), upper, "upper {x}");
+    }
+
+    let empty = VecDeque::<i32>::new();
+    assert_eq!(empty.binary_search_lower_bound(&1), 0);
+    assert_eq!(empty.binary_search_upper_bound(&1), 0);
+}
+
+#[test]
+fn binary_search_desc() {
+    let deque = wrapped(16, &[9, 7, 5], &[5, 3, 1]);
+
+    assert_eq!(deque.binary_search_desc(&9), Ok(0));
+    assert_eq!(deque.binary_search_desc(&3), Ok(4));
+    assert_eq!(deque.binary_search_desc(&1), Ok(5));
+    assert!(matches!(deque.binary_search_desc(&5), Ok(2..=3)));
+
+    assert_eq!(deque.binary_search_desc(&10), Err(0));
+    assert_eq!(deque.binary_search_desc(&8), Err(1));
+    assert_eq!(deque.binary_search_desc(&4), Err(4));
+    assert_eq!(deque.binary_search_desc(&0), Err(6));
+
+    let empty = VecDeque::<i32>::new();
+    assert_eq!(empty.binary_search_desc(&1), Err(0));
+}
+
+#[test]
+fn get_back() {
+    let mut deque = wrapped(8, &[1, 2, 3], &[4, 5]);
+    let len = deque.len();
+
+    assert_eq!(deque.get_back(0), Some(&5));
+    assert_eq!(deque.get_back(2), Some(&3));
+    assert_eq!(deque.get_back(len - 1), Some(&1));
+    assert_eq!(deque.get_back(len), None);
+    assert_eq!(deque.get_back(usize::MAX), None);
+
+    *deque.get_back_mut(0).unwrap() = 50;
+    *deque.get_back_mut(len - 1).unwrap() = 10;
+    assert!(deque.get_back_mut(len).is_none());
+    assert_eq!(deque, [10, 2, 3, 4, 50]);
+
+    let mut empty = VecDeque::<i32>::new();
+    assert_eq!(empty.get_back(0), None);
+    assert!(empty.get_back_mut(0).is_none());
+}
+
+#[test]
+fn remaining_capacity() {
+    let mut deque = VecDeque::try_with_capacity(4).abort();
+    let cap = deque.capacity();
+
+    assert_eq!(deque.remaining_capacity(), cap);
+    assert!(!deque.is_full());
+
+    for n in 0..cap {
+        assert_eq!(deque.remaining_capacity(), cap - n);
+        deque.try_push_back(n).abort();
+    }
+
+    assert_eq!(deque.remaining_capacity(), 0);
+    assert!(deque.is_full());
+
+    deque.pop_front();
+    assert_eq!(deque.remaining_capacity(), 1);
+    assert!(!deque.is_full());
+
+    let mut zst = VecDeque::<()>::new();
+    assert_eq!(zst.capacity(), usize::MAX);
+    assert_eq!(zst.remaining_capacity(), usize::MAX);
+    assert!(!zst.is_full());
+
+    zst.try_push_back(()).abort();
+    zst.try_push_front(()).abort();
+    assert_eq!(zst.remaining_capacity(), usize::MAX - 2);
+    assert!(!zst.is_full());
+}
+
+#[test]
+fn remove_range() {
+    let mut deque = VecDeque::try_from([1, 2, 3, 4, 5, 6]).abort();
+    deque.remove_range(..2);
+    assert_eq!(deque, [3, 4, 5, 6]);
+
+    let mut deque = VecDeque::try_from([1, 2, 3, 4, 5, 6]).abort();
+    deque.remove_range(2..4);
+    assert_eq!(deque, [1, 2, 5, 6]);
+
+    let mut deque = VecDeque::try_from([1, 2, 3, 4, 5, 6]).abort();
+    deque.remove_range(3..3);
+    assert_eq!(deque, [1, 2, 3, 4, 5, 6]);
+
+    // Across the wrap boundary, with either side being the shorter one.
+    let mut deque = wrapped(8, &[1, 2, 3], &[4, 5, 6]);
+    deque.remove_range(2..4);
+    assert_eq!(deque, [1, 2, 5, 6]);
+
+    let mut deque = wrapped(8, &[1, 2, 3], &[4, 5, 6]);
+    deque.remove_range(1..4);
+    assert_eq!(deque, [1, 5, 6]);
+
+    let mut deque = wrapped(8, &[1, 2, 3], &[4, 5, 6]);
+    deque.remove_range(2..5);
+    assert_eq!(deque, [1, 2, 6]);
+}
+
+#[test]
+fn truncate_with() {
+    let mut deque = wrapped(8, &[1, 2, 3], &[4, 5, 6]);
+    let mut dropped = StdVec::new();
+
+    deque.truncate_with(2, |value| dropped.push(value));
+    assert_eq!(deque, [1, 2]);
+    assert_eq!(dropped, [3, 4, 5, 6]);
+
+    deque.truncate_with(2, |value| dropped.push(value));
+    deque.truncate_with(5, |value| dropped.push(value));
+    assert_eq!(deque, [1, 2]);
+    assert_eq!(dropped.len(), 4);
+}
+
+#[test]
+fn truncate_with_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let values = (0..6).map(Rc::new).collect::<StdVec<_>>();
+    let mut deque = VecDeque::try_with_capacity(6).abort();
+
+    for value in &values {
+        deque.try_push_back(value.clone()).abort();
+    }
+
+    let mut seen = 0;
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        deque.truncate_with(1, |value| {
+            seen += 1;
+            drop(value);
+
+            if seen == 2 {
+                panic!("boom");
+            }
+        });
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(seen, 2);
+    assert_eq!(deque.len(), 1);
+
+    // Every removed element has been dropped, including the ones which were
+    // never passed to the callback.
+    for value in &values[1..] {
+        assert_eq!(Rc::strong_count(value), 1);
+    }
+
+    assert_eq!(Rc::strong_count(&values[0]), 2);
+}
+
+#[test]
+fn remove_range_drops() {
+    let values = (0..8).map(Rc::new).collect::<StdVec<_>>();
+
+    let mut deque = VecDeque::try_with_capacity(8).abort();
+
+    for value in &values[4..] {
+        deque.try_push_back(value.clone()).abort();
+    }
+
+    for value in values[..4].iter().rev() {
+        deque.try_push_front(value.clone()).abort();
+    }
+
+    deque.remove_range(3..6);
+    assert_eq!(deque, [0, 1, 2, 6, 7].map(Rc::new));
+
+    for value in &values {
+        let expected = if (3..6).contains(&**value) { 1 } else { 2 };
+        assert_eq!(Rc::strong_count(value), expected, "{value}");
+    }
+
+    drop(deque);
+    assert!(values.iter().all(|value| Rc::strong_count(value) == 1));
+}
+
+#[test]
+fn hash_contiguous_and_wrapped() {
+    use core::hash::{Hash, Hasher};
+    use std::collections::hash_map::DefaultHasher;
+
+    fn hash<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let contiguous = VecDeque::try_from([1, 2, 3, 4, 5]).abort();
+    assert!(contiguous.as_slices().1.is_empty());
+
+    let wrapped = wrapped(8, &[1, 2, 3], &[4, 5]);
+
+    assert_eq!(contiguous, wrapped);
+    assert_eq!(hash(&contiguous), hash(&wrapped));
+
+    let other = VecDeque::try_from([1, 2, 3, 4]).abort();
+    assert_ne!(hash(&contiguous), hash(&other));
+}
+
+#[test]
+fn into_sorted_by() {
+    #[derive(Debug, PartialEq)]
+    struct Entry {
+        key: u32,
+        name: &'static str,
+    }
+
+    let entries = [(3, "c"), (1, "a"), (2, "b"), (1, "d"), (0, "e"), (2, "f")];
+
+    let mut deque = VecDeque::try_with_capacity(8).abort();
+
+    for &(key, name) in &entries[..3] {
+        deque.try_push_front(Entry { key, name }).abort();
+    }
+
+    for &(key, name) in &entries[3..] {
+        deque.try_push_back(Entry { key, name }).abort();
+    }
+
+    assert!(!deque.as_slices().1.is_empty());
+
+    let vec = deque.into_sorted_by(|a, b| a.key.cmp(&b.key)).abort();
+    let names = vec.iter().map(|e| e.name).collect::<StdVec<_>>();
+    // Elements with equal keys keep their relative order.
+    assert_eq!(names, ["e", "a", "d", "b", "f", "c"]);
+
+    let deque = VecDeque::<i32>::new();
+    assert!(deque.into_sorted_by(|a, b| a.cmp(b)).abort().is_empty());
+
+    let deque = wrapped(8, &[5, 3, 1], &[4, 2]);
+    let vec = deque.into_sorted_by(|a, b| b.cmp(a)).abort();
+    assert_eq!(vec, [5, 4, 3, 2, 1]);
+}
+
+#[test]
+fn try_from_back_iter() {
+    let source = [1, 2, 3, 4, 5];
+
+    let deque = VecDeque::try_from_back_iter(source).abort();
+    let expected = source.iter().rev().copied().collect::<StdVec<_>>();
+    assert!(deque.iter().eq(expected.iter()));
+    assert!(deque.capacity() >= source.len());
+
+    // Iterators which don't report an exact size are still fully consumed.
+    let deque = VecDeque::try_from_back_iter(source.iter().filter(|&&n| n % 2 == 1)).abort();
+    assert_eq!(deque, [&5, &3, &1]);
+
+    let deque = VecDeque::<i32>::try_from_back_iter([]).abort();
+    assert!(deque.is_empty());
+}
+
+#[test]
+fn front_and_back_slice() {
+    let deque = wrapped(8, &[1, 2, 3], &[4, 5, 6]);
+    assert!(!deque.as_slices().1.is_empty());
+
+    assert_eq!(deque.front_slice(7), None);
+    assert_eq!(deque.back_slice(7), None);
+
+    // A front window which wraps around the end of the buffer.
+    let (a, b) = deque.front_slice(5).unwrap();
+    assert_eq!((a, b), (&[1, 2, 3][..], &[4, 5][..]));
+
+    let (a, b) = deque.back_slice(4).unwrap();
+    assert_eq!((a, b), (&[3][..], &[4, 5, 6][..]));
+
+    let (a, b) = deque.front_slice(2).unwrap();
+    assert_eq!((a, b), (&[1, 2][..], &[][..]));
+
+    let (a, b) = deque.front_slice(6).unwrap();
+    assert_eq!((a, b), deque.as_slices());
+
+    assert_eq!(deque.front_slice(0), Some((&[][..], &[][..])));
+    assert_eq!(deque.back_slice(0), Some((&[][..], &[][..])));
+
+    let empty = VecDeque::<i32>::new();
+    assert_eq!(empty.front_slice(1), None);
+    assert_eq!(empty.back_slice(0), Some((&[][..], &[][..])));
+}
+
+#[test]
+fn into_vec() {
+    use crate::clone::TryClone;
+    use crate::vec::Vec;
+
+    let contiguous = VecDeque::try_from([1, 2, 3, 4, 5]).abort();
+    let expected = Vec::from(contiguous.try_clone().abort());
+    assert_eq!(contiguous.into_vec(), expected);
+
+    let deque = wrapped(8, &[1, 2, 3], &[4, 5]);
+    let expected = Vec::from(deque.try_clone().abort());
+    let vec = deque.into_vec();
+    assert_eq!(vec, expected);
+    assert_eq!(vec, [1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn try_append_or_adopt() {
+    let mut a = VecDeque::new();
+    let mut b = wrapped(8, &[1, 2], &[3, 4]);
+    let ptr = b.as_slices().0.as_ptr();
+    let cap = b.capacity();
+
+    a.try_append_or_adopt(&mut b).abort();
+    assert_eq!(a, [1, 2, 3, 4]);
+    assert_eq!(a.as_slices().0.as_ptr(), ptr);
+    assert_eq!(a.capacity(), cap);
+    assert!(b.is_empty());
+
+    // A non-empty deque copies the elements as usual.
+    let mut c = wrapped(8, &[5], &[6]);
+    let cap = c.capacity();
+
+    a.try_append_or_adopt(&mut c).abort();
+    assert_eq!(a, [1, 2, 3, 4, 5, 6]);
+    assert!(c.is_empty());
+    assert_eq!(c.capacity(), cap);
+}
+
+#[test]
+fn try_extend_reserves_size_hint() {
+    use crate::iter::TryExtend;
+
+    let mut deque = VecDeque::new();
+    deque.try_extend(0..100).abort();
+    assert_eq!(deque.len(), 100);
+    assert_eq!(deque.capacity(), 100);
+
+    // Only the lower bound is reserved if the upper bound is larger.
+    let mut deque = VecDeque::new();
+    deque.try_extend((0..100).filter(|n| n % 2 == 0)).abort();
+    assert_eq!(deque.len(), 50);
+    assert_eq!(deque.capacity(), 64);
+}
+
+#[test]
+fn try_extend_unbounded() {
+    use crate::iter::TryExtend;
+
+    /// An iterator without an upper bound, which stops after a few elements.
+    struct Unbounded(usize);
+
+    impl Iterator for Unbounded {
+        type Item = usize;
+
+        fn next(&mut self) -> Option<usize> {
+            self.0 = self.0.checked_sub(1)?;
+            Some(self.0)
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.0, None)
+        }
+    }
+
+    let mut deque = VecDeque::new();
+    deque.try_extend(Unbounded(10)).abort();
+    assert_eq!(deque, [9, 8, 7, 6, 5, 4, 3, 2, 1, 0]);
+    assert_eq!(deque.capacity(), 10);
+}
+
+#[test]
+fn try_shrink_to_fit_contiguous() {
+    let mut deque = wrapped(16, &[1, 2, 3], &[4, 5]);
+    assert!(!deque.is_contiguous());
+
+    deque.try_shrink_to_fit_contiguous().abort();
+    assert_eq!(deque.head, 0);
+    assert!(deque.capacity() >= 5);
+    assert_eq!(deque.as_slices(), (&[1, 2, 3, 4, 5][..], &[][..]));
+
+    // Contiguous elements which don't start at the front are moved too.
+    let mut deque = VecDeque::try_with_capacity(16).abort();
+
+    for n in 0..8 {
+        deque.try_push_back(n).abort();
+    }
+
+    deque.drain(..4);
+    assert_ne!(deque.head, 0);
+
+    deque.try_shrink_to_fit_contiguous().abort();
+    assert_eq!(deque.head, 0);
+    assert_eq!(deque.as_slices(), (&[4, 5, 6, 7][..], &[][..]));
+}
+
+#[test]
+fn try_rotate_left_collecting() {
+    let mut deque = wrapped(8, &[1, 2, 3], &[4, 5, 6]);
+
+    let moved = deque.try_rotate_left_collecting(4).abort();
+    assert_eq!(moved, [1, 2, 3, 4]);
+    assert_eq!(deque, [5, 6, 1, 2, 3, 4]);
+
+    let moved = deque.try_rotate_left_collecting(0).abort();
+    assert!(moved.is_empty());
+    assert_eq!(deque, [5, 6, 1, 2, 3, 4]);
+
+    let moved = deque.try_rotate_left_collecting(6).abort();
+    assert_eq!(moved, [5, 6, 1, 2, 3, 4]);
+    assert_eq!(deque, [5, 6, 1, 2, 3, 4]);
+}
+
+#[test]
+fn dedup_by_key() {
+    #[derive(Debug, PartialEq)]
+    struct Item {
+        id: u32,
+        value: &'static str,
+    }
+
+    let mut deque = VecDeque::try_with_capacity(8).abort();
+
+    for (id, value) in [(2, "c"), (2, "d"), (3, "e"), (3, "f")] {
+        deque.try_push_back(Item { id, value }).abort();
+    }
+
+    for (id, value) in [(1, "a"), (1, "b"), (2, "x")].into_iter().rev() {
+        deque.try_push_front(Item { id, value }).abort();
+    }
+
+    assert!(!deque.is_contiguous());
+
+    deque.dedup_by_key(|item| item.id);
+
+    let values = deque.iter().map(|item| item.value).collect::<StdVec<_>>();
+    assert_eq!(values, ["a", "x", "e"]);
+
+    let mut deque = wrapped(8, &[1, 1, 2], &[2, 2, 3]);
+    deque.dedup_by_key(|x| *x);
+    assert_eq!(deque, [1, 2, 3]);
+
+    let mut deque = VecDeque::<i32>::new();
+    deque.dedup_by_key(|x| *x);
+    assert!(deque.is_empty());
+}
+
+#[test]
+fn try_with_capacity_is_exact() {
+    for capacity in [1, 3, 7, 10, 100] {
+        let deque = VecDeque::<u64>::try_with_capacity(capacity).abort();
+        assert_eq!(deque.capacity(), capacity);
+
+        let deque = VecDeque::<u8>::try_with_capacity_in(capacity, Global).abort();
+        assert_eq!(deque.capacity(), capacity);
+    }
+
+    // Growing past the exact capacity is amortized.
+    let mut deque = VecDeque::try_with_capacity(3).abort();
+
+    for n in 0..4 {
+        deque.try_push_back(n).abort();
+    }
+
+    assert!(deque.capacity() >= 6);
+}
+
+#[test]
+fn try_merge_sorted() {
+    let mut deque = wrapped(8, &[1, 4, 6], &[6, 9]);
+    deque.try_merge_sorted([0, 2, 6, 7, 10, 11]).abort();
+    assert_eq!(deque, [0, 1, 2, 4, 6, 6, 6, 7, 9, 10, 11]);
+
+    let mut deque = VecDeque::new();
+    deque.try_merge_sorted([1, 2, 3]).abort();
+    assert_eq!(deque, [1, 2, 3]);
+
+    let mut deque = wrapped(4, &[1, 2], &[3]);
+    deque.try_merge_sorted([]).abort();
+    assert_eq!(deque, [1, 2, 3]);
+
+    // Elements in the deque are placed before equal elements from the
+    // iterator.
+    let mut deque = VecDeque::new();
+    deque.try_push_back((1, 'a')).abort();
+    deque.try_push_back((2, 'a')).abort();
+    deque.try_merge_sorted([(1, 'b'), (2, 'b')]).abort();
+    assert_eq!(deque, [(1, 'a'), (1, 'b'), (2, 'a'), (2, 'b')]);
+
+    // Merge pseudo-random sorted sequences.
+    let mut state = 0x2545_f491u32;
+
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        (state % 100) as i32
+    };
+
+    for _ in 0..32 {
+        let mut a = (0..next() % 16).map(|_| next()).collect::<StdVec<_>>();
+        let mut b = (0..next() % 16).map(|_| next()).collect::<StdVec<_>>();
+        a.sort();
+        b.sort();
+
+        let mut deque = VecDeque::new();
+
+        for &value in &a {
+            deque.try_push_back(value).abort();
+        }
+
+        deque.try_merge_sorted(b.iter().copied()).abort();
+
+        let mut expected = a.clone();
+        expected.extend_from_slice(&b);
+        expected.sort();
+
+        assert!(deque.iter().copied().eq(expected));
+    }
+}
+
+#[test]
+fn try_clear_and_shrink() {
+    let mut deque = wrapped(64, &[1, 2, 3], &[4, 5, 6]);
+    assert_ne!(deque.head, 0);
+
+    deque.try_clear_and_shrink(8).abort();
+    assert!(deque.is_empty());
+    assert_eq!(deque.head, 0);
+    assert!((8..64).contains(&deque.capacity()));
+
+    // Growing the capacity isn't done by shrinking.
+    deque.try_clear_and_shrink(32).abort();
+    assert!((8..32).contains(&deque.capacity()));
+
+    deque.try_push_back(7).abort();
+    assert_eq!(deque, [7]);
+}
+
+#[test]
+fn try_split_when() {
+    let mut deque = wrapped(8, &[1, 2, 3], &[-1, 4, -1]);
+
+    let rest = deque.try_split_when(|&x| x < 0).abort();
+    assert_eq!(deque, [1, 2, 3]);
+    assert_eq!(rest.unwrap(), [-1, 4, -1]);
+
+    assert!(deque.try_split_when(|&x| x < 0).abort().is_none());
+    assert_eq!(deque, [1, 2, 3]);
+
+    // Matching the front element moves everything into the returned deque.
+    let rest = deque.try_split_when(|_| true).abort();
+    assert!(deque.is_empty());
+    assert_eq!(rest.unwrap(), [1, 2, 3]);
+}
+
+#[test]
+fn try_binary_insert() {
+    let mut deque = wrapped(8, &[2, 4], &[6, 8]);
+
+    assert_eq!(deque.try_binary_insert(5).abort(), Err(2));
+    assert_eq!(deque.try_binary_insert(0).abort(), Err(0));
+    assert_eq!(deque.try_binary_insert(9).abort(), Err(6));
+    assert_eq!(deque, [0, 2, 4, 5, 6, 8, 9]);
+
+    for (index, value) in [0, 2, 4, 5, 6, 8, 9].into_iter().enumerate() {
+        assert_eq!(deque.try_binary_insert(value).abort(), Ok(index));
+    }
+
+    assert_eq!(deque, [0, 2, 4, 5, 6, 8, 9]);
+
+    let mut deque = VecDeque::new();
+
+    for value in [5, 1, 5, 3, 1, 4, 2, 3] {
+        _ = deque.try_binary_insert(value).abort();
+    }
+
+    assert_eq!(deque, [1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn as_ptr_physical_head() {
+    let mut deque = VecDeque::try_with_capacity(4).abort();
+
+    for value in 0..4 {
+        deque.try_push_back(value).abort();
+    }
+
+    for value in 4..7 {
+        assert_eq!(deque.pop_front(), Some(value - 4));
+        deque.try_push_back(value).abort();
+
+        let front = unsafe { *deque.as_ptr().add(deque.physical_head()) };
+        assert_eq!(Some(&front), deque.front());
+    }
+
+    // The elements have wrapped around the end of the buffer.
+    let (a, b) = deque.as_slices();
+    assert!(!a.is_empty() && !b.is_empty());
+    assert_eq!(deque.as_ptr(), b.as_ptr());
+
+    let head = deque.physical_head();
+
+    unsafe {
+        *deque.as_mut_ptr().add(head) = 10;
+    }
+
+    assert_eq!(deque, [10, 4, 5, 6]);
+}
+
+#[test]
+fn boxed_slice_round_trip() {
+    let cases: [(&[i32], &[i32]); 3] = [(&[1], &[2, 3]), (&[1, 2], &[3, 4, 5]), (&[1, 2], &[])];
+
+    for (front, back) in cases {
+        let deque = wrapped(8, front, back);
+        let expected: StdVec<_> = deque.iter().copied().collect();
+
+        let slice = deque.try_into_boxed_slice().abort();
+        assert_eq!(&*slice, &expected[..]);
+
+        let deque = VecDeque::from_boxed_slice(slice);
+        assert_eq!(deque.head, 0);
+        assert_eq!(deque.capacity(), expected.len());
+        assert!(deque.iter().eq(expected.iter()));
+    }
+}
+
+#[test]
+fn try_into_array() {
+    let deque = wrapped(8, &[1, 2], &[3, 4, 5]);
+
+    let Err(deque) = deque.try_into_array::<4>() else {
+        panic!("expected the length to mismatch");
+    };
+
+    assert_eq!(deque.as_slices(), (&[1, 2][..], &[3, 4, 5][..]));
+
+    let Err(deque) = deque.try_into_array::<6>() else {
+        panic!("expected the length to mismatch");
+    };
+
+    assert_eq!(deque, [1, 2, 3, 4, 5]);
+    assert_eq!(deque.try_into_array::<5>().ok(), Some([1, 2, 3, 4, 5]));
+
+    let empty = VecDeque::<i32>::new();
+    assert_eq!(empty.try_into_array::<0>().ok(), Some([]));
+
+    // Elements are moved out exactly once.
+    let counter = Rc::new(());
+    let mut deque = VecDeque::new();
+
+    for _ in 0..3 {
+        deque.try_push_front(counter.clone()).abort();
+    }
+
+    let Err(deque) = deque.try_into_array::<2>() else {
+        panic!("expected the length to mismatch");
+    };
+
+    assert_eq!(Rc::strong_count(&counter), 4);
+    let array = deque.try_into_array::<3>().ok().unwrap();
+    assert_eq!(Rc::strong_count(&counter), 4);
+    drop(array);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+fn retain_range() {
+    let mut deque = wrapped(16, &[0, 1, 2, 3], &[4, 5, 6, 7, 8, 9]);
+    deque.retain_range(2..8, |&x| x % 3 == 0);
+    assert_eq!(deque, [0, 1, 3, 6, 8, 9]);
+
+    let mut deque = wrapped(16, &[0, 1, 2, 3], &[4, 5, 6, 7, 8, 9]);
+    deque.retain_range(.., |&x| x % 2 == 0);
+    assert_eq!(deque, [0, 2, 4, 6, 8]);
+
+    let mut deque = wrapped(16, &[0, 1, 2, 3], &[4, 5, 6, 7, 8, 9]);
+    deque.retain_range(3..3, |_| false);
+    deque.retain_range(3..7, |_| true);
+    assert_eq!(deque, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+    deque.retain_range(7.., |_| false);
+    assert_eq!(deque, [0, 1, 2, 3, 4, 5, 6]);
+
+    deque.retain_range(..3, |_| false);
+    assert_eq!(deque, [3, 4, 5, 6]);
+
+    // Removed elements are dropped exactly once.
+    let counter = Rc::new(());
+    let mut deque = VecDeque::new();
+
+    for _ in 0..6 {
+        deque.try_push_back(counter.clone()).abort();
+    }
+
+    let mut n = 0;
+
+    deque.retain_range(1..5, |_| {
+        n += 1;
+        n % 2 == 0
+    });
+
+    assert_eq!(deque.len(), 4);
+    assert_eq!(Rc::strong_count(&counter), 5);
+}
+
+#[test]
+#[should_panic]
+fn retain_range_out_of_bounds() {
+    let mut deque = VecDeque::try_from([1, 2, 3]).abort();
+    deque.retain_range(1..4, |_| true);
+}
+
+#[test]
+fn first_last_chunk() {
+    let mut deque = wrapped(8, &[1, 2, 3], &[4, 5]);
+
+    assert_eq!(deque.first_chunk::<0>(), Some([]));
+    assert_eq!(deque.first_chunk::<4>(), Some([&1, &2, &3, &4]));
+    assert_eq!(deque.first_chunk::<5>(), Some([&1, &2, &3, &4, &5]));
+    assert_eq!(deque.first_chunk::<6>(), None);
+
+    assert_eq!(deque.last_chunk::<0>(), Some([]));
+    assert_eq!(deque.last_chunk::<3>(), Some([&3, &4, &5]));
+    assert_eq!(deque.last_chunk::<5>(), Some([&1, &2, &3, &4, &5]));
+    assert_eq!(deque.last_chunk::<6>(), None);
+
+    for value in deque.first_chunk_mut::<4>().unwrap() {
+        *value *= 10;
+    }
+
+    for value in deque.last_chunk_mut::<2>().unwrap() {
+        *value += 1;
+    }
+
+    assert!(deque.first_chunk_mut::<6>().is_none());
+    assert!(deque.last_chunk_mut::<6>().is_none());
+    assert_eq!(deque, [10, 20, 30, 41, 6]);
+
+    assert_eq!(deque.pop_first_chunk::<6>(), None);
+    assert_eq!(deque.pop_last_chunk::<6>(), None);
+    assert_eq!(deque.len(), 5);
+
+    assert_eq!(deque.pop_first_chunk::<2>(), Some([10, 20]));
+    assert_eq!(deque.pop_last_chunk::<2>(), Some([41, 6]));
+    assert_eq!(deque, [30]);
+
+    assert_eq!(deque.pop_last_chunk::<1>(), Some([30]));
+    assert!(deque.is_empty());
+}
+
+#[test]
+fn drain_tail() {
+    for from in 0..=6 {
+        let mut expected = wrapped(8, &[1, 2, 3, 4], &[5, 6]);
+        let mut deque = wrapped(8, &[1, 2, 3, 4], &[5, 6]);
+
+        let expected_drained = expected.drain(from..).collect::<StdVec<_>>();
+
+        let head = deque.physical_head();
+        let kept = deque.iter().map(|v| v as *const i32).collect::<StdVec<_>>();
+
+        let drained = deque.drain_tail(from).collect::<StdVec<_>>();
+        assert_eq!(drained, expected_drained);
+        assert_eq!(deque, expected);
+
+        // None of the kept elements were moved.
+        if !deque.is_empty() {
+            assert_eq!(deque.physical_head(), head);
+        }
+
+        for (value, ptr) in deque.iter().zip(&kept) {
+            assert!(core::ptr::eq(value, *ptr));
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn drain_tail_out_of_bounds() {
+    let mut deque = wrapped(8, &[1, 2], &[3]);
+    deque.drain_tail(4);
+}
+
+#[test]
+fn swap_ranges() {
+    let mut deque = wrapped(8, &[1, 2, 3], &[4, 5, 6]);
+    deque.swap_ranges(0..2, 4..6);
+    assert_eq!(deque, [5, 6, 3, 4, 1, 2]);
+
+    // The first window straddles the physical end of the buffer.
+    let mut deque = wrapped(8, &[1, 2, 3], &[4, 5, 6]);
+    deque.swap_ranges(2..4, 0..2);
+    assert_eq!(deque, [3, 4, 1, 2, 5, 6]);
+
+    let mut deque = wrapped(8, &[1, 2, 3], &[4, 5, 6]);
+    deque.swap_ranges(2..2, 0..0);
+    assert_eq!(deque, [1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+#[should_panic]
+fn swap_ranges_different_lengths() {
+    let mut deque = wrapped(8, &[1, 2, 3], &[4, 5, 6]);
+    deque.swap_ranges(0..2, 3..6);
+}
+
+#[test]
+#[should_panic]
+fn swap_ranges_overlapping() {
+    let mut deque = wrapped(8, &[1, 2, 3], &[4, 5, 6]);
+    deque.swap_ranges(0..3, 2..5);
+}
+
+#[test]
+fn shrink_to_fit_minimal() {
+    use crate::testing::counting_alloc::CountingAlloc;
+
+    let alloc = CountingAlloc::new();
+
+    let mut deque = VecDeque::try_with_capacity_in(16, alloc.clone()).abort();
+
+    for value in 0..4 {
+        deque.try_push_back(value).abort();
+    }
+
+    deque.try_shrink_to_fit().abort();
+    assert_eq!(deque.capacity(), deque.len());
+
+    let before = alloc.calls();
+
+    for _ in 0..8 {
+        deque.try_shrink_to_fit().abort();
+    }
+
+    assert_eq!(alloc.calls(), before);
+    assert_eq!(deque, [0, 1, 2, 3]);
+}
+
+#[test]
+fn rotate_to_min() {
+    let mut a = wrapped(8, &[5, 2, 3], &[1, 4]);
+    let mut b = wrapped(8, &[1, 4], &[5, 2, 3]);
+
+    a.rotate_to_min();
+    b.rotate_to_min();
+
+    assert_eq!(a, [1, 4, 5, 2, 3]);
+    assert_eq!(a, b);
+
+    // The first minimum is moved to the front.
+    let mut c = wrapped(8, &[5, 1, 3], &[1, 4]);
+    c.rotate_to_min();
+    assert_eq!(c, [1, 3, 1, 4, 5]);
+
+    let mut empty = VecDeque::<i32>::new();
+    empty.rotate_to_min();
+    assert!(empty.is_empty());
+}
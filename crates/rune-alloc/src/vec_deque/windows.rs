@@ -0,0 +1,131 @@
+use core::fmt;
+use core::iter::FusedIterator;
+
+/// An iterator over overlapping windows of the elements of a `VecDeque`.
+///
+/// Since the elements of a deque might wrap around the end of its buffer, each
+/// window is produced as a pair of slices which, when concatenated, make up the
+/// window in logical order. Windows which do not straddle the wrap point have
+/// an empty second slice.
+///
+/// This `struct` is created by the [`windows`] method on [`super::VecDeque`].
+/// See its documentation for more.
+///
+/// [`windows`]: super::VecDeque::windows
+pub struct Windows<'a, T>
+where
+    T: 'a,
+{
+    a: &'a [T],
+    b: &'a [T],
+    size: usize,
+    start: usize,
+    end: usize,
+}
+
+impl<'a, T> Windows<'a, T> {
+    pub(super) fn new(a: &'a [T], b: &'a [T], size: usize) -> Self {
+        let len = a.len() + b.len();
+
+        let end = if len < size { 0 } else { len - size + 1 };
+
+        Self {
+            a,
+            b,
+            size,
+            start: 0,
+            end,
+        }
+    }
+
+    /// Get the window starting at the logical index `at`.
+    #[inline]
+    fn window(&self, at: usize) -> (&'a [T], &'a [T]) {
+        let split = self.a.len();
+        let end = at + self.size;
+
+        if end <= split {
+            (&self.a[at..end], &[])
+        } else if at >= split {
+            (&self.b[at - split..end - split], &[])
+        } else {
+            (&self.a[at..], &self.b[..end - split])
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Windows<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Windows")
+            .field("a", &self.a)
+            .field("b", &self.b)
+            .field("size", &self.size)
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .finish()
+    }
+}
+
+impl<T> Clone for Windows<'_, T> {
+    fn clone(&self) -> Self {
+        Windows {
+            a: self.a,
+            b: self.b,
+            size: self.size,
+            start: self.start,
+            end: self.end,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Windows<'a, T> {
+    type Item = (&'a [T], &'a [T]);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+
+        let window = self.window(self.start);
+        self.start += 1;
+        Some(window)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.start = self.start.saturating_add(n).min(self.end);
+        self.next()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<T> DoubleEndedIterator for Windows<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        Some(self.window(self.end))
+    }
+}
+
+impl<T> ExactSizeIterator for Windows<'_, T> {
+    fn len(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+impl<T> FusedIterator for Windows<'_, T> {}
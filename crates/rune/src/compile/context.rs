@@ -481,6 +481,29 @@ impl Context {
         Ok(())
     }
 
+    /// Iterate over the items registered in the [Context] together with
+    /// their hashes, such as modules, types and functions.
+    ///
+    /// This is useful to offer completions in an interactive prompt.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::Context;
+    ///
+    /// let context = Context::with_default_modules()?;
+    ///
+    /// assert!(context
+    ///     .items()
+    ///     .any(|(item, _)| item == rune::item!(::std::i64::max)));
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn items(&self) -> impl Iterator<Item = (&Item, Hash)> + '_ {
+        self.meta
+            .iter()
+            .filter_map(|meta| Some((meta.item.as_deref()?, meta.hash)))
+    }
+
     /// Iterate over all available functions in the [Context].
     #[cfg(any(feature = "cli", feature = "languageserver"))]
     pub(crate) fn iter_functions(&self) -> impl Iterator<Item = (&ContextMeta, &meta::Signature)> {
@@ -897,6 +897,9 @@ mod musli;
 #[cfg(feature = "serde")]
 mod serde;
 
+#[cfg(test)]
+mod testing;
+
 #[cfg(test)]
 mod tests;
 
@@ -3,7 +3,6 @@
 use crate as rune;
 use crate::alloc;
 use crate::alloc::clone::TryClone;
-use crate::alloc::fmt::TryWrite;
 use crate::runtime::object::{RuneIter, RuneIterKeys, RuneValues};
 use crate::runtime::{EnvProtocolCaller, Formatter, Object, Protocol, Value, VmError};
 use crate::{ContextError, Module};
@@ -198,6 +197,6 @@ fn clone(this: &Object) -> alloc::Result<Object> {
 /// ```
 #[rune::function(keep, instance, protocol = DEBUG_FMT)]
 #[inline]
-fn debug_fmt(this: &Object, f: &mut Formatter) -> alloc::Result<()> {
-    write!(f, "{this:?}")
+fn debug_fmt(this: &Object, f: &mut Formatter) -> Result<(), VmError> {
+    this.debug_fmt_with(f, &mut EnvProtocolCaller)
 }
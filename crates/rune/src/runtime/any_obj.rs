@@ -485,6 +485,12 @@ impl AnyObj {
         }
     }
 
+    /// Test if the value is owned, as opposed to referencing data which is
+    /// owned externally such as by the host.
+    pub(crate) fn is_owned(&self) -> bool {
+        vtable(self).is_owned()
+    }
+
     /// Get access snapshot of shared value.
     pub(crate) fn snapshot(&self) -> Snapshot {
         unsafe { self.shared.as_ref().access.snapshot() }
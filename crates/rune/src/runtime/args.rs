@@ -193,3 +193,32 @@ impl Args for rust_alloc::vec::Vec<Value> {
         self.len()
     }
 }
+
+/// Arguments from a slice of values, which are pushed onto the stack without
+/// first having to be collected into an owned vector.
+impl Args for &[Value] {
+    #[inline]
+    fn into_stack(self, stack: &mut Stack) -> Result<(), VmError> {
+        for value in self {
+            stack.push(value.clone())?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn try_into_vec(self) -> Result<Vec<Value>, VmError> {
+        let mut vec = Vec::try_with_capacity(self.len())?;
+
+        for value in self {
+            vec.try_push(value.clone())?;
+        }
+
+        Ok(vec)
+    }
+
+    #[inline]
+    fn count(&self) -> usize {
+        self.len()
+    }
+}
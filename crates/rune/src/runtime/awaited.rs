@@ -33,6 +33,7 @@ impl Awaited {
                 let select = unsafe { Pin::new_unchecked(select) };
                 let result = ready!(select.poll(cx));
                 let (ip, value) = async_vm_try!(VmError::with_vm(result, vm));
+                async_vm_try!(vm.record_select(ip));
                 vm.set_ip(ip);
                 async_vm_try!(vm.stack_mut().store(out, || value));
             }
@@ -0,0 +1,62 @@
+use core::future::Future;
+use core::mem::ManuallyDrop;
+use core::pin::pin;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+use crate::alloc::alloc::Global;
+use crate::sync::Arc;
+
+use super::VmErrorKind;
+
+static WAKER_VTABLE: RawWakerVTable =
+    RawWakerVTable::new(waker_clone, waker_wake, waker_wake_by_ref, waker_drop);
+
+unsafe fn waker_clone(data: *const ()) -> RawWaker {
+    let woken = ManuallyDrop::new(Arc::from_raw_in(data.cast::<AtomicBool>(), Global));
+    let (data, Global) = Arc::into_raw_with_allocator((*woken).clone());
+    RawWaker::new(data.cast(), &WAKER_VTABLE)
+}
+
+unsafe fn waker_wake(data: *const ()) {
+    let woken = Arc::from_raw_in(data.cast::<AtomicBool>(), Global);
+    woken.store(true, Ordering::Release);
+}
+
+unsafe fn waker_wake_by_ref(data: *const ()) {
+    (*data.cast::<AtomicBool>()).store(true, Ordering::Release);
+}
+
+unsafe fn waker_drop(data: *const ()) {
+    _ = Arc::from_raw_in(data.cast::<AtomicBool>(), Global);
+}
+
+/// Drive the given future to completion on the current thread.
+///
+/// The future is polled again for as long as it wakes itself up while being
+/// polled. If it is pending without having been woken up, it's waiting for
+/// something which can't complete synchronously and
+/// [`VmErrorKind::WouldBlock`] is returned.
+pub(crate) fn block_on<F>(future: F) -> Result<F::Output, VmErrorKind>
+where
+    F: Future,
+{
+    let woken = Arc::try_new(AtomicBool::new(false))?;
+    let (data, Global) = Arc::into_raw_with_allocator(woken.clone());
+
+    // SAFETY: The data is a leaked reference count of the flag, which is
+    // released when the waker is dropped.
+    let waker = unsafe { Waker::from_raw(RawWaker::new(data.cast(), &WAKER_VTABLE)) };
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return Ok(output);
+        }
+
+        if !woken.swap(false, Ordering::AcqRel) {
+            return Err(VmErrorKind::WouldBlock);
+        }
+    }
+}
@@ -126,6 +126,19 @@ impl BudgetGuard {
         self.0 -= 1;
         true
     }
+
+    /// Get the number of permits remaining in the budget, which is
+    /// `usize::MAX` if it's unlimited.
+    #[inline]
+    pub(crate) fn remaining(&self) -> usize {
+        self.0
+    }
+
+    /// Replace the remaining budget with the given number of permits.
+    #[inline]
+    pub(crate) fn grant(&mut self, budget: usize) {
+        self.0 = budget;
+    }
 }
 
 impl Drop for BudgetGuard {
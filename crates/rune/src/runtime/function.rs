@@ -188,6 +188,15 @@ impl Function {
         self.0.call_with_vm(vm, addr, args, out)
     }
 
+    /// Call the function to completion using the given virtual machine as
+    /// the entrypoint, which re-uses its allocations in case the function
+    /// lives in the same unit and context as the virtual machine.
+    ///
+    /// Any other function is called as if through [`Function::call`].
+    pub(crate) fn call_in_vm(&self, vm: &mut Vm, args: impl GuardedArgs) -> Result<Value, VmError> {
+        self.0.call_in_vm(vm, args)
+    }
+
     /// Create a function pointer from a handler.
     pub(crate) fn from_handler(handler: FunctionHandler, hash: Hash) -> Self {
         Self(FunctionImpl::from_handler(handler, hash))
@@ -594,6 +603,22 @@ where
         unsafe { AssertSend::new(future) }
     }
 
+    /// Call the function to completion using the given virtual machine as
+    /// the entrypoint if possible.
+    fn call_in_vm(&self, vm: &mut Vm, args: impl GuardedArgs) -> Result<Value, VmError> {
+        match &self.inner {
+            Inner::FnOffset(fn_offset) if fn_offset.is_immediate_in(vm) => {
+                fn_offset.call_in_vm(vm, args, ())
+            }
+            Inner::FnClosureOffset(closure) if closure.fn_offset.is_immediate_in(vm) => {
+                let environment = closure.environment.try_clone()?;
+                let environment = OwnedTuple::try_from(environment)?;
+                closure.fn_offset.call_in_vm(vm, args, (environment,))
+            }
+            _ => self.call(args),
+        }
+    }
+
     /// Call with the given virtual machine. This allows for certain
     /// optimizations, like avoiding the allocation of a new vm state in case
     /// the call is internal.
@@ -867,6 +892,25 @@ impl FnOffset {
         self.call.call_with_vm(vm)
     }
 
+    /// Test if the function can be called immediately as an entrypoint in the
+    /// given virtual machine.
+    #[inline]
+    fn is_immediate_in(&self, vm: &Vm) -> bool {
+        matches!(self.call, Call::Immediate) && vm.is_same(&self.context, &self.unit)
+    }
+
+    /// Perform a call to completion using the specified vm as the entrypoint.
+    #[tracing::instrument(skip_all, fields(args = args.count(), extra = extra.count(), ?self.offset, ?self.call, ?self.args, ?self.hash))]
+    fn call_in_vm(
+        &self,
+        vm: &mut Vm,
+        args: impl GuardedArgs,
+        extra: impl Args,
+    ) -> Result<Value, VmError> {
+        check_args(args.count().wrapping_add(extra.count()), self.args)?;
+        vm.call_offset(self.offset, args, extra)
+    }
+
     /// Perform a potentially optimized call into the specified vm.
     ///
     /// This will cause a halt in case the vm being called into isn't the same
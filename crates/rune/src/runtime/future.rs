@@ -122,6 +122,12 @@ impl<T, F> SelectFuture<T, F> {
     pub fn new(data: T, future: F) -> Self {
         Self { data, future }
     }
+
+    /// Get the data associated with the future.
+    #[inline]
+    pub(crate) fn data(&self) -> &T {
+        &self.data
+    }
 }
 
 impl<T, F> future::Future for SelectFuture<T, F>
@@ -1,5 +1,5 @@
 use core::fmt;
-use core::iter;
+use core::iter::{self, FusedIterator};
 
 use crate as rune;
 use crate::alloc::clone::TryClone;
@@ -61,6 +61,69 @@ impl Generator {
     }
 
     /// Resume the generator with a value and get the next [`GeneratorState`].
+    ///
+    /// The value is produced by the `yield` expression which the generator is
+    /// suspended on, which allows the generator to be used as a coroutine.
+    /// Since a generator which hasn't started yet isn't suspended on a
+    /// `yield`, the value passed in when it is first resumed is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::{Generator, GeneratorState};
+    /// use rune::sync::Arc;
+    /// use rune::{Context, Vm};
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         fn accumulate() {
+    ///             let total = 0;
+    ///
+    ///             loop {
+    ///                 let value = yield total;
+    ///
+    ///                 if let () = value {
+    ///                     return total;
+    ///                 }
+    ///
+    ///                 total += value;
+    ///             }
+    ///         }
+    ///
+    ///         pub fn main() {
+    ///             accumulate()
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::try_new(context.runtime()?)?;
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let unit = Arc::try_new(unit)?;
+    ///
+    /// let mut vm = Vm::new(runtime, unit);
+    ///
+    /// let mut generator: Generator = rune::from_value(vm.call(["main"], ())?)?;
+    ///
+    /// // The first value is ignored, since the generator hasn't started yet.
+    /// let GeneratorState::Yielded(total) = generator.resume(rune::to_value(())?)? else {
+    ///     panic!("expected the generator to yield");
+    /// };
+    ///
+    /// assert_eq!(rune::from_value::<i64>(total)?, 0);
+    ///
+    /// for n in 1..=3i64 {
+    ///     generator.resume(rune::to_value(n)?)?;
+    /// }
+    ///
+    /// let GeneratorState::Complete(total) = generator.resume(rune::to_value(())?)? else {
+    ///     panic!("expected the generator to complete");
+    /// };
+    ///
+    /// assert_eq!(rune::from_value::<i64>(total)?, 6);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
     pub fn resume(&mut self, value: Value) -> Result<GeneratorState, VmError> {
         let execution = self
             .execution
@@ -89,6 +152,53 @@ impl Generator {
     }
 }
 
+/// Drive the generator as a Rust iterator, producing each value it yields
+/// until it completes.
+///
+/// If resuming the generator errors, the error is produced and iteration
+/// stops. The exception is running out of budget, after which iteration can
+/// continue once more budget is available.
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::Generator;
+/// use rune::sync::Arc;
+/// use rune::{Context, Vm};
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         fn numbers(n) {
+///             for n in 0..n {
+///                 yield n;
+///             }
+///         }
+///
+///         pub fn main() {
+///             numbers(3)
+///         }
+///     }
+/// };
+///
+/// let context = Context::with_default_modules()?;
+/// let runtime = Arc::try_new(context.runtime()?)?;
+///
+/// let unit = rune::prepare(&mut sources).build()?;
+/// let unit = Arc::try_new(unit)?;
+///
+/// let mut vm = Vm::new(runtime, unit);
+///
+/// let generator: Generator = rune::from_value(vm.call(["main"], ())?)?;
+///
+/// let mut values = Vec::new();
+///
+/// for value in generator {
+///     values.push(rune::from_value::<i64>(value?)?);
+/// }
+///
+/// assert_eq!(values, [0, 1, 2]);
+/// # Ok::<_, rune::support::Error>(())
+/// ```
 impl IntoIterator for Generator {
     type Item = Result<Value, VmError>;
     type IntoIter = Iter;
@@ -117,14 +227,31 @@ impl iter::Iterator for Iter {
 
     #[inline]
     fn next(&mut self) -> Option<Result<Value, VmError>> {
-        match Iter::next(self) {
-            Ok(Some(value)) => Some(Ok(value)),
-            Ok(None) => None,
-            Err(error) => Some(Err(error)),
+        let execution = self.generator.execution.as_mut()?;
+
+        match execution.resume().complete() {
+            Ok(VmOutcome::Complete(_)) => {
+                self.generator.execution = None;
+                None
+            }
+            Ok(VmOutcome::Yielded(value)) => Some(Ok(value)),
+            // NB: A generator which ran out of budget can be resumed once more
+            // budget is available, so the iterator is not fused.
+            Ok(VmOutcome::Limited) => Some(Err(VmError::from(VmErrorKind::Halted {
+                halt: VmHaltInfo::Limited,
+            }))),
+            Err(error) => {
+                // NB: Resuming a generator which has errored is not
+                // meaningful, so we fuse the iterator.
+                self.generator.execution = None;
+                Some(Err(error))
+            }
         }
     }
 }
 
+impl FusedIterator for Iter {}
+
 impl fmt::Debug for Generator {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Generator")
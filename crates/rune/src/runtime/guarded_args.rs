@@ -107,3 +107,22 @@ impl GuardedArgs for rust_alloc::vec::Vec<Value> {
         self.len()
     }
 }
+
+impl GuardedArgs for &[Value] {
+    type Guard = ();
+
+    #[inline]
+    unsafe fn guarded_into_stack(self, stack: &mut Stack) -> Result<Self::Guard, VmError> {
+        self.into_stack(stack)
+    }
+
+    #[inline]
+    unsafe fn guarded_into_vec(self) -> Result<(Vec<Value>, Self::Guard), VmError> {
+        Ok((self.try_into_vec()?, ()))
+    }
+
+    #[inline]
+    fn count(&self) -> usize {
+        self.len()
+    }
+}
@@ -10,6 +10,8 @@ use crate::alloc::alloc::Global;
 use crate::alloc::prelude::*;
 use crate::alloc::{self, Vec};
 
+#[cfg(debug_assertions)]
+use super::Inline;
 use super::{Address, IntoOutput, Output, Value, VmErrorKind};
 
 // This is a bit tricky. We know that `Value::empty()` is `Sync` but we can't
@@ -292,6 +294,12 @@ pub struct Stack {
     ///
     /// It is not possible to interact with values below this stack frame.
     top: usize,
+    /// The size the current stack frame was allocated with.
+    ///
+    /// This is used in debug builds to verify that the stack is balanced when
+    /// a frame is popped.
+    #[cfg(debug_assertions)]
+    size: usize,
 }
 
 impl Stack {
@@ -301,6 +309,8 @@ impl Stack {
         Self {
             stack: Vec::new(),
             top: 0,
+            #[cfg(debug_assertions)]
+            size: 0,
         }
     }
 
@@ -454,6 +464,11 @@ impl Stack {
     /// Try to resize the stack with space for the given size.
     #[inline]
     pub(crate) fn resize(&mut self, size: usize) -> alloc::Result<()> {
+        #[cfg(debug_assertions)]
+        {
+            self.size = size;
+        }
+
         if size == 0 {
             return Ok(());
         }
@@ -468,6 +483,8 @@ impl Stack {
         Ok(Self {
             stack: Vec::try_with_capacity(capacity)?,
             top: 0,
+            #[cfg(debug_assertions)]
+            size: 0,
         })
     }
 
@@ -510,11 +527,32 @@ impl Stack {
         self.stack.drain(self.top..)
     }
 
+    /// Drain the values beyond the size the current stack frame was allocated
+    /// with.
+    ///
+    /// Slots which are left behind empty when values are moved out of them,
+    /// like the arguments of a function called through a protocol, are
+    /// removed but not produced.
+    #[cfg(debug_assertions)]
+    #[inline]
+    pub(crate) fn drain_residual(&mut self) -> impl DoubleEndedIterator<Item = Value> + '_ {
+        let start = self.top.wrapping_add(self.size).min(self.stack.len());
+
+        self.stack
+            .drain(start..)
+            .filter(|value| !matches!(value.as_inline_unchecked(), Some(Inline::Empty)))
+    }
+
     /// Clear the current stack.
     #[inline]
     pub(crate) fn clear(&mut self) {
         self.stack.clear();
         self.top = 0;
+
+        #[cfg(debug_assertions)]
+        {
+            self.size = 0;
+        }
     }
 
     /// Get the offset that corresponds to the bottom of the stack right now.
@@ -528,8 +566,21 @@ impl Stack {
         self.top
     }
 
+    /// Get the size the current stack frame was allocated with.
+    #[cfg(debug_assertions)]
+    #[inline]
+    pub(crate) const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Set the size of the current stack frame, used when returning to it.
+    #[cfg(debug_assertions)]
+    #[inline]
+    pub(crate) fn set_size(&mut self, size: usize) {
+        self.size = size;
+    }
+
     /// Get the length of the stack.
-    #[cfg_attr(not(feature = "tracing"), allow(unused))]
     #[inline]
     pub(crate) const fn len(&self) -> usize {
         self.stack.len()
@@ -568,6 +619,11 @@ impl Stack {
         let old_len = self.stack.len();
 
         if len == 0 {
+            #[cfg(debug_assertions)]
+            {
+                self.size = 0;
+            }
+
             return Ok(replace(&mut self.top, old_len));
         }
 
@@ -604,6 +660,11 @@ impl Stack {
             self.stack.set_len(new_len);
         }
 
+        #[cfg(debug_assertions)]
+        {
+            self.size = len;
+        }
+
         Ok(replace(&mut self.top, old_len))
     }
 
@@ -612,10 +673,42 @@ impl Stack {
     #[tracing::instrument(skip_all)]
     pub(crate) fn pop_stack_top(&mut self, top: usize) {
         tracing::trace!(stack = self.stack.len(), self.top);
+
+        #[cfg(debug_assertions)]
+        self.check_balance();
+
         self.stack.truncate(self.top);
         self.top = top;
     }
 
+    /// Verify that the current stack frame holds no values beyond the size it
+    /// was allocated with.
+    ///
+    /// Slots which are left behind empty when values are moved out of them,
+    /// like the arguments of a function called through a protocol, are
+    /// allowed. Any other value indicates a stack imbalance caused by a bug in
+    /// the compiler or the virtual machine.
+    #[cfg(debug_assertions)]
+    fn check_balance(&self) {
+        let end = self.top.wrapping_add(self.size);
+
+        let residual = self
+            .stack
+            .get(end..)
+            .unwrap_or_default()
+            .iter()
+            .filter(|value| !matches!(value.as_inline_unchecked(), Some(Inline::Empty)))
+            .count();
+
+        assert!(
+            self.stack.len() >= end && residual == 0,
+            "stack imbalance on return: the frame starting at {} has {} values but was allocated with {}, of which {residual} are left behind",
+            self.top,
+            self.stack.len().wrapping_sub(self.top),
+            self.size,
+        );
+    }
+
     /// Copy the value at the given address to the output.
     pub(crate) fn copy(&mut self, from: Address, out: Output) -> Result<(), StackError> {
         let Some(to) = out.as_addr() else {
@@ -731,6 +824,8 @@ impl TryClone for Stack {
         Ok(Self {
             stack: self.stack.try_clone()?,
             top: self.top,
+            #[cfg(debug_assertions)]
+            size: self.size,
         })
     }
 }
@@ -744,6 +839,8 @@ impl TryFromIteratorIn<Value, Global> for Stack {
         Ok(Self {
             stack: iter.into_iter().try_collect_in(alloc)?,
             top: 0,
+            #[cfg(debug_assertions)]
+            size: 0,
         })
     }
 }
@@ -751,6 +848,11 @@ impl TryFromIteratorIn<Value, Global> for Stack {
 impl From<Vec<Value>> for Stack {
     #[inline]
     fn from(stack: Vec<Value>) -> Self {
-        Self { stack, top: 0 }
+        Self {
+            stack,
+            top: 0,
+            #[cfg(debug_assertions)]
+            size: 0,
+        }
     }
 }
@@ -40,6 +40,9 @@ pub(crate) use self::args::{DynArgs, DynArgsUsed, DynGuardedArgs};
 mod awaited;
 pub(crate) use self::awaited::Awaited;
 
+mod block_on;
+use self::block_on::block_on;
+
 pub mod budget;
 
 mod bytes;
@@ -112,11 +115,14 @@ pub use self::object::Object;
 mod panic;
 pub(crate) use self::panic::{BoxedPanic, Panic};
 
+mod prepared_call;
+pub use self::prepared_call::PreparedCall;
+
 mod protocol;
 pub use self::protocol::Protocol;
 
 mod protocol_caller;
-pub(crate) use self::protocol_caller::{EnvProtocolCaller, ProtocolCaller};
+pub(crate) use self::protocol_caller::{EnvProtocolCaller, ProtocolCaller, ProtocolFn};
 
 pub(crate) mod range_from;
 pub use self::range_from::RangeFrom;
@@ -140,7 +146,7 @@ mod runtime_context;
 pub use self::runtime_context::RuntimeContext;
 
 mod select;
-pub(crate) use self::select::Select;
+pub use self::select::Select;
 
 mod r#ref;
 use self::r#ref::RefVtable;
@@ -190,22 +196,25 @@ mod vec_tuple;
 pub use self::vec_tuple::VecTuple;
 
 mod vm;
-use self::vm::CallResultOnly;
-pub use self::vm::{CallFrame, Isolated, Vm};
+pub(crate) use self::vm::CallResultOnly;
+pub use self::vm::{CallFrame, DivByZero, Isolated, OverflowMode, Vm};
 
 mod vm_call;
 pub(crate) use self::vm_call::VmCall;
 
 pub(crate) mod vm_diagnostics;
-pub use self::vm_diagnostics::VmDiagnostics;
 pub(crate) use self::vm_diagnostics::VmDiagnosticsObj;
+pub use self::vm_diagnostics::{
+    BudgetAction, BudgetHandler, DropTracer, FunctionProfiler, MatchTracer, ProtocolFallback,
+    ProtocolFallbackCounter, VmDiagnostics,
+};
 
 mod vm_error;
 #[cfg(feature = "emit")]
 pub(crate) use self::vm_error::VmErrorAt;
 #[allow(deprecated)]
 pub use self::vm_error::VmResult;
-pub use self::vm_error::{ExpectedType, RuntimeError, VmError};
+pub use self::vm_error::{BacktraceFrame, ExpectedType, RuntimeError, VmError};
 pub(crate) use self::vm_error::{VmErrorKind, VmIntegerRepr};
 
 mod vm_execution;
@@ -213,8 +222,13 @@ pub(crate) use self::vm_execution::ExecutionState;
 pub use self::vm_execution::{VmExecution, VmOutcome, VmResume, VmSendExecution};
 
 mod vm_halt;
+pub use self::vm_halt::VmBreak;
 pub(crate) use self::vm_halt::{VmHalt, VmHaltInfo};
 
+mod vm_replay;
+pub use self::vm_replay::ReplayLog;
+pub(crate) use self::vm_replay::VmReplay;
+
 mod fmt;
 pub use self::fmt::Formatter;
 
@@ -3,15 +3,19 @@ use core::cmp;
 use core::fmt;
 use core::hash;
 use core::iter;
+use core::ptr::NonNull;
+use core::slice;
 
 use rune_alloc::hashbrown::raw::RawIter;
 
 use crate as rune;
+use crate::alloc::fmt::TryWrite;
 use crate::alloc::hash_map;
 use crate::alloc::prelude::*;
 use crate::alloc::{self, String};
 use crate::runtime::{
-    FieldMap, FromValue, ProtocolCaller, RawAnyGuard, Ref, ToValue, Value, VmError,
+    FieldMap, Formatter, FromValue, Protocol, ProtocolCaller, ProtocolFn, RawAnyGuard, Ref,
+    ToValue, Value, VmError,
 };
 use crate::Any;
 
@@ -60,6 +64,60 @@ pub type Keys<'a> = hash_map::Keys<'a, String, Value>;
 /// [`Object`]: struct.Object.html
 pub type Values<'a> = hash_map::Values<'a, String, Value>;
 
+/// An iterator over the entries of a `Object`, which follows the order in
+/// which they were inserted if the object [keeps track of
+/// it][Object::tracks_insertion_order].
+///
+/// This `struct` is created by the [`iter_ordered`] method on [`Object`]. See
+/// its documentation for more.
+///
+/// [`iter_ordered`]: struct.Object.html#method.iter_ordered
+/// [`Object`]: struct.Object.html
+#[derive(Clone)]
+pub struct IterOrdered<'a> {
+    repr: IterOrderedRepr<'a>,
+}
+
+#[derive(Clone)]
+enum IterOrderedRepr<'a> {
+    Unordered(Iter<'a>),
+    Ordered {
+        keys: slice::Iter<'a, Option<String>>,
+        map: &'a FieldMap<String, Value>,
+        len: usize,
+    },
+}
+
+impl<'a> Iterator for IterOrdered<'a> {
+    type Item = (&'a String, &'a Value);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.repr {
+            IterOrderedRepr::Unordered(iter) => iter.next(),
+            IterOrderedRepr::Ordered { keys, map, len } => {
+                let key = keys.flatten().next()?;
+                let value = map.get(key.as_str())?;
+                *len -= 1;
+                Some((key, value))
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = match &self.repr {
+            IterOrderedRepr::Unordered(iter) => iter.len(),
+            IterOrderedRepr::Ordered { len, .. } => *len,
+        };
+
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for IterOrdered<'_> {}
+impl iter::FusedIterator for IterOrdered<'_> {}
+
 /// Struct representing a dynamic anonymous object.
 ///
 /// # Rust Examples
@@ -80,10 +138,76 @@ pub type Values<'a> = hash_map::Values<'a, String, Value>;
 /// # Ok::<_, rune::support::Error>(())
 /// ```
 #[derive(Any, Default)]
-#[repr(transparent)]
 #[rune(item = ::std::object)]
 pub struct Object {
     inner: FieldMap<String, Value>,
+    /// The order in which keys were inserted, if it's being tracked.
+    order: Option<Box<InsertionOrder>>,
+}
+
+/// The order in which the keys of an object were inserted.
+struct InsertionOrder {
+    /// Keys in insertion order, where removed keys are `None` until the order
+    /// is compacted.
+    keys: alloc::Vec<Option<String>>,
+    /// The index of each key in `keys`.
+    positions: FieldMap<String, usize>,
+}
+
+impl InsertionOrder {
+    fn with_capacity(capacity: usize) -> alloc::Result<Self> {
+        Ok(Self {
+            keys: alloc::Vec::try_with_capacity(capacity)?,
+            positions: crate::runtime::new_field_hash_map_with_capacity(capacity)?,
+        })
+    }
+
+    fn push(&mut self, key: &String) -> alloc::Result<()> {
+        self.keys.try_reserve(1)?;
+        self.positions.try_insert(key.try_clone()?, self.keys.len())?;
+        self.keys.try_push(Some(key.try_clone()?))?;
+        Ok(())
+    }
+
+    fn remove<Q>(&mut self, key: &Q)
+    where
+        String: borrow::Borrow<Q>,
+        Q: ?Sized + hash::Hash + cmp::Eq + cmp::Ord,
+    {
+        let Some(index) = self.positions.remove(key) else {
+            return;
+        };
+
+        if let Some(key) = self.keys.get_mut(index) {
+            *key = None;
+        }
+
+        // NB: Compacting once at least half of the keys have been removed
+        // keeps removal amortized constant time.
+        if self.keys.len() > self.positions.len().saturating_mul(2) {
+            self.keys.retain(Option::is_some);
+
+            for (index, key) in self.keys.iter().flatten().enumerate() {
+                if let Some(position) = self.positions.get_mut(key.as_str()) {
+                    *position = index;
+                }
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.keys.clear();
+        self.positions.clear();
+    }
+}
+
+impl TryClone for InsertionOrder {
+    fn try_clone(&self) -> alloc::Result<Self> {
+        Ok(Self {
+            keys: self.keys.try_clone()?,
+            positions: self.positions.try_clone()?,
+        })
+    }
 }
 
 impl Object {
@@ -100,6 +224,7 @@ impl Object {
     pub fn new() -> Self {
         Self {
             inner: crate::runtime::new_field_map(),
+            order: None,
         }
     }
 
@@ -116,9 +241,36 @@ impl Object {
     pub fn with_capacity(capacity: usize) -> alloc::Result<Self> {
         Ok(Self {
             inner: crate::runtime::new_field_hash_map_with_capacity(capacity)?,
+            order: None,
         })
     }
 
+    /// Construct a new object with the given capacity which keeps track of
+    /// the order in which its keys are inserted.
+    ///
+    /// See [`Object::tracks_insertion_order`].
+    pub(crate) fn with_insertion_order(capacity: usize) -> alloc::Result<Self> {
+        Ok(Self {
+            inner: crate::runtime::new_field_hash_map_with_capacity(capacity)?,
+            order: Some(Box::try_new(InsertionOrder::with_capacity(capacity)?)?),
+        })
+    }
+
+    /// Returns `true` if the object keeps track of the order in which its keys
+    /// were inserted.
+    ///
+    /// Only objects constructed by a virtual machine with
+    /// [`Vm::set_object_preserve_insertion_order`] enabled keep track of
+    /// their insertion order. Formatting, serializing or iterating over such
+    /// objects in a script visits their entries in insertion order, and so
+    /// does [`Object::iter_ordered`].
+    ///
+    /// [`Vm::set_object_preserve_insertion_order`]: crate::Vm::set_object_preserve_insertion_order
+    #[inline]
+    pub fn tracks_insertion_order(&self) -> bool {
+        self.order.is_some()
+    }
+
     /// Returns the number of elements in the object.
     ///
     /// # Examples
@@ -203,7 +355,13 @@ impl Object {
         String: borrow::Borrow<Q>,
         Q: ?Sized + hash::Hash + cmp::Eq + cmp::Ord,
     {
-        self.inner.remove(k)
+        let value = self.inner.remove(k)?;
+
+        if let Some(order) = &mut self.order {
+            order.remove(k);
+        }
+
+        Some(value)
     }
 
     /// Inserts a key-value pair into the dynamic object, converting it as
@@ -213,7 +371,7 @@ impl Object {
     where
         T: ToValue,
     {
-        self.inner.try_insert(k, v.to_value()?)?;
+        self.insert(k, v.to_value()?)?;
         Ok(())
     }
 
@@ -235,6 +393,15 @@ impl Object {
     #[inline]
     #[rune::function(keep, path = Self::insert)]
     pub fn insert(&mut self, k: String, v: Value) -> alloc::Result<Option<Value>> {
+        if let Some(order) = &mut self.order {
+            if !self.inner.contains_key(k.as_str()) {
+                // NB: Reserve first so that inserting can't fail once the key
+                // has been added to the order.
+                self.inner.try_reserve(1)?;
+                order.push(&k)?;
+            }
+        }
+
         self.inner.try_insert(k, v)
     }
 
@@ -244,6 +411,10 @@ impl Object {
     #[rune::function(keep)]
     pub fn clear(&mut self) {
         self.inner.clear();
+
+        if let Some(order) = &mut self.order {
+            order.clear();
+        }
     }
 
     /// An iterator visiting all key-value pairs in arbitrary order.
@@ -272,7 +443,27 @@ impl Object {
         self.inner.iter_mut()
     }
 
-    /// An iterator visiting all keys and values in arbitrary order.
+    /// An iterator visiting all key-value pairs in insertion order if the
+    /// object [keeps track of it][Object::tracks_insertion_order], or in
+    /// arbitrary order otherwise.
+    ///
+    /// The iterator element type is `(&'a String, &'a Value)`.
+    pub fn iter_ordered(&self) -> IterOrdered<'_> {
+        let repr = match &self.order {
+            Some(order) => IterOrderedRepr::Ordered {
+                keys: order.keys.iter(),
+                map: &self.inner,
+                len: self.inner.len(),
+            },
+            None => IterOrderedRepr::Unordered(self.inner.iter()),
+        };
+
+        IterOrdered { repr }
+    }
+
+    /// An iterator visiting all keys and values in insertion order if the object
+    /// [keeps track of it][Object::tracks_insertion_order], or in arbitrary
+    /// order otherwise.
     ///
     /// # Examples
     ///
@@ -291,12 +482,14 @@ impl Object {
     pub fn rune_iter(this: Ref<Self>) -> RuneIter {
         // SAFETY: we're holding onto the related reference guard, and making
         // sure that it's dropped after the iterator.
-        let iter = unsafe { this.inner.raw_table().iter() };
+        let iter = unsafe { RawEntries::new(&this) };
         let (_, guard) = Ref::into_raw(this);
         RuneIter { iter, guard }
     }
 
-    /// An iterator visiting all keys in arbitrary order.
+    /// An iterator visiting all keys in insertion order if the object
+    /// [keeps track of it][Object::tracks_insertion_order], or in arbitrary
+    /// order otherwise.
     ///
     /// # Examples
     ///
@@ -315,12 +508,14 @@ impl Object {
     pub fn rune_keys(this: Ref<Self>) -> RuneIterKeys {
         // SAFETY: we're holding onto the related reference guard, and making
         // sure that it's dropped after the iterator.
-        let iter = unsafe { this.inner.raw_table().iter() };
+        let iter = unsafe { RawEntries::new(&this) };
         let (_, guard) = Ref::into_raw(this);
         RuneIterKeys { iter, guard }
     }
 
-    /// An iterator visiting all values in arbitrary order.
+    /// An iterator visiting all values in insertion order if the object
+    /// [keeps track of it][Object::tracks_insertion_order], or in arbitrary
+    /// order otherwise.
     ///
     /// # Examples
     ///
@@ -339,11 +534,39 @@ impl Object {
     pub fn rune_values(this: Ref<Self>) -> RuneValues {
         // SAFETY: we're holding onto the related reference guard, and making
         // sure that it's dropped after the iterator.
-        let iter = unsafe { this.inner.raw_table().iter() };
+        let iter = unsafe { RawEntries::new(&this) };
         let (_, guard) = Ref::into_raw(this);
         RuneValues { iter, guard }
     }
 
+    pub(crate) fn debug_fmt_with(
+        &self,
+        f: &mut Formatter,
+        caller: &mut dyn ProtocolCaller,
+    ) -> Result<(), VmError> {
+        let protocol_fn =
+            ProtocolFn::resolve_homogeneous(self.values(), &Protocol::DEBUG_FMT, caller)?;
+
+        let mut it = self.iter_ordered().peekable();
+        write!(f, "{{")?;
+
+        while let Some((key, value)) = it.next() {
+            write!(f, "{key:?}: ")?;
+
+            match &protocol_fn {
+                Some(protocol_fn) => protocol_fn.fmt(value, f, caller)?,
+                None => value.debug_fmt_with(f, caller)?,
+            }
+
+            if it.peek().is_some() {
+                write!(f, ", ")?;
+            }
+        }
+
+        write!(f, "}}")?;
+        Ok(())
+    }
+
     pub(crate) fn partial_eq_with(
         a: &Self,
         b: &Self,
@@ -372,12 +595,12 @@ impl Object {
         eq: fn(&Value, &Value, &mut dyn ProtocolCaller) -> Result<bool, VmError>,
         caller: &mut dyn ProtocolCaller,
     ) -> Result<bool, VmError> {
-        if a.inner.len() != b.inner.len() {
+        if a.len() != b.len() {
             return Ok(false);
         }
 
-        for (key, a) in a.inner.iter() {
-            let Some(b) = b.inner.get(key) else {
+        for (key, a) in a.iter() {
+            let Some(b) = b.get(key) else {
                 return Ok(false);
             };
 
@@ -390,10 +613,12 @@ impl Object {
     }
 }
 
+
 impl TryClone for Object {
     fn try_clone(&self) -> alloc::Result<Self> {
         Ok(Self {
             inner: self.inner.try_clone()?,
+            order: self.order.try_clone()?,
         })
     }
 }
@@ -431,14 +656,85 @@ impl IntoIterator for Object {
 impl fmt::Debug for Object {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_map().entries(self.inner.iter()).finish()
+        f.debug_map().entries(self.iter_ordered()).finish()
+    }
+}
+
+/// Entries of an object which is kept alive by a reference guard, visited in
+/// the same order as [`Object::iter_ordered`].
+enum RawEntries {
+    Unordered(RawIter<(String, Value)>),
+    Ordered {
+        object: NonNull<Object>,
+        index: usize,
+        len: usize,
+    },
+}
+
+impl RawEntries {
+    /// # Safety
+    ///
+    /// The caller must ensure that the object outlives the returned entries
+    /// and that it is not modified while they are in use.
+    unsafe fn new(object: &Object) -> Self {
+        match &object.order {
+            Some(..) => RawEntries::Ordered {
+                object: NonNull::from(object),
+                index: 0,
+                len: object.inner.len(),
+            },
+            None => RawEntries::Unordered(object.inner.raw_table().iter()),
+        }
+    }
+
+    /// # Safety
+    ///
+    /// The object the entries were constructed from must still be alive and
+    /// unmodified.
+    unsafe fn next<'a>(&mut self) -> Option<(&'a String, &'a Value)> {
+        match self {
+            RawEntries::Unordered(iter) => {
+                let (key, value) = iter.next()?.as_ref();
+                Some((key, value))
+            }
+            RawEntries::Ordered { object, index, len } => {
+                // SAFETY: The caller ensures that the object is still alive
+                // and unmodified.
+                let object = unsafe { object.as_ref() };
+                let order = object.order.as_ref()?;
+
+                while let Some(key) = order.keys.get(*index) {
+                    *index += 1;
+
+                    let Some(key) = key else {
+                        continue;
+                    };
+
+                    let Some(value) = object.inner.get(key.as_str()) else {
+                        continue;
+                    };
+
+                    *len -= 1;
+                    return Some((key, value));
+                }
+
+                None
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            RawEntries::Unordered(iter) => iter.len(),
+            RawEntries::Ordered { len, .. } => *len,
+        }
     }
 }
 
 #[derive(Any)]
 #[rune(item = ::std::object, name = Iter)]
 pub struct RuneIter {
-    iter: RawIter<(String, Value)>,
+    iter: RawEntries,
     #[allow(unused)]
     guard: RawAnyGuard,
 }
@@ -447,11 +743,10 @@ impl RuneIter {
     #[rune::function(instance, keep, protocol = NEXT)]
     pub fn next(&mut self) -> Result<Option<(String, Value)>, VmError> {
         unsafe {
-            let Some(bucket) = self.iter.next() else {
+            let Some((key, value)) = self.iter.next() else {
                 return Ok(None);
             };
 
-            let (key, value) = bucket.as_ref();
             let key = key.try_clone()?;
             Ok(Some((key, value.clone())))
         }
@@ -459,7 +754,8 @@ impl RuneIter {
 
     #[rune::function(instance, keep, protocol = SIZE_HINT)]
     pub fn size_hint(&self) -> (usize, Option<usize>) {
-        self.iter.size_hint()
+        let len = self.iter.len();
+        (len, Some(len))
     }
 
     #[rune::function(instance, keep, protocol = LEN)]
@@ -484,7 +780,7 @@ impl iter::Iterator for RuneIter {
 #[derive(Any)]
 #[rune(item = ::std::object, name = Keys)]
 pub struct RuneIterKeys {
-    iter: RawIter<(String, Value)>,
+    iter: RawEntries,
     #[allow(unused)]
     guard: RawAnyGuard,
 }
@@ -493,11 +789,10 @@ impl RuneIterKeys {
     #[rune::function(instance, keep, protocol = NEXT)]
     pub fn next(&mut self) -> Result<Option<String>, VmError> {
         unsafe {
-            let Some(bucket) = self.iter.next() else {
+            let Some((key, _)) = self.iter.next() else {
                 return Ok(None);
             };
 
-            let (key, _) = bucket.as_ref();
             let key = key.try_clone()?;
             Ok(Some(key))
         }
@@ -505,7 +800,8 @@ impl RuneIterKeys {
 
     #[rune::function(instance, keep, protocol = SIZE_HINT)]
     pub fn size_hint(&self) -> (usize, Option<usize>) {
-        self.iter.size_hint()
+        let len = self.iter.len();
+        (len, Some(len))
     }
 
     #[rune::function(instance, keep, protocol = LEN)]
@@ -530,7 +826,7 @@ impl iter::Iterator for RuneIterKeys {
 #[derive(Any)]
 #[rune(item = ::std::object, name = Values)]
 pub struct RuneValues {
-    iter: RawIter<(String, Value)>,
+    iter: RawEntries,
     #[allow(unused)]
     guard: RawAnyGuard,
 }
@@ -539,18 +835,18 @@ impl RuneValues {
     #[rune::function(instance, keep, protocol = NEXT)]
     pub fn next(&mut self) -> Result<Option<Value>, VmError> {
         unsafe {
-            let Some(bucket) = self.iter.next() else {
+            let Some((_, value)) = self.iter.next() else {
                 return Ok(None);
             };
 
-            let (_, value) = bucket.as_ref();
             Ok(Some(value.clone()))
         }
     }
 
     #[rune::function(instance, keep, protocol = SIZE_HINT)]
     pub fn size_hint(&self) -> (usize, Option<usize>) {
-        self.iter.size_hint()
+        let len = self.iter.len();
+        (len, Some(len))
     }
 
     #[rune::function(instance, keep, protocol = LEN)]
@@ -0,0 +1,61 @@
+use crate::hash::Hash;
+use crate::sync::Arc;
+
+use super::{Args, Unit, Value, Vm, VmError};
+
+/// A function call which has been resolved and validated ahead of time.
+///
+/// This is constructed through [`Vm::prepare_call`], and allows for calling
+/// the same function repeatedly without having to look it up for every call.
+///
+/// If the prepared call is used with a virtual machine which has a different
+/// [`Unit`] than the one it was prepared for, the function is looked up by its
+/// hash as if it was called through [`Vm::call`].
+#[derive(Debug, Clone)]
+pub struct PreparedCall {
+    /// The unit the call was prepared for.
+    unit: Arc<Unit>,
+    /// The hash of the function being called.
+    hash: Hash,
+    /// The offset of the function in the unit.
+    offset: usize,
+    /// The number of arguments the function expects.
+    args: usize,
+}
+
+impl PreparedCall {
+    #[inline]
+    pub(crate) fn new(unit: Arc<Unit>, hash: Hash, offset: usize, args: usize) -> Self {
+        Self {
+            unit,
+            hash,
+            offset,
+            args,
+        }
+    }
+
+    /// The hash of the prepared function.
+    #[inline]
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    /// The number of arguments the prepared function expects.
+    #[inline]
+    pub fn args(&self) -> usize {
+        self.args
+    }
+
+    /// Call the prepared function immediately, returning the produced value.
+    ///
+    /// Unlike [`Vm::call`] this only accepts owned arguments, which can be
+    /// pushed onto the stack without guarding any references.
+    pub fn call(&self, vm: &mut Vm, args: impl Args) -> Result<Value, VmError> {
+        if !vm.is_same_unit(&self.unit) {
+            let prepared = vm.prepare_call(self.hash)?;
+            return vm.call_prepared(prepared.offset, prepared.args, args);
+        }
+
+        vm.call_prepared(self.offset, self.args, args)
+    }
+}
@@ -1,6 +1,7 @@
 use crate::runtime::vm::{CallResult, CallResultOnly, Isolated};
 use crate::runtime::{
-    DynArgs, Protocol, Stack, UnitFn, Value, Vm, VmError, VmErrorKind, VmExecution,
+    DynArgs, DynGuardedArgs, Formatter, FromValue, FunctionHandler, Protocol, Repr, RuntimeContext,
+    Stack, Unit, UnitFn, Value, Vm, VmError, VmErrorKind, VmExecution,
 };
 use crate::Hash;
 
@@ -31,6 +32,129 @@ pub(crate) trait ProtocolCaller: 'static {
         target: Value,
         args: &mut dyn DynArgs,
     ) -> Result<CallResultOnly<Value>, VmError>;
+
+    /// Resolve the native function implementing the given protocol for
+    /// values of the given type, so that it can be called repeatedly without
+    /// being looked up again.
+    ///
+    /// Returns `None` if the protocol isn't implemented natively for the
+    /// type, or if the caller doesn't support resolving functions ahead of
+    /// time. In that case [`ProtocolCaller::call_protocol_fn`] should be used
+    /// instead.
+    fn resolve_protocol_fn(
+        &mut self,
+        _protocol: &'static Protocol,
+        _type_hash: Hash,
+    ) -> Result<Option<ProtocolFn>, VmError> {
+        Ok(None)
+    }
+
+    /// Call a protocol function which has been resolved through
+    /// [`ProtocolCaller::resolve_protocol_fn`] in the same way as
+    /// [`ProtocolCaller::call_protocol_fn`] would, except without looking it
+    /// up.
+    fn call_resolved_protocol_fn(
+        &mut self,
+        protocol_fn: &ProtocolFn,
+        target: Value,
+        args: &mut dyn DynArgs,
+    ) -> Result<Value, VmError>;
+}
+
+/// A native protocol function which has been resolved ahead of time through
+/// [`ProtocolCaller::resolve_protocol_fn`].
+pub(crate) struct ProtocolFn {
+    hash: Hash,
+    handler: FunctionHandler,
+}
+
+impl ProtocolFn {
+    /// Resolve the native function with the given hash, unless it's shadowed
+    /// by a function in the unit.
+    fn resolve(context: &RuntimeContext, unit: &Unit, hash: Hash) -> Option<Self> {
+        // NB: Functions in the unit take precedence, and are not resolved
+        // ahead of time.
+        if unit.function(&hash).is_some() {
+            return None;
+        }
+
+        let handler = context.function(&hash)?;
+
+        Some(Self {
+            hash,
+            handler: handler.clone(),
+        })
+    }
+
+    /// Resolve the native function implementing the given protocol once for
+    /// all values, if they are values of the same type which would otherwise
+    /// have the protocol function looked up for each of them.
+    ///
+    /// Only values which are stored as [`Repr::Any`] are resolved. Inline
+    /// values like integers, floats and booleans are formatted directly
+    /// without looking up a protocol function, so there is nothing to resolve
+    /// for them, and dynamic values are defined in the unit which is never
+    /// resolved ahead of time.
+    pub(crate) fn resolve_homogeneous<'a>(
+        values: impl IntoIterator<Item = &'a Value>,
+        protocol: &'static Protocol,
+        caller: &mut dyn ProtocolCaller,
+    ) -> Result<Option<Self>, VmError> {
+        let mut values = values.into_iter();
+
+        let Some(first) = values.next() else {
+            return Ok(None);
+        };
+
+        if !matches!(first.as_ref(), Repr::Any(..)) {
+            return Ok(None);
+        }
+
+        let type_hash = first.type_hash();
+        let mut count = 1usize;
+
+        for value in values {
+            if !matches!(value.as_ref(), Repr::Any(..)) || value.type_hash() != type_hash {
+                return Ok(None);
+            }
+
+            count = count.wrapping_add(1);
+        }
+
+        if count < 2 {
+            return Ok(None);
+        }
+
+        caller.resolve_protocol_fn(protocol, type_hash)
+    }
+
+    /// The hash of the resolved function.
+    #[inline]
+    pub(crate) fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    /// The handler of the resolved function.
+    #[inline]
+    pub(crate) fn handler(&self) -> &FunctionHandler {
+        &self.handler
+    }
+
+    /// Format the given value through the protocol function, which must be a
+    /// formatting protocol like [`Protocol::DEBUG_FMT`].
+    ///
+    /// The value must be of the type the function was resolved for.
+    pub(crate) fn fmt(
+        &self,
+        value: &Value,
+        f: &mut Formatter,
+        caller: &mut dyn ProtocolCaller,
+    ) -> Result<(), VmError> {
+        let mut args = DynGuardedArgs::new((f,));
+        let result = caller.call_resolved_protocol_fn(self, value.clone(), &mut args)?;
+        <()>::from_value(result)?;
+        Ok(())
+    }
 }
 
 /// Use the global environment caller.
@@ -91,6 +215,35 @@ impl ProtocolCaller for EnvProtocolCaller {
             Ok(CallResultOnly::Unsupported(target))
         })
     }
+
+    fn resolve_protocol_fn(
+        &mut self,
+        protocol: &'static Protocol,
+        type_hash: Hash,
+    ) -> Result<Option<ProtocolFn>, VmError> {
+        crate::runtime::env::shared(|context, unit| {
+            let hash = Hash::associated_function(type_hash, protocol.hash);
+            Ok(ProtocolFn::resolve(context, unit, hash))
+        })
+    }
+
+    fn call_resolved_protocol_fn(
+        &mut self,
+        protocol_fn: &ProtocolFn,
+        target: Value,
+        args: &mut dyn DynArgs,
+    ) -> Result<Value, VmError> {
+        let count = args.count() + 1;
+        let mut stack = Stack::with_capacity(count)?;
+        let addr = stack.addr();
+        stack.push(target)?;
+        args.push_to_stack(&mut stack)?;
+        protocol_fn
+            .handler
+            .call(&mut stack, addr, count, addr.output())?;
+        let value = stack.at(addr).clone();
+        Ok(value)
+    }
 }
 
 impl ProtocolCaller for Vm {
@@ -122,4 +275,27 @@ impl ProtocolCaller for Vm {
         self.stack_mut().truncate(addr);
         Ok(result)
     }
+
+    fn resolve_protocol_fn(
+        &mut self,
+        protocol: &'static Protocol,
+        type_hash: Hash,
+    ) -> Result<Option<ProtocolFn>, VmError> {
+        let hash = Hash::associated_function(type_hash, protocol.hash);
+        Ok(ProtocolFn::resolve(self.context(), self.unit(), hash))
+    }
+
+    fn call_resolved_protocol_fn(
+        &mut self,
+        protocol_fn: &ProtocolFn,
+        target: Value,
+        args: &mut dyn DynArgs,
+    ) -> Result<Value, VmError> {
+        let addr = self.stack().addr();
+        self.stack_mut().push(())?;
+        self.call_resolved_fn(protocol_fn, target, args, addr.output())?;
+        let value = self.stack().at(addr).clone();
+        self.stack_mut().truncate(addr);
+        Ok(value)
+    }
 }
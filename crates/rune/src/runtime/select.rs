@@ -9,15 +9,43 @@ use crate::runtime::future::SelectFuture;
 use crate::runtime::{Future, Mut, Value, VmError};
 
 /// A stored select.
+///
+/// This is what the virtual machine waits on while it's parked on a
+/// `select` expression, see [`VmResume::awaited_select`].
+///
+/// [`VmResume::awaited_select`]: crate::runtime::VmResume::awaited_select
 #[derive(Debug)]
 pub struct Select {
+    /// The instruction pointer of the first branch.
+    ip: usize,
     futures: FuturesUnordered<SelectFuture<usize, Mut<Future>>>,
 }
 
 impl Select {
-    /// Construct a new stored select.
-    pub(crate) fn new(futures: FuturesUnordered<SelectFuture<usize, Mut<Future>>>) -> Self {
-        Self { futures }
+    /// Construct a new stored select, where each future is associated with
+    /// the instruction pointer of its branch starting at `ip`.
+    pub(crate) fn new(
+        ip: usize,
+        futures: FuturesUnordered<SelectFuture<usize, Mut<Future>>>,
+    ) -> Self {
+        Self { ip, futures }
+    }
+
+    /// Get the number of branches whose futures are still pending.
+    #[inline]
+    pub fn pending_len(&self) -> usize {
+        self.futures.len()
+    }
+
+    /// Iterate over the indexes of the branches whose futures are still
+    /// pending, in no particular order.
+    ///
+    /// Branches are indexed in the order they appear in the `select`
+    /// expression, not counting the default branch.
+    pub fn pending_branches(&self) -> impl Iterator<Item = usize> + '_ {
+        Pin::new(&self.futures)
+            .iter_pin_ref()
+            .map(|future| future.data() - self.ip)
     }
 }
 
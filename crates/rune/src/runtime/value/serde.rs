@@ -92,7 +92,7 @@ impl ser::Serialize for Value {
                     let object = value.borrow_ref::<Object>().map_err(S::Error::custom)?;
                     let mut serializer = serializer.serialize_map(Some(object.len()))?;
 
-                    for (key, value) in object.iter() {
+                    for (key, value) in object.iter_ordered() {
                         serializer.serialize_entry(key, value)?;
                     }
 
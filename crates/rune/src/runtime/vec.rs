@@ -14,9 +14,9 @@ use crate::shared::FixedVec;
 use crate::{Any, TypeHash};
 
 use super::{
-    EnvProtocolCaller, Formatter, FromValue, Hasher, ProtocolCaller, Range, RangeFrom, RangeFull,
-    RangeInclusive, RangeTo, RangeToInclusive, RawAnyGuard, Ref, RuntimeError, ToValue,
-    UnsafeToRef, Value, VmError, VmErrorKind,
+    EnvProtocolCaller, Formatter, FromValue, Hasher, Protocol, ProtocolCaller, ProtocolFn, Range,
+    RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive, RawAnyGuard, Ref,
+    RuntimeError, ToValue, UnsafeToRef, Value, VmError, VmErrorKind,
 };
 
 /// Struct representing a dynamic vector.
@@ -252,11 +252,44 @@ impl Vec {
         f: &mut Formatter,
         caller: &mut dyn ProtocolCaller,
     ) -> Result<(), VmError> {
+        Self::fmt_with(this, f, &Protocol::DEBUG_FMT, Value::debug_fmt_with, caller)
+    }
+
+    #[cfg_attr(not(test), allow(unused))]
+    pub(crate) fn display_fmt_with(
+        this: &[Value],
+        f: &mut Formatter,
+        caller: &mut dyn ProtocolCaller,
+    ) -> Result<(), VmError> {
+        Self::fmt_with(
+            this,
+            f,
+            &Protocol::DISPLAY_FMT,
+            Value::display_fmt_with,
+            caller,
+        )
+    }
+
+    /// Format every element with the given formatting protocol, which is
+    /// resolved once if all elements are of the same type. Otherwise each
+    /// element is formatted through `fmt`.
+    fn fmt_with(
+        this: &[Value],
+        f: &mut Formatter,
+        protocol: &'static Protocol,
+        fmt: fn(&Value, &mut Formatter, &mut dyn ProtocolCaller) -> Result<(), VmError>,
+        caller: &mut dyn ProtocolCaller,
+    ) -> Result<(), VmError> {
+        let protocol_fn = ProtocolFn::resolve_homogeneous(this, protocol, caller)?;
+
         let mut it = this.iter().peekable();
         write!(f, "[")?;
 
         while let Some(value) = it.next() {
-            value.debug_fmt_with(f, caller)?;
+            match &protocol_fn {
+                Some(protocol_fn) => protocol_fn.fmt(value, f, caller)?,
+                None => fmt(value, f, caller)?,
+            }
 
             if it.peek().is_some() {
                 write!(f, ", ")?;
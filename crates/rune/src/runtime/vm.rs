@@ -1,8 +1,10 @@
 use core::cmp::Ordering;
 use core::fmt;
-use core::mem::replace;
+use core::mem::{replace, size_of};
 use core::ptr::NonNull;
 
+use rust_alloc::boxed::Box;
+
 use crate::alloc::prelude::*;
 use crate::alloc::{self, String};
 use crate::hash::{Hash, IntoHash, ToTypeHash};
@@ -14,14 +16,16 @@ mod ops;
 use self::ops::*;
 
 use super::{
-    budget, inst, Address, AnySequence, Args, Awaited, BorrowMut, Bytes, Call, ControlFlow,
-    DynArgs, DynGuardedArgs, Format, FormatSpec, Formatter, FromValue, Function, Future, Generator,
-    GeneratorState, GuardedArgs, Inline, InstArithmeticOp, InstBitwiseOp, InstOp, InstRange,
-    InstShiftOp, InstTarget, InstValue, Object, Output, OwnedTuple, Pair, Panic, Protocol,
-    ProtocolCaller, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive, Repr,
-    RttiKind, RuntimeContext, Select, SelectFuture, Stack, Stream, Type, TypeHash, TypeInfo,
-    TypeOf, Unit, UnitFn, UnitStorage, Value, Vec, VmDiagnostics, VmDiagnosticsObj, VmError,
-    VmErrorKind, VmExecution, VmHalt, VmIntegerRepr, VmOutcome, VmSendExecution,
+    block_on, budget, inst, Address, AnySequence, Args, Awaited, BorrowMut, BudgetAction, Bytes,
+    Call, ControlFlow, DynArgs, DynGuardedArgs, EmptyConstContext, Format, FormatSpec, Formatter,
+    FromValue, Function, Future, Generator, GeneratorState, GuardedArgs, Inline, Inst,
+    InstArithmeticOp, InstBitwiseOp, InstOp, InstRange, InstShiftOp, InstTarget, InstValue, Object,
+    Output, OwnedTuple, Pair, Panic, PreparedCall, Protocol, ProtocolCaller, ProtocolFallback,
+    ProtocolFn, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive, ReplayLog,
+    Repr, RttiKind, RuntimeContext, Select, SelectFuture, Stack, Stream, Type, TypeHash, TypeInfo,
+    TypeOf, Unit, UnitFn, UnitStorage, Value, Vec, VmBreak, VmDiagnostics, VmDiagnosticsObj,
+    VmError, VmErrorKind, VmExecution, VmHalt, VmHaltInfo, VmIntegerRepr, VmOutcome, VmReplay,
+    VmSendExecution,
 };
 
 /// Helper to take a value, replacing the old one with empty.
@@ -73,6 +77,40 @@ impl fmt::Display for Isolated {
     }
 }
 
+/// How integer arithmetic performed by the virtual machine behaves on
+/// overflow.
+///
+/// See [`Vm::set_integer_overflow_mode`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OverflowMode {
+    /// Overflow raises an error. This is the default.
+    #[default]
+    Checked,
+    /// Overflow wraps around at the boundary of the integer type.
+    Wrapping,
+    /// Overflow saturates at the numeric bounds of the integer type.
+    Saturating,
+}
+
+/// How integer division and remainder by zero performed by the virtual
+/// machine behaves.
+///
+/// See [`Vm::set_div_by_zero`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DivByZero {
+    /// Division by zero raises an error. This is the default.
+    #[default]
+    Error,
+    /// Division and remainder by zero produce zero.
+    Zero,
+    /// Division by zero saturates at the numeric bound of the integer type in
+    /// the direction of the dividend, or produces zero if the dividend is
+    /// zero. Remainder by zero produces zero.
+    Saturate,
+}
+
 /// The result from a dynamic call. Indicates if the attempted operation is
 /// supported.
 #[derive(Debug)]
@@ -109,10 +147,61 @@ pub struct Vm {
     ip: usize,
     /// The length of the last instruction pointer.
     last_ip_len: u8,
+    /// The instruction pointer of the instruction which raised the last
+    /// error.
+    error_ip: Option<usize>,
     /// The current stack.
     stack: Stack,
     /// Frames relative to the stack.
     call_frames: alloc::Vec<CallFrame>,
+    /// The minimum number of bytes to reserve for each operand of a string
+    /// concatenation.
+    string_concat_reserve: usize,
+    /// Whether to capture a backtrace into errors raised by the virtual
+    /// machine.
+    capture_backtrace: bool,
+    /// The maximum number of bytes a single runtime allocation is permitted
+    /// to request.
+    max_allocation: Option<usize>,
+    /// The maximum length in bytes of strings built by the virtual machine.
+    max_string_len: Option<usize>,
+    /// The maximum cumulative number of elements in collections constructed
+    /// by the virtual machine during a call.
+    max_live_elements: Option<usize>,
+    /// The number of elements in collections constructed since execution
+    /// started.
+    live_elements: usize,
+    /// How integer arithmetic behaves on overflow.
+    overflow_mode: OverflowMode,
+    /// How integer division and remainder by zero behaves.
+    div_by_zero: DivByZero,
+    /// Whether mutating externally owned values is forbidden.
+    read_only: bool,
+    /// Whether conditional jumps require their condition to be a boolean.
+    strict_conditions: bool,
+    /// Whether constructed objects keep track of their insertion order.
+    object_insertion_order: bool,
+    /// Handler invoked inline when the virtual machine yields.
+    yield_handler: Option<YieldHandler>,
+    /// Recording or replay of nondeterministic decisions.
+    replay: VmReplay,
+    /// Handler consulted when a called function is missing.
+    missing_function_handler: Option<MissingFunctionHandler>,
+    /// Handlers consulted when a field is missing, sorted by type hash.
+    dynamic_field_handlers: alloc::Vec<(Hash, DynamicFieldHandler)>,
+    /// Mocks replacing called functions, sorted by function hash.
+    call_mocks: alloc::Vec<(Hash, CallMock)>,
+    /// Writer receiving a line for each executed instruction.
+    trace_writer: Option<TraceWriter>,
+    /// Whether diagnostics are installed for the current run, which lets
+    /// hooks skip looking them up in the environment.
+    has_diagnostics: bool,
+    /// Values left behind by returning call frames, captured during
+    /// [`Vm::call_and_capture`].
+    residual: Option<alloc::Vec<Value>>,
+    /// The number of instructions executed, counted during
+    /// [`Vm::execute_counting`].
+    instruction_count: Option<usize>,
 }
 
 impl Vm {
@@ -136,8 +225,29 @@ impl Vm {
             unit,
             ip: 0,
             last_ip_len: 0,
+            error_ip: None,
             stack,
             call_frames: alloc::Vec::new(),
+            string_concat_reserve: 0,
+            capture_backtrace: false,
+            max_allocation: None,
+            max_string_len: None,
+            max_live_elements: None,
+            live_elements: 0,
+            overflow_mode: OverflowMode::Checked,
+            div_by_zero: DivByZero::Error,
+            read_only: false,
+            strict_conditions: false,
+            object_insertion_order: false,
+            yield_handler: None,
+            replay: VmReplay::None,
+            missing_function_handler: None,
+            dynamic_field_handlers: alloc::Vec::new(),
+            call_mocks: alloc::Vec::new(),
+            trace_writer: None,
+            has_diagnostics: false,
+            residual: None,
+            instruction_count: None,
         }
     }
 
@@ -163,112 +273,164 @@ impl Vm {
         Arc::ptr_eq(&self.unit, unit)
     }
 
-    /// Set  the current instruction pointer.
+    /// Set the minimum number of bytes to reserve for each operand when
+    /// concatenating strings, such as in template strings.
+    ///
+    /// The compiler only knows the size of the literal fragments of a
+    /// template, so when large values are formatted into it the buffer might
+    /// have to grow several times. Setting this reserves at least `min` bytes
+    /// per operand up front, in addition to the static size hint.
+    ///
+    /// Defaults to `0`, which only reserves the static size hint.
     #[inline]
-    pub fn set_ip(&mut self, ip: usize) {
-        self.ip = ip;
+    pub fn set_string_concat_reserve(&mut self, min: usize) {
+        self.string_concat_reserve = min;
     }
 
-    /// Get the stack.
+    /// Get the minimum number of bytes reserved for each operand when
+    /// concatenating strings.
+    ///
+    /// See [`Vm::set_string_concat_reserve`].
     #[inline]
-    pub fn call_frames(&self) -> &[CallFrame] {
-        &self.call_frames
+    pub fn string_concat_reserve(&self) -> usize {
+        self.string_concat_reserve
     }
 
-    /// Get the stack.
+    /// Set whether errors raised while running the virtual machine should
+    /// capture the call-frame backtrace at the point of the error.
+    ///
+    /// The captured backtrace is available through [`VmError::backtrace`].
+    /// Defaults to `false`.
     #[inline]
-    pub fn stack(&self) -> &Stack {
-        &self.stack
+    pub fn set_capture_backtrace(&mut self, capture_backtrace: bool) {
+        self.capture_backtrace = capture_backtrace;
     }
 
-    /// Get the stack mutably.
+    /// Set the maximum number of bytes a single allocation performed by the
+    /// virtual machine is permitted to request, or `None` for no limit.
+    ///
+    /// This is consulted when allocating stack space for a function and when
+    /// constructing vectors, tuples and strings in instructions, and causes
+    /// the virtual machine to error instead of performing oversized
+    /// allocations. Note that it doesn't limit allocations performed by native
+    /// functions. Defaults to `None`.
     #[inline]
-    pub fn stack_mut(&mut self) -> &mut Stack {
-        &mut self.stack
+    pub fn set_max_allocation(&mut self, bytes: Option<usize>) {
+        self.max_allocation = bytes;
     }
 
-    /// Access the context related to the virtual machine mutably.
-    ///
-    /// Note that this can be used to swap out the [`RuntimeContext`] associated
-    /// with the running vm. Note that this is only necessary if the underlying
-    /// [`Context`] is different or has been modified. In contrast to
-    /// constructing a [`new`] vm, this allows for amortised re-use of any
-    /// allocations.
-    ///
-    /// After doing this, it's important to call [`clear`] to clean up any
-    /// residual state.
+    /// Get the maximum number of bytes a single allocation performed by the
+    /// virtual machine is permitted to request.
     ///
-    /// [`clear`]: Vm::clear
-    /// [`Context`]: crate::Context
-    /// [`new`]: Vm::new
+    /// See [`Vm::set_max_allocation`].
     #[inline]
-    pub fn context_mut(&mut self) -> &mut Arc<RuntimeContext> {
-        &mut self.context
+    pub fn max_allocation(&self) -> Option<usize> {
+        self.max_allocation
     }
 
-    /// Access the context related to the virtual machine.
+    /// Set the maximum length in bytes of strings built by the virtual
+    /// machine, such as through template strings like `` `${a}${b}` ``.
+    ///
+    /// Building a string which would exceed the limit errors as soon as it
+    /// does so. Note that strings constructed by native functions are not
+    /// limited. Defaults to `None`.
     #[inline]
-    pub fn context(&self) -> &Arc<RuntimeContext> {
-        &self.context
+    pub fn set_max_string_len(&mut self, len: Option<usize>) {
+        self.max_string_len = len;
     }
 
-    /// Access the underlying unit of the virtual machine mutably.
-    ///
-    /// Note that this can be used to swap out the [`Unit`] of execution in the
-    /// running vm. In contrast to constructing a [`new`] vm, this allows for
-    /// amortised re-use of any allocations.
+    /// Get the maximum length in bytes of strings built by the virtual
+    /// machine.
     ///
-    /// After doing this, it's important to call [`clear`] to clean up any
-    /// residual state.
+    /// See [`Vm::set_max_string_len`].
+    #[inline]
+    pub fn max_string_len(&self) -> Option<usize> {
+        self.max_string_len
+    }
+
+    /// Set the maximum number of live elements in vectors, tuples and objects
+    /// constructed by the virtual machine during a call, or `None` for no
+    /// limit.
     ///
-    /// [`clear`]: Vm::clear
-    /// [`new`]: Vm::new
+    /// This is a coarse safeguard against scripts which exhaust memory by
+    /// constructing many collections. It acts as a cumulative allocation cap:
+    /// the virtual machine can't observe when values are dropped since they
+    /// may escape to native code, so elements are counted when they are
+    /// constructed and the count never decreases. This makes it an upper
+    /// bound on the number of elements which are live. The count is reset
+    /// each time a function is called through the virtual machine, and
+    /// exceeding the limit causes an error. Note that collections constructed
+    /// or grown by native functions are not counted. Defaults to `None`.
     #[inline]
-    pub fn unit_mut(&mut self) -> &mut Arc<Unit> {
-        &mut self.unit
+    pub fn set_max_live_elements(&mut self, limit: Option<usize>) {
+        self.max_live_elements = limit;
     }
 
-    /// Access the underlying unit of the virtual machine.
+    /// Get the maximum cumulative number of elements in collections
+    /// constructed by the virtual machine during a call.
+    ///
+    /// See [`Vm::set_max_live_elements`].
     #[inline]
-    pub fn unit(&self) -> &Arc<Unit> {
-        &self.unit
+    pub fn max_live_elements(&self) -> Option<usize> {
+        self.max_live_elements
     }
 
-    /// Access the current instruction pointer.
+    /// Get the number of elements in collections constructed since execution
+    /// of the current call started.
+    ///
+    /// See [`Vm::set_max_live_elements`].
     #[inline]
-    pub fn ip(&self) -> usize {
-        self.ip
+    pub fn live_elements(&self) -> usize {
+        self.live_elements
     }
 
-    /// Access the last instruction that was executed.
+    /// Count the elements of a newly constructed collection against the
+    /// configured element limit.
     #[inline]
-    pub fn last_ip(&self) -> usize {
-        self.ip.wrapping_sub(self.last_ip_len as usize)
+    fn count_elements(&mut self, count: usize) -> Result<(), VmErrorKind> {
+        let live_elements = self.live_elements.saturating_add(count);
+
+        if let Some(limit) = self.max_live_elements {
+            if live_elements > limit {
+                return Err(VmErrorKind::ElementLimitExceeded { limit });
+            }
+        }
+
+        self.live_elements = live_elements;
+        Ok(())
     }
 
-    /// Reset this virtual machine, freeing all memory used.
-    pub fn clear(&mut self) {
-        self.ip = 0;
-        self.stack.clear();
-        self.call_frames.clear();
+    /// Check that a string of the given length is within the configured
+    /// string length limit.
+    #[inline]
+    fn check_string_len(&self, length: usize) -> Result<(), VmErrorKind> {
+        if let Some(limit) = self.max_string_len {
+            if length > limit {
+                return Err(VmErrorKind::StringLengthExceeded { length, limit });
+            }
+        }
+
+        Ok(())
     }
 
-    /// Look up a function in the virtual machine by its name.
+    /// Set how integer arithmetic performed by the virtual machine behaves on
+    /// overflow. Defaults to [`OverflowMode::Checked`], which raises an error.
+    ///
+    /// This affects the arithmetic operators `+`, `-`, `*`, `/` and `%` and
+    /// their assigning variants. Division or remainder by zero is configured
+    /// separately through [`Vm::set_div_by_zero`].
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```
+    /// use rune::runtime::OverflowMode;
     /// use rune::sync::Arc;
-    /// use rune::{Context, Unit, Vm};
+    /// use rune::{Context, Vm};
     ///
     /// let mut sources = rune::sources! {
     ///     entry => {
-    ///         pub fn max(a, b) {
-    ///             if a > b {
-    ///                 a
-    ///             } else {
-    ///                 b
-    ///             }
+    ///         pub fn main(a) {
+    ///             a + 1
     ///         }
     ///     }
     /// };
@@ -279,143 +441,1402 @@ impl Vm {
     /// let unit = rune::prepare(&mut sources).build()?;
     /// let unit = Arc::try_new(unit)?;
     ///
-    /// let vm = Vm::new(runtime, unit);
+    /// let mut vm = Vm::new(runtime, unit);
+    /// assert!(vm.call(["main"], (i64::MAX,)).is_err());
     ///
-    /// // Looking up an item from the source.
-    /// let dynamic_max = vm.lookup_function(["max"])?;
+    /// vm.set_integer_overflow_mode(OverflowMode::Wrapping);
+    /// let output: i64 = rune::from_value(vm.call(["main"], (i64::MAX,))?)?;
+    /// assert_eq!(output, i64::MIN);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    #[inline]
+    pub fn set_integer_overflow_mode(&mut self, mode: OverflowMode) {
+        self.overflow_mode = mode;
+    }
+
+    /// Get how integer arithmetic performed by the virtual machine behaves on
+    /// overflow.
     ///
-    /// let value = dynamic_max.call::<i64>((10, 20))?;
-    /// assert_eq!(value, 20);
+    /// See [`Vm::set_integer_overflow_mode`].
+    #[inline]
+    pub fn integer_overflow_mode(&self) -> OverflowMode {
+        self.overflow_mode
+    }
+
+    /// Set how integer division and remainder by zero performed by the
+    /// virtual machine behaves. Defaults to [`DivByZero::Error`], which raises
+    /// an error.
     ///
-    /// // Building an item buffer to lookup an `::std` item.
-    /// let item = rune::item!(::std::i64::max);
-    /// let max = vm.lookup_function(item)?;
+    /// This affects the `/` and `%` operators and their assigning variants
+    /// when applied to integers, which is useful for languages where dividing
+    /// by zero should produce a sentinel value rather than fail.
     ///
-    /// let value = max.call::<i64>((10, 20))?;
-    /// assert_eq!(value, 20);
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::DivByZero;
+    /// use rune::sync::Arc;
+    /// use rune::{Context, Vm};
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main(a, b) {
+    ///             a / b
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::try_new(context.runtime()?)?;
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let unit = Arc::try_new(unit)?;
+    ///
+    /// let mut vm = Vm::new(runtime, unit);
+    /// assert!(vm.call(["main"], (5i64, 0i64)).is_err());
+    ///
+    /// vm.set_div_by_zero(DivByZero::Saturate);
+    /// let output: i64 = rune::from_value(vm.call(["main"], (5i64, 0i64))?)?;
+    /// assert_eq!(output, i64::MAX);
     /// # Ok::<_, rune::support::Error>(())
     /// ```
-    pub fn lookup_function<N>(&self, name: N) -> Result<Function, VmError>
-    where
-        N: ToTypeHash,
-    {
-        Ok(self.lookup_function_by_hash(name.to_type_hash())?)
+    #[inline]
+    pub fn set_div_by_zero(&mut self, mode: DivByZero) {
+        self.div_by_zero = mode;
     }
 
-    /// Convert into an execution.
-    pub(crate) fn into_execution(self) -> VmExecution<Self> {
-        VmExecution::new(self)
+    /// Get how integer division and remainder by zero performed by the
+    /// virtual machine behaves.
+    ///
+    /// See [`Vm::set_div_by_zero`].
+    #[inline]
+    pub fn div_by_zero(&self) -> DivByZero {
+        self.div_by_zero
     }
 
-    /// Run the given vm to completion.
+    /// Set whether the virtual machine runs in read-only mode, where scripts
+    /// are not permitted to mutate values which are owned externally, such as
+    /// values passed in by reference through [`Value::from_mut`].
     ///
-    /// # Errors
-    ///
-    /// If any non-completing outcomes like yielding or awaiting are
-    /// encountered, this will error.
-    pub fn complete(self) -> Result<Value, VmError> {
-        self.into_execution().complete()
+    /// In read-only mode, index and field assignments and assigning operators
+    /// like `+=` which target an externally owned value error. Values owned by
+    /// the virtual machine, including values which were passed in by value,
+    /// can still be mutated. Note that this doesn't prevent native functions
+    /// from mutating the values they are called with. Defaults to `false`.
+    #[inline]
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
     }
 
-    /// Run the given vm to completion with support for async functions.
-    pub async fn async_complete(self) -> Result<Value, VmError> {
-        self.into_execution().resume().await?.into_complete()
+    /// Test if the virtual machine runs in read-only mode.
+    ///
+    /// See [`Vm::set_read_only`].
+    #[inline]
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
     }
 
-    /// Call the function identified by the given name.
+    /// Set whether the virtual machine runs in strict conditions mode, where
+    /// the condition of a conditional jump must be a boolean.
     ///
-    /// Computing the function hash from the name can be a bit costly, so it's
-    /// worth noting that it can be precalculated:
+    /// By default, a conditional jump only considers a condition which is
+    /// exactly the boolean it's testing for and silently falls through for
+    /// any other value. So `if 1 { .. }` is never treated as true and doesn't
+    /// error either. In strict conditions mode such a condition instead
+    /// causes an error, which can help catch mistakes in scripts. Defaults to
+    /// `false`.
     ///
-    /// ```
-    /// use rune::Hash;
+    /// # Examples
     ///
-    /// let name = Hash::type_hash(["main"]);
     /// ```
+    /// use rune::sync::Arc;
+    /// use rune::{Context, Vm};
     ///
-    /// # Examples
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main(cond) {
+    ///             if cond { 1 } else { 2 }
+    ///         }
+    ///     }
+    /// };
     ///
-    /// ```no_run
-    /// use rune::sync::Arc;
-    /// use rune::{Context, Unit, Vm};
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::try_new(context.runtime()?)?;
     ///
-    /// let unit = Arc::try_new(Unit::default())?;
-    /// let mut vm = Vm::without_runtime(unit)?;
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let unit = Arc::try_new(unit)?;
     ///
-    /// let output = vm.execute(["main"], (33i64,))?.complete()?;
-    /// let output: i64 = rune::from_value(output)?;
+    /// let mut vm = Vm::new(runtime, unit);
+    /// let output: i64 = rune::from_value(vm.call(["main"], (42i64,))?)?;
+    /// assert_eq!(output, 2);
     ///
-    /// println!("output: {}", output);
+    /// vm.set_strict_conditions(true);
+    /// assert!(vm.call(["main"], (42i64,)).is_err());
     /// # Ok::<_, rune::support::Error>(())
     /// ```
+    #[inline]
+    pub fn set_strict_conditions(&mut self, strict_conditions: bool) {
+        self.strict_conditions = strict_conditions;
+    }
+
+    /// Test if the virtual machine runs in strict conditions mode.
     ///
-    /// You can use a `Vec<Value>` to provide a variadic collection of
-    /// arguments.
+    /// See [`Vm::set_strict_conditions`].
+    #[inline]
+    pub fn is_strict_conditions(&self) -> bool {
+        self.strict_conditions
+    }
+
+    /// Set whether objects constructed by the virtual machine keep track of
+    /// the order in which their keys are inserted.
     ///
-    /// ```no_run
+    /// Objects are hash maps, so their entries are otherwise iterated in an
+    /// arbitrary order. Objects constructed through object literals like
+    /// `#{b: 1, a: 2}` while this is enabled remember the order of their keys,
+    /// starting with the order they appear in the literal. Formatting,
+    /// serializing or iterating over such objects in a script follows this
+    /// order, which is useful for reproducible output. In Rust the order is
+    /// followed by [`Object::iter_ordered`]. Defaults to `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
     /// use rune::sync::Arc;
-    /// use rune::{Context, Unit, Vm};
+    /// use rune::{Context, Vm};
     ///
-    /// // Normally the unit would be created by compiling some source,
-    /// // and since this one is empty it won't do anything.
-    /// let unit = Arc::try_new(Unit::default())?;
-    /// let mut vm = Vm::without_runtime(unit)?;
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() {
+    ///             let object = #{b: 1, c: 2};
+    ///             object.a = 3;
+    ///             object
+    ///         }
+    ///     }
+    /// };
     ///
-    /// let mut args = Vec::new();
-    /// args.push(rune::to_value(1u32)?);
-    /// args.push(rune::to_value(String::from("Hello World"))?);
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::try_new(context.runtime()?)?;
     ///
-    /// let output = vm.execute(["main"], args)?.complete()?;
-    /// let output: i64 = rune::from_value(output)?;
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let unit = Arc::try_new(unit)?;
     ///
-    /// println!("output: {}", output);
+    /// let mut vm = Vm::new(runtime, unit);
+    /// vm.set_object_preserve_insertion_order(true);
+    ///
+    /// let object: rune::runtime::Object = rune::from_value(vm.call(["main"], ())?)?;
+    /// assert!(object.tracks_insertion_order());
+    ///
+    /// let keys = object
+    ///     .iter_ordered()
+    ///     .map(|(key, _)| key.as_str())
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(keys, ["b", "c", "a"]);
     /// # Ok::<_, rune::support::Error>(())
     /// ```
-    pub fn execute(
-        &mut self,
-        name: impl ToTypeHash,
-        args: impl Args,
-    ) -> Result<VmExecution<&mut Self>, VmError> {
-        self.set_entrypoint(name, args.count())?;
-        args.into_stack(&mut self.stack)?;
-        Ok(VmExecution::new(self))
+    #[inline]
+    pub fn set_object_preserve_insertion_order(&mut self, preserve: bool) {
+        self.object_insertion_order = preserve;
     }
 
-    /// An `execute` variant that returns an execution which implements
-    /// [`Send`], allowing it to be sent and executed on a different thread.
+    /// Test if objects constructed by the virtual machine keep track of the
+    /// order in which their keys are inserted.
     ///
-    /// This is accomplished by preventing values escaping from being
-    /// non-exclusively sent with the execution or escaping the execution. We
-    /// only support encoding arguments which themselves are `Send`.
-    pub fn send_execute(
-        mut self,
-        name: impl ToTypeHash,
-        args: impl Args + Send,
-    ) -> Result<VmSendExecution, VmError> {
-        // Safety: make sure the stack is clear, preventing any values from
-        // being sent along with the virtual machine.
-        self.stack.clear();
+    /// See [`Vm::set_object_preserve_insertion_order`].
+    #[inline]
+    pub fn object_preserve_insertion_order(&self) -> bool {
+        self.object_insertion_order
+    }
 
-        self.set_entrypoint(name, args.count())?;
-        args.into_stack(&mut self.stack)?;
-        Ok(VmSendExecution(VmExecution::new(self)))
+    /// Check that the given value is permitted to be mutated.
+    #[inline]
+    fn check_read_only(&self, target: &Value) -> Result<(), VmErrorKind> {
+        if !self.read_only {
+            return Ok(());
+        }
+
+        if let Repr::Any(value) = target.as_ref() {
+            if !value.is_owned() {
+                return Err(VmErrorKind::ReadOnlyViolation {
+                    target: value.type_info(),
+                });
+            }
+        }
+
+        Ok(())
     }
 
-    /// Call the given function immediately, returning the produced value.
-    ///
+    /// Check that the value an assignment targets is permitted to be mutated.
+    #[inline]
+    fn check_read_only_target(&self, target: InstTarget) -> Result<(), VmErrorKind> {
+        let (InstTarget::Address(addr)
+        | InstTarget::Field(addr, _)
+        | InstTarget::TupleField(addr, _)) = target;
+
+        self.check_read_only(self.stack.at(addr))
+    }
+
+    /// Check that an allocation of the given number of bytes is within the
+    /// configured allocation limit.
+    #[inline]
+    fn check_allocation(&self, requested: usize) -> Result<(), VmErrorKind> {
+        if let Some(limit) = self.max_allocation {
+            if requested > limit {
+                return Err(VmErrorKind::AllocationLimitExceeded { requested, limit });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that an allocation of `count` values is within the configured
+    /// allocation limit.
+    #[inline]
+    fn check_values_allocation(&self, count: usize) -> Result<(), VmErrorKind> {
+        self.check_allocation(count.saturating_mul(size_of::<Value>()))
+    }
+
+    /// Set a handler which is called whenever the virtual machine yields,
+    /// or `None` to restore the default behavior.
+    ///
+    /// By default yielding halts execution, producing the yielded value as a
+    /// [`VmOutcome::Yielded`] through [`VmExecution`]. When a handler is set
+    /// it is instead called inline with the yielded value, or `None` if the
+    /// unit value was yielded, and the value it returns is used to resume
+    /// execution without leaving the virtual machine.
+    ///
+    /// The handler is not retained when the virtual machine is cloned, nor
+    /// is it used by generators constructed by the virtual machine since they
+    /// run in virtual machines of their own.
+    #[inline]
+    pub fn set_yield_handler(
+        &mut self,
+        handler: Option<Box<dyn FnMut(Option<Value>) -> Result<Value, VmError> + Send>>,
+    ) {
+        self.yield_handler = handler.map(YieldHandler);
+    }
+
+    /// Set a handler which is consulted when a called function can't be
+    /// found, or `None` to restore the default behavior.
+    ///
+    /// The handler is called with the hash of the missing function, the
+    /// stack, the address of the arguments, the number of arguments, and the
+    /// output to store the result in. For instance function calls, the hash
+    /// is that of the function name and the instance is the first argument.
+    ///
+    /// If the handler returns `Ok(true)` it is assumed to have stored the
+    /// result of the call in the output, otherwise a missing function error
+    /// is raised as usual.
+    ///
+    /// This can be used to implement dynamic method resolution, such as for
+    /// proxy objects.
+    #[inline]
+    pub fn set_missing_function_handler(
+        &mut self,
+        handler: Option<
+            Box<
+                dyn FnMut(Hash, &mut Stack, Address, usize, Output) -> Result<bool, VmError> + Send,
+            >,
+        >,
+    ) {
+        self.missing_function_handler = handler.map(MissingFunctionHandler);
+    }
+
+    /// Set a handler which is consulted when a field can't be found on a
+    /// value of the type identified by `type_hash`, or `None` to remove it.
+    ///
+    /// The handler is called with the value and the name of the field when
+    /// getting a field like `value.field` fails because the type has no such
+    /// field. If it returns `Ok(Some(value))` that value is used as the
+    /// field, otherwise the usual error is raised.
+    ///
+    /// This can be used to implement dynamic or proxy objects whose fields
+    /// are computed on demand.
+    ///
+    /// The handlers are not retained when the virtual machine is cloned.
+    pub fn set_dynamic_field_handler(
+        &mut self,
+        type_hash: Hash,
+        handler: Option<Box<dyn FnMut(&Value, &str) -> Result<Option<Value>, VmError> + Send>>,
+    ) -> alloc::Result<()> {
+        let result = self
+            .dynamic_field_handlers
+            .binary_search_by(|e| e.0.cmp(&type_hash));
+
+        match (result, handler) {
+            (Ok(index), Some(handler)) => {
+                self.dynamic_field_handlers[index].1 = DynamicFieldHandler(handler);
+            }
+            (Ok(index), None) => {
+                self.dynamic_field_handlers.remove(index);
+            }
+            (Err(index), Some(handler)) => {
+                self.dynamic_field_handlers
+                    .try_insert(index, (type_hash, DynamicFieldHandler(handler)))?;
+            }
+            (Err(..), None) => {}
+        }
+
+        Ok(())
+    }
+
+    /// Set a mock which replaces calls to the function identified by `hash`,
+    /// or `None` to remove it.
+    ///
+    /// The mock is called with the stack, the address of the arguments, the
+    /// number of arguments, and the output to store the result in. It takes
+    /// precedence over both functions in the unit and functions provided
+    /// through the context, both when the function is called directly and
+    /// when it's called through a function value.
+    ///
+    /// This can be used to test scripts in isolation by replacing the
+    /// functions they depend on with stubs.
+    ///
+    /// The mocks are not retained when the virtual machine is cloned.
+    pub fn set_call_mock(
+        &mut self,
+        hash: Hash,
+        mock: Option<
+            Box<dyn FnMut(&mut Stack, Address, usize, Output) -> Result<(), VmError> + Send>,
+        >,
+    ) -> alloc::Result<()> {
+        let result = self.call_mocks.binary_search_by(|e| e.0.cmp(&hash));
+
+        match (result, mock) {
+            (Ok(index), Some(mock)) => {
+                self.call_mocks[index].1 = CallMock(mock);
+            }
+            (Ok(index), None) => {
+                self.call_mocks.remove(index);
+            }
+            (Err(index), Some(mock)) => {
+                self.call_mocks.try_insert(index, (hash, CallMock(mock)))?;
+            }
+            (Err(..), None) => {}
+        }
+
+        Ok(())
+    }
+
+    /// Set a writer which receives a line for each instruction executed by
+    /// the virtual machine, or `None` to disable tracing.
+    ///
+    /// Each line consists of the instruction pointer followed by the decoded
+    /// instruction, like `0004 = return-unit`. Unlike the `tracing` events
+    /// emitted by the virtual machine this doesn't require a subscriber,
+    /// which makes it a simple way to debug embedded scripts.
+    ///
+    /// The writer is not retained when the virtual machine is cloned.
+    #[inline]
+    pub fn set_trace_writer(&mut self, writer: Option<Box<dyn FnMut(fmt::Arguments<'_>) + Send>>) {
+        self.trace_writer = writer.map(TraceWriter);
+    }
+
+    /// Start recording the nondeterministic decisions made by the virtual
+    /// machine, such as which branch a `select` expression takes.
+    ///
+    /// The recorded [`ReplayLog`] is retrieved through
+    /// [`Vm::stop_recording`], and can be replayed through
+    /// [`Vm::start_replay`] to reproduce the same execution. This replaces
+    /// any ongoing recording or replay.
+    ///
+    /// Generators, streams and async functions which are not awaited
+    /// immediately run in virtual machines of their own and are not recorded.
+    pub fn start_recording(&mut self) {
+        self.replay = VmReplay::Record(ReplayLog::new());
+    }
+
+    /// Stop recording, returning the recorded log.
+    ///
+    /// If the recording has been replaced through [`Vm::start_recording`] or
+    /// [`Vm::start_replay`], an empty log is returned.
+    pub fn stop_recording(&mut self) -> ReplayLog {
+        match replace(&mut self.replay, VmReplay::None) {
+            VmReplay::Record(log) => log,
+            replay => {
+                self.replay = replay;
+                ReplayLog::new()
+            }
+        }
+    }
+
+    /// Start replaying the decisions in the given log, forcing the virtual
+    /// machine to make the same decisions as when it was recorded.
+    ///
+    /// If execution diverges from the log, such as when a `select` is
+    /// reached after the log has been exhausted or the recorded branch can no
+    /// longer be taken, an error is raised. This replaces any ongoing
+    /// recording or replay.
+    pub fn start_replay(&mut self, log: ReplayLog) {
+        self.replay = VmReplay::Replay { log, index: 0 };
+    }
+
+    /// Stop replaying, returning to making decisions as they happen.
+    pub fn stop_replay(&mut self) {
+        if let VmReplay::Replay { .. } = self.replay {
+            self.replay = VmReplay::None;
+        }
+    }
+
+    /// Record that the select at the current instruction completed by
+    /// jumping to the given instruction pointer.
+    #[inline]
+    pub(crate) fn record_select(&mut self, ip: usize) -> alloc::Result<()> {
+        self.replay.record_select(Some(ip.wrapping_sub(self.ip)))
+    }
+
+    /// Set  the current instruction pointer.
+    #[inline]
+    pub fn set_ip(&mut self, ip: usize) {
+        self.ip = ip;
+    }
+
+    /// Get the stack.
+    #[inline]
+    pub fn call_frames(&self) -> &[CallFrame] {
+        &self.call_frames
+    }
+
+    /// Get the stack.
+    #[inline]
+    pub fn stack(&self) -> &Stack {
+        &self.stack
+    }
+
+    /// Get the stack mutably.
+    #[inline]
+    pub fn stack_mut(&mut self) -> &mut Stack {
+        &mut self.stack
+    }
+
+    /// Access the context related to the virtual machine mutably.
+    ///
+    /// Note that this can be used to swap out the [`RuntimeContext`] associated
+    /// with the running vm. Note that this is only necessary if the underlying
+    /// [`Context`] is different or has been modified. In contrast to
+    /// constructing a [`new`] vm, this allows for amortised re-use of any
+    /// allocations.
+    ///
+    /// After doing this, it's important to call [`clear`] to clean up any
+    /// residual state.
+    ///
+    /// [`clear`]: Vm::clear
+    /// [`Context`]: crate::Context
+    /// [`new`]: Vm::new
+    #[inline]
+    pub fn context_mut(&mut self) -> &mut Arc<RuntimeContext> {
+        &mut self.context
+    }
+
+    /// Access the context related to the virtual machine.
+    #[inline]
+    pub fn context(&self) -> &Arc<RuntimeContext> {
+        &self.context
+    }
+
+    /// Access the underlying unit of the virtual machine mutably.
+    ///
+    /// Note that this can be used to swap out the [`Unit`] of execution in the
+    /// running vm. In contrast to constructing a [`new`] vm, this allows for
+    /// amortised re-use of any allocations.
+    ///
+    /// After doing this, it's important to call [`clear`] to clean up any
+    /// residual state.
+    ///
+    /// [`clear`]: Vm::clear
+    /// [`new`]: Vm::new
+    #[inline]
+    pub fn unit_mut(&mut self) -> &mut Arc<Unit> {
+        &mut self.unit
+    }
+
+    /// Access the underlying unit of the virtual machine.
+    #[inline]
+    pub fn unit(&self) -> &Arc<Unit> {
+        &self.unit
+    }
+
+    /// Access the current instruction pointer.
+    #[inline]
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    /// Access the last instruction that was executed.
+    #[inline]
+    pub fn last_ip(&self) -> usize {
+        self.ip.wrapping_sub(self.last_ip_len as usize)
+    }
+
+    /// Decode the instruction at the current instruction pointer, which is
+    /// the next instruction to be executed.
+    ///
+    /// Returns `None` if the instruction pointer is at the end of the unit.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the instruction at the current instruction pointer can't be
+    /// decoded.
+    #[inline]
+    pub fn current_instruction(&self) -> Result<Option<Inst>, VmError> {
+        let Some((inst, _)) = self.unit.instruction_at(self.ip)? else {
+            return Ok(None);
+        };
+
+        Ok(Some(inst))
+    }
+
+    /// Access the instruction pointer of the instruction which was being
+    /// executed when the virtual machine last raised an error.
+    ///
+    /// Unlike [`Vm::ip`] this isn't affected by the instruction pointer
+    /// advancing past the faulting instruction, which makes it useful for
+    /// post-mortem debugging. This is cleared once the virtual machine runs
+    /// without raising an error.
+    #[inline]
+    pub fn error_ip(&self) -> Option<usize> {
+        self.error_ip
+    }
+
+    /// Visit every instruction in the unit associated with the virtual
+    /// machine in order, together with its instruction pointer.
+    ///
+    /// This does not execute any instructions, which makes it suitable for
+    /// static analysis such as detecting the use of certain operations. The
+    /// visitor can stop the walk early by returning [`ControlFlow::Break`].
+    ///
+    /// [`ControlFlow::Break`]: core::ops::ControlFlow::Break
+    ///
+    /// # Errors
+    ///
+    /// Errors if the unit contains an instruction which can't be decoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::ops::ControlFlow;
+    ///
+    /// use rune::sync::Arc;
+    /// use rune::{Context, Vm};
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main(a, b) {
+    ///             a + b
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::try_new(context.runtime()?)?;
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let unit = Arc::try_new(unit)?;
+    ///
+    /// let vm = Vm::new(runtime, unit);
+    ///
+    /// let mut count = 0;
+    ///
+    /// vm.visit_instructions(|_, _| {
+    ///     count += 1;
+    ///     ControlFlow::Continue(())
+    /// })?;
+    ///
+    /// assert!(count > 0);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn visit_instructions<F>(&self, mut f: F) -> Result<(), VmError>
+    where
+        F: FnMut(usize, &Inst) -> core::ops::ControlFlow<()>,
+    {
+        let mut ip = 0;
+
+        while let Some((inst, inst_len)) = self.unit.instruction_at(ip)? {
+            if f(ip, &inst).is_break() {
+                break;
+            }
+
+            ip = ip.wrapping_add(inst_len);
+        }
+
+        Ok(())
+    }
+
+    /// Look up the set of stack addresses which are cleared by a `Drop`
+    /// instruction referencing the drop set `set`.
+    ///
+    /// This is intended for tooling which verifies the integrity of a unit,
+    /// such as ensuring that every address in a drop set is within the bounds
+    /// of the stack allocated for the function it is used in.
+    ///
+    /// Returns `None` if the unit doesn't contain the given drop set, which
+    /// would cause execution to error with a missing drop set.
+    #[inline]
+    pub fn drop_set(&self, set: usize) -> Option<&[Address]> {
+        self.unit.lookup_drop_set(set)
+    }
+
+    /// Reset this virtual machine, freeing all memory used.
+    pub fn clear(&mut self) {
+        self.ip = 0;
+        self.stack.clear();
+        self.call_frames.clear();
+    }
+
+    /// Look up a function in the virtual machine by its name.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rune::sync::Arc;
+    /// use rune::{Context, Unit, Vm};
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn max(a, b) {
+    ///             if a > b {
+    ///                 a
+    ///             } else {
+    ///                 b
+    ///             }
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::try_new(context.runtime()?)?;
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let unit = Arc::try_new(unit)?;
+    ///
+    /// let vm = Vm::new(runtime, unit);
+    ///
+    /// // Looking up an item from the source.
+    /// let dynamic_max = vm.lookup_function(["max"])?;
+    ///
+    /// let value = dynamic_max.call::<i64>((10, 20))?;
+    /// assert_eq!(value, 20);
+    ///
+    /// // Building an item buffer to lookup an `::std` item.
+    /// let item = rune::item!(::std::i64::max);
+    /// let max = vm.lookup_function(item)?;
+    ///
+    /// let value = max.call::<i64>((10, 20))?;
+    /// assert_eq!(value, 20);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn lookup_function<N>(&self, name: N) -> Result<Function, VmError>
+    where
+        N: ToTypeHash,
+    {
+        Ok(self.lookup_function_by_hash(name.to_type_hash())?)
+    }
+
+    /// Test if the function with the given name is an `async` function,
+    /// which must be called through [`Vm::async_call`] to be completed.
+    ///
+    /// Returns `None` if the function is a native function provided through
+    /// the context, since their calling convention is not known.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the function doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::sync::Arc;
+    /// use rune::{Context, Vm};
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn sync() {
+    ///             1
+    ///         }
+    ///
+    ///         pub async fn not_sync() {
+    ///             2
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::try_new(context.runtime()?)?;
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let unit = Arc::try_new(unit)?;
+    ///
+    /// let vm = Vm::new(runtime, unit);
+    ///
+    /// assert_eq!(vm.is_async_function(["sync"])?, Some(false));
+    /// assert_eq!(vm.is_async_function(["not_sync"])?, Some(true));
+    /// assert_eq!(vm.is_async_function(rune::item!(::std::i64::max))?, None);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn is_async_function<N>(&self, name: N) -> Result<Option<bool>, VmError>
+    where
+        N: ToTypeHash,
+    {
+        let hash = name.to_type_hash();
+
+        let Some(info) = self.unit.function(&hash) else {
+            if self.context.function(&hash).is_none() {
+                return Err(VmError::new(VmErrorKind::MissingContextFunction { hash }));
+            }
+
+            return Ok(None);
+        };
+
+        match info {
+            UnitFn::Offset { call, .. } => Ok(Some(matches!(call, Call::Async))),
+            // NB: Struct and variant constructors are called immediately.
+            _ => Ok(Some(false)),
+        }
+    }
+
+    /// Look up the value of a constant defined in the unit by name.
+    ///
+    /// Constants are evaluated when the unit is compiled, so this doesn't
+    /// execute any instructions. Returns `None` if the unit doesn't define a
+    /// constant with the given name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::sync::Arc;
+    /// use rune::{Context, Vm};
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub const NAME = "rune";
+    ///         pub const PORT = 8000 + 80;
+    ///     }
+    /// };
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::try_new(context.runtime()?)?;
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let unit = Arc::try_new(unit)?;
+    ///
+    /// let vm = Vm::new(runtime, unit);
+    ///
+    /// let port = vm.constant(["PORT"])?.expect("missing constant");
+    /// assert_eq!(rune::from_value::<i64>(port)?, 8080);
+    /// assert!(vm.constant(["MISSING"])?.is_none());
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn constant<N>(&self, name: N) -> Result<Option<Value>, VmError>
+    where
+        N: ToTypeHash,
+    {
+        let Some(value) = self.unit.constant(&name.to_type_hash()) else {
+            return Ok(None);
+        };
+
+        Ok(Some(value.to_value_with(&EmptyConstContext)?))
+    }
+
+    /// Convert into an execution.
+    pub(crate) fn into_execution(self) -> VmExecution<Self> {
+        VmExecution::new(self)
+    }
+
+    /// Run the given vm to completion.
+    ///
+    /// # Errors
+    ///
+    /// If any non-completing outcomes like yielding or awaiting are
+    /// encountered, this will error.
+    pub fn complete(self) -> Result<Value, VmError> {
+        self.into_execution().complete()
+    }
+
+    /// Run the given vm to completion with support for async functions.
+    pub async fn async_complete(self) -> Result<Value, VmError> {
+        self.into_execution().resume().await?.into_complete()
+    }
+
+    /// Call the function identified by the given name.
+    ///
+    /// Computing the function hash from the name can be a bit costly, so it's
+    /// worth noting that it can be precalculated:
+    ///
+    /// ```
+    /// use rune::Hash;
+    ///
+    /// let name = Hash::type_hash(["main"]);
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rune::sync::Arc;
+    /// use rune::{Context, Unit, Vm};
+    ///
+    /// let unit = Arc::try_new(Unit::default())?;
+    /// let mut vm = Vm::without_runtime(unit)?;
+    ///
+    /// let output = vm.execute(["main"], (33i64,))?.complete()?;
+    /// let output: i64 = rune::from_value(output)?;
+    ///
+    /// println!("output: {}", output);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    ///
+    /// You can use a `Vec<Value>` to provide a variadic collection of
+    /// arguments.
+    ///
+    /// ```no_run
+    /// use rune::sync::Arc;
+    /// use rune::{Context, Unit, Vm};
+    ///
+    /// // Normally the unit would be created by compiling some source,
+    /// // and since this one is empty it won't do anything.
+    /// let unit = Arc::try_new(Unit::default())?;
+    /// let mut vm = Vm::without_runtime(unit)?;
+    ///
+    /// let mut args = Vec::new();
+    /// args.push(rune::to_value(1u32)?);
+    /// args.push(rune::to_value(String::from("Hello World"))?);
+    ///
+    /// let output = vm.execute(["main"], args)?.complete()?;
+    /// let output: i64 = rune::from_value(output)?;
+    ///
+    /// println!("output: {}", output);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn execute(
+        &mut self,
+        name: impl ToTypeHash,
+        args: impl Args,
+    ) -> Result<VmExecution<&mut Self>, VmError> {
+        self.set_entrypoint(name, args.count())?;
+        args.into_stack(&mut self.stack)?;
+        Ok(VmExecution::new(self))
+    }
+
+    /// Run the given function like [`Vm::execute`], but check that the
+    /// type of each argument matches the type hash at the same position in
+    /// `expected_types` before running it.
+    ///
+    /// Errors for the first argument of the wrong type, which is easier to
+    /// diagnose than the error raised once the argument is used inside of the
+    /// function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::TypeHash;
+    /// use rune::sync::Arc;
+    /// use rune::{Context, Vm};
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main(n) {
+    ///             n + 1
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::try_new(context.runtime()?)?;
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let unit = Arc::try_new(unit)?;
+    ///
+    /// let mut vm = Vm::new(runtime, unit);
+    ///
+    /// let output = vm.execute_checked(["main"], (1i64,), &[i64::HASH])?.complete()?;
+    /// assert_eq!(rune::from_value::<i64>(output)?, 2);
+    ///
+    /// let result = vm.execute_checked(["main"], ("one",), &[i64::HASH]);
+    /// assert!(result.is_err());
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn execute_checked(
+        &mut self,
+        name: impl ToTypeHash,
+        args: impl Args,
+        expected_types: &[Hash],
+    ) -> Result<VmExecution<&mut Self>, VmError> {
+        let count = args.count();
+        check_args(count, expected_types.len())?;
+        self.set_entrypoint(name, count)?;
+        args.into_stack(&mut self.stack)?;
+
+        let values = self.stack.slice_at(Address::ZERO, count)?;
+
+        let mismatch = values
+            .iter()
+            .zip(expected_types)
+            .enumerate()
+            .find(|(_, (value, &expected))| value.type_hash() != expected);
+
+        if let Some((position, (value, &expected))) = mismatch {
+            let kind = VmErrorKind::ArgumentTypeMismatch {
+                position,
+                expected,
+                actual: value.type_info(),
+            };
+
+            self.stack.clear();
+            return Err(VmError::new(kind));
+        }
+
+        Ok(VmExecution::new(self))
+    }
+
+    /// Run the given function by its precomputed hash. This is the same as
+    /// [`Vm::execute`], except the hash of the function doesn't have to be
+    /// computed for every call.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rune::sync::Arc;
+    /// use rune::{Hash, Unit, Vm};
+    ///
+    /// // Normally the unit would be created by compiling some source,
+    /// // and since this one is empty it won't do anything.
+    /// let unit = Arc::try_new(Unit::default())?;
+    /// let mut vm = Vm::without_runtime(unit)?;
+    ///
+    /// let main = Hash::type_hash(["main"]);
+    ///
+    /// for n in 0..10i64 {
+    ///     let output = vm.execute_hash(main, (n,))?.complete()?;
+    ///     let output: i64 = rune::from_value(output)?;
+    ///     println!("output: {}", output);
+    /// }
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    #[inline]
+    pub fn execute_hash(
+        &mut self,
+        hash: Hash,
+        args: impl Args,
+    ) -> Result<VmExecution<&mut Self>, VmError> {
+        self.execute(hash, args)
+    }
+
+    /// Run the given function to completion, returning the produced value
+    /// together with the number of instructions which were executed.
+    ///
+    /// Since instruction counts are deterministic for a given unit and set of
+    /// arguments, this is useful for golden tests of the compiler and the
+    /// virtual machine. Only instructions executed by this virtual machine
+    /// are counted, so deferred calls such as async functions which are not
+    /// awaited are not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::sync::Arc;
+    /// use rune::{Context, Vm};
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main(n) {
+    ///             let total = 0;
+    ///
+    ///             for i in 0..n {
+    ///                 total += i;
+    ///             }
+    ///
+    ///             total
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::try_new(context.runtime()?)?;
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let unit = Arc::try_new(unit)?;
+    ///
+    /// let mut vm = Vm::new(runtime, unit);
+    ///
+    /// let (output, few) = vm.execute_counting(["main"], (2i64,))?;
+    /// assert_eq!(rune::from_value::<i64>(output)?, 1);
+    ///
+    /// let (output, many) = vm.execute_counting(["main"], (10i64,))?;
+    /// assert_eq!(rune::from_value::<i64>(output)?, 45);
+    ///
+    /// assert!(few < many);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn execute_counting(
+        &mut self,
+        name: impl ToTypeHash,
+        args: impl Args,
+    ) -> Result<(Value, usize), VmError> {
+        self.instruction_count = Some(0);
+
+        let value = self
+            .execute(name, args)
+            .and_then(|mut execution| execution.complete());
+
+        let count = self.instruction_count.take().unwrap_or_default();
+        Ok((value?, count))
+    }
+
+    /// Run the given function to completion, returning the produced value
+    /// together with any values which were left behind on the stack.
+    ///
+    /// Every call frame which returns during the call has the values it holds
+    /// beyond the size it was allocated with taken off the stack and
+    /// collected, rather than discarded. A well-formed call leaves no such
+    /// values, so any residual values indicate a stack imbalance, which makes
+    /// this useful when diagnosing issues in the compiler or the virtual
+    /// machine. The stack is cleared once the call has completed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::sync::Arc;
+    /// use rune::{Context, Vm};
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main(a, b) {
+    ///             let c = a * b;
+    ///             c + 1
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::try_new(context.runtime()?)?;
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let unit = Arc::try_new(unit)?;
+    ///
+    /// let mut vm = Vm::new(runtime, unit);
+    ///
+    /// let (output, residual) = vm.call_and_capture(["main"], (4i64, 5i64))?;
+    /// assert_eq!(rune::from_value::<i64>(output)?, 21);
+    /// assert!(residual.is_empty());
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn call_and_capture(
+        &mut self,
+        name: impl ToTypeHash,
+        args: impl Args,
+    ) -> Result<(Value, alloc::Vec<Value>), VmError> {
+        let vm = ClearStack(self);
+        vm.0.residual = Some(alloc::Vec::new());
+
+        let value =
+            vm.0.execute(name, args)
+                .and_then(|mut execution| execution.complete());
+
+        let residual = vm.0.residual.take().unwrap_or_default();
+        Ok((value?, residual))
+    }
+
+    /// An `execute` variant that returns an execution which implements
+    /// [`Send`], allowing it to be sent and executed on a different thread.
+    ///
+    /// This is accomplished by preventing values escaping from being
+    /// non-exclusively sent with the execution or escaping the execution. We
+    /// only support encoding arguments which themselves are `Send`.
+    pub fn send_execute(
+        mut self,
+        name: impl ToTypeHash,
+        args: impl Args + Send,
+    ) -> Result<VmSendExecution, VmError> {
+        // Safety: make sure the stack is clear, preventing any values from
+        // being sent along with the virtual machine.
+        self.stack.clear();
+
+        self.set_entrypoint(name, args.count())?;
+        args.into_stack(&mut self.stack)?;
+        Ok(VmSendExecution(VmExecution::new(self)))
+    }
+
+    /// Run the closure identified by `hash` to completion with an explicitly
+    /// provided captured environment, as opposed to one captured by the
+    /// virtual machine when the closure was constructed.
+    ///
+    /// The hash of a closure can be obtained through [`Function::type_hash`]
+    /// on a closure produced by a script.
+    ///
+    /// # Errors
+    ///
+    /// Errors if `hash` does not identify a closure which captures its
+    /// environment in the unit of the virtual machine, if the number of
+    /// `captures` does not match the number of variables captured by the
+    /// closure, or if the number of `args` does not match what the closure
+    /// expects.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::Function;
+    /// use rune::sync::Arc;
+    /// use rune::{Context, Vm};
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() {
+    ///             let a = 1;
+    ///             |b| a + b
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::try_new(context.runtime()?)?;
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let unit = Arc::try_new(unit)?;
+    ///
+    /// let mut vm = Vm::new(runtime, unit);
+    ///
+    /// let closure: Function = rune::from_value(vm.call(["main"], ())?)?;
+    ///
+    /// let output = vm
+    ///     .execute_with_captures(closure.type_hash(), (10i64,), (2i64,))?
+    ///     .complete()?;
+    ///
+    /// let output: i64 = rune::from_value(output)?;
+    /// assert_eq!(output, 12);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn execute_with_captures(
+        &mut self,
+        hash: impl ToTypeHash,
+        captures: impl Args,
+        args: impl Args,
+    ) -> Result<VmExecution<&mut Self>, VmError> {
+        let hash = hash.to_type_hash();
+
+        let Some(UnitFn::Offset {
+            offset,
+            args: expected,
+            captures: Some(expected_captures),
+            ..
+        }) = self.unit.function(&hash)
+        else {
+            return Err(VmError::new(VmErrorKind::MissingFunction { hash }));
+        };
+
+        let (offset, expected) = (*offset, *expected);
+
+        if captures.count() != *expected_captures {
+            return Err(VmError::new(VmErrorKind::BadEnvironmentCount {
+                expected: *expected_captures,
+                actual: captures.count(),
+            }));
+        }
+
+        // NB: The environment is passed as an additional trailing argument.
+        check_args(args.count().wrapping_add(1), expected)?;
+
+        let environment = OwnedTuple::try_from(captures.try_into_vec()?)?;
+
+        self.enter_entrypoint(offset);
+        args.into_stack(&mut self.stack)?;
+        (environment,).into_stack(&mut self.stack)?;
+        Ok(VmExecution::new(self))
+    }
+
+    /// Call the given function immediately, returning the produced value.
+    ///
+    /// This function permits for using references since it doesn't defer its
+    /// execution.
+    pub fn call(
+        &mut self,
+        name: impl ToTypeHash,
+        args: impl GuardedArgs,
+    ) -> Result<Value, VmError> {
+        self.set_entrypoint(name, args.count())?;
+        self.complete_guarded(args, ())
+    }
+
+    /// Call the given function by its precomputed hash immediately,
+    /// returning the produced value. This is the same as [`Vm::call`],
+    /// except the hash of the function doesn't have to be computed for every
+    /// call.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rune::sync::Arc;
+    /// use rune::{Hash, Unit, Vm};
+    ///
+    /// // Normally the unit would be created by compiling some source,
+    /// // and since this one is empty it won't do anything.
+    /// let unit = Arc::try_new(Unit::default())?;
+    /// let mut vm = Vm::without_runtime(unit)?;
+    ///
+    /// let add = Hash::type_hash(["add"]);
+    /// let mut sum = 0i64;
+    ///
+    /// for n in 0..10i64 {
+    ///     sum = rune::from_value(vm.call_hash(add, (sum, n))?)?;
+    /// }
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    #[inline]
+    pub fn call_hash(&mut self, hash: Hash, args: impl GuardedArgs) -> Result<Value, VmError> {
+        self.call(hash, args)
+    }
+
+    /// Call the given [`Function`] immediately, returning the produced value.
+    ///
+    /// In contrast to [`Function::call`], functions which live in the same
+    /// unit and context as the virtual machine are executed using it, which
+    /// re-uses its allocations. Any other function is called as if through
+    /// [`Function::call`].
+    ///
     /// This function permits for using references since it doesn't defer its
     /// execution.
-    pub fn call(
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::Function;
+    /// use rune::sync::Arc;
+    /// use rune::{Context, Vm};
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         fn add(a, b) {
+    ///             a + b
+    ///         }
+    ///
+    ///         pub fn main() {
+    ///             add
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::try_new(context.runtime()?)?;
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let unit = Arc::try_new(unit)?;
+    ///
+    /// let mut vm = Vm::new(runtime, unit);
+    ///
+    /// let add: Function = rune::from_value(vm.call(["main"], ())?)?;
+    ///
+    /// let output: i64 = rune::from_value(vm.call_function(&add, (1i64, 2i64))?)?;
+    /// assert_eq!(output, 3);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn call_function(
         &mut self,
-        name: impl ToTypeHash,
+        f: &Function,
         args: impl GuardedArgs,
     ) -> Result<Value, VmError> {
-        self.set_entrypoint(name, args.count())?;
+        f.call_in_vm(self, args)
+    }
+
+    /// Resolve and validate the function identified by the given name once,
+    /// producing a [`PreparedCall`] which can be called repeatedly without
+    /// looking it up again.
+    ///
+    /// This is useful for hot loops which call the same function many times,
+    /// since it avoids hashing the name and looking up the function for every
+    /// call, and pushes the owned arguments without guarding them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::sync::Arc;
+    /// use rune::{Context, Vm};
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn add(a, b) {
+    ///             a + b
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::try_new(context.runtime()?)?;
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let unit = Arc::try_new(unit)?;
+    ///
+    /// let mut vm = Vm::new(runtime, unit);
+    ///
+    /// let add = vm.prepare_call(["add"])?;
+    /// assert_eq!(add.args(), 2);
+    ///
+    /// let mut sum = 0i64;
+    ///
+    /// for n in 0..10i64 {
+    ///     let value = add.call(&mut vm, (sum, n))?;
+    ///     sum = rune::from_value(value)?;
+    /// }
+    ///
+    /// assert_eq!(sum, 45);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn prepare_call(&self, name: impl ToTypeHash) -> Result<PreparedCall, VmError> {
+        let (hash, offset, args) = self.lookup_entrypoint(name)?;
+        Ok(PreparedCall::new(self.unit.clone(), hash, offset, args))
+    }
+
+    /// Call a function which has been resolved through [`Vm::prepare_call`].
+    ///
+    /// Since the arguments are owned they are pushed directly onto the stack
+    /// without setting up a reference guard. The stack is cleared once the
+    /// call completes, so it's only cleared on entry if something else left
+    /// values on it.
+    pub(crate) fn call_prepared(
+        &mut self,
+        offset: usize,
+        expected: usize,
+        args: impl Args,
+    ) -> Result<Value, VmError> {
+        check_args(args.count(), expected)?;
+
+        if self.stack.len() != 0 {
+            self.stack.clear();
+        }
+
+        self.ip = offset;
+        self.call_frames.clear();
+        self.live_elements = 0;
+
+        let vm = ClearStack(self);
+        args.into_stack(&mut vm.0.stack)?;
+        VmExecution::new(&mut *vm.0).complete()
+    }
 
+    /// Run the function at the given offset to completion, using the given
+    /// arguments followed by `extra` arguments.
+    ///
+    /// The caller is responsible for checking the number of arguments.
+    pub(crate) fn call_offset(
+        &mut self,
+        offset: usize,
+        args: impl GuardedArgs,
+        extra: impl Args,
+    ) -> Result<Value, VmError> {
+        self.enter_entrypoint(offset);
+        self.complete_guarded(args, extra)
+    }
+
+    /// Push the given arguments followed by any `extra` arguments onto the
+    /// stack and run the virtual machine to completion from the current
+    /// entrypoint.
+    fn complete_guarded(
+        &mut self,
+        args: impl GuardedArgs,
+        extra: impl Args,
+    ) -> Result<Value, VmError> {
         // Safety: We hold onto the guard until the vm has completed and
         // `VmExecution` will clear the stack before this function returns.
         // Erronously or not.
         let guard = unsafe { args.guarded_into_stack(&mut self.stack)? };
+        extra.into_stack(&mut self.stack)?;
 
         let value = {
             // Clearing the stack here on panics has safety implications - see
@@ -500,9 +1921,93 @@ impl Vm {
         value
     }
 
+    /// Call the given async function immediately, and drive the future it
+    /// produces to completion on the current thread.
+    ///
+    /// This allows for calling async functions without an async runtime,
+    /// which is sufficient as long as they don't await anything which
+    /// doesn't complete synchronously. If they do, an error is raised instead
+    /// of blocking indefinitely.
+    ///
+    /// This function permits for using references since it doesn't defer its
+    /// execution.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the function awaits something which is pending on something
+    /// other than itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::sync::Arc;
+    /// use rune::Vm;
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         async fn add(a, b) {
+    ///             a + b
+    ///         }
+    ///
+    ///         pub async fn main(a, b) {
+    ///             add(a, b).await
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let unit = Arc::try_new(unit)?;
+    /// let mut vm = Vm::without_runtime(unit)?;
+    ///
+    /// let output = vm.block_on_call(["main"], (1i64, 2i64))?;
+    /// let output: i64 = rune::from_value(output)?;
+    /// assert_eq!(output, 3);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn block_on_call(
+        &mut self,
+        name: impl ToTypeHash,
+        args: impl GuardedArgs,
+    ) -> Result<Value, VmError> {
+        self.set_entrypoint(name, args.count())?;
+
+        // Safety: We hold onto the guard until the future has completed and
+        // `VmExecution` will clear the stack before this function returns.
+        // Erronously or not.
+        let guard = unsafe { args.guarded_into_stack(&mut self.stack)? };
+
+        let value = {
+            // Clearing the stack here on panics has safety implications - see
+            // above.
+            let vm = ClearStack(self);
+            let mut execution = VmExecution::new(&mut *vm.0);
+            block_on(execution.resume())
+                .map_err(VmError::new)
+                .and_then(|outcome| outcome?.into_complete())
+        };
+
+        // Note: this might panic if something in the vm is holding on to a
+        // reference of the value. We should prevent it from being possible to
+        // take any owned references to values held by this.
+        drop(guard);
+        value
+    }
+
     /// Update the instruction pointer to match the function matching the given
     /// name and check that the number of argument matches.
     fn set_entrypoint<N>(&mut self, name: N, count: usize) -> Result<(), VmErrorKind>
+    where
+        N: ToTypeHash,
+    {
+        let (_, offset, expected) = self.lookup_entrypoint(name)?;
+        check_args(count, expected)?;
+        self.enter_entrypoint(offset);
+        Ok(())
+    }
+
+    /// Look up the function matching the given name, returning its hash,
+    /// offset and the number of arguments it expects.
+    fn lookup_entrypoint<N>(&self, name: N) -> Result<(Hash, usize, usize), VmErrorKind>
     where
         N: ToTypeHash,
     {
@@ -516,26 +2021,21 @@ impl Vm {
             });
         };
 
-        let offset = match info {
+        match info {
             // NB: we ignore the calling convention.
             // everything is just async when called externally.
-            UnitFn::Offset {
-                offset,
-                args: expected,
-                ..
-            } => {
-                check_args(count, *expected)?;
-                *offset
-            }
-            _ => {
-                return Err(VmErrorKind::MissingFunction { hash });
-            }
-        };
+            UnitFn::Offset { offset, args, .. } => Ok((hash, *offset, *args)),
+            _ => Err(VmErrorKind::MissingFunction { hash }),
+        }
+    }
 
+    /// Reset the virtual machine so that execution starts at the given offset.
+    #[inline]
+    fn enter_entrypoint(&mut self, offset: usize) {
         self.ip = offset;
         self.stack.clear();
         self.call_frames.clear();
-        Ok(())
+        self.live_elements = 0;
     }
 
     /// Helper function to call an instance function.
@@ -584,14 +2084,188 @@ impl Vm {
         self.call_hash_with(Isolated::None, hash, target, args, count, out)
     }
 
-    fn called_function_hook(&self, hash: Hash) -> Result<(), VmError> {
-        runtime::env::exclusive(|_, _, diagnostics| {
-            if let Some(diagnostics) = diagnostics {
-                diagnostics.function_used(hash, self.ip())?;
-            }
+    /// Consult the missing function handler, returning `true` if it handled
+    /// the call.
+    fn missing_function_hook(
+        &mut self,
+        hash: Hash,
+        addr: Address,
+        args: usize,
+        out: Output,
+    ) -> Result<bool, VmError> {
+        let Some(MissingFunctionHandler(handler)) = &mut self.missing_function_handler else {
+            return Ok(false);
+        };
+
+        handler(hash, &mut self.stack, addr, args, out)
+    }
+
+    /// Call the mock registered for the given function, returning `true` if
+    /// there was one.
+    fn call_mock_hook(
+        &mut self,
+        hash: Hash,
+        addr: Address,
+        args: usize,
+        out: Output,
+    ) -> Result<bool, VmError> {
+        let Ok(index) = self.call_mocks.binary_search_by(|e| e.0.cmp(&hash)) else {
+            return Ok(false);
+        };
+
+        let CallMock(mock) = &mut self.call_mocks[index].1;
+        mock(&mut self.stack, addr, args, out)?;
+        Ok(true)
+    }
+
+    /// Call the mock registered for the function at the given offset,
+    /// returning `true` if there was one.
+    ///
+    /// Calls to functions in the unit which have already been compiled are
+    /// encoded as direct calls to their offset, so each mocked function is
+    /// looked up in the unit to find the one at the given offset.
+    fn call_offset_mock_hook(
+        &mut self,
+        offset: usize,
+        addr: Address,
+        args: usize,
+        out: Output,
+    ) -> Result<bool, VmError> {
+        let unit = &self.unit;
+
+        let Some(index) = self.call_mocks.iter().position(|(hash, _)| {
+            matches!(
+                unit.function(hash),
+                Some(UnitFn::Offset { offset: o, .. }) if *o == offset
+            )
+        }) else {
+            return Ok(false);
+        };
+
+        let CallMock(mock) = &mut self.call_mocks[index].1;
+        mock(&mut self.stack, addr, args, out)?;
+        Ok(true)
+    }
+
+    /// Report that an instruction fell back to calling a protocol function.
+    fn protocol_fallback_hook(&self, op: ProtocolFallback) -> Result<(), VmError> {
+        if !self.has_diagnostics {
+            return Ok(());
+        }
+
+        runtime::env::exclusive(|_, _, diagnostics| {
+            if let Some(diagnostics) = diagnostics {
+                diagnostics.protocol_fallback(op)?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Consult diagnostics for what to do when the budget has been exhausted.
+    fn budget_exhausted_hook(&self, instructions_run: usize) -> Result<BudgetAction, VmError> {
+        if !self.has_diagnostics {
+            return Ok(BudgetAction::Halt);
+        }
+
+        runtime::env::exclusive(|_, _, diagnostics| {
+            let Some(diagnostics) = diagnostics else {
+                return Ok(BudgetAction::Halt);
+            };
+
+            Ok(diagnostics.budget_exhausted(instructions_run))
+        })
+    }
+
+    /// Report a call to the function identified by `hash` to diagnostics,
+    /// recording it both as used and as called at the current instruction.
+    fn called_function_hook(&self, hash: Hash) -> Result<(), VmError> {
+        if !self.has_diagnostics {
+            return Ok(());
+        }
+
+        runtime::env::exclusive(|_, _, diagnostics| {
+            if let Some(diagnostics) = diagnostics {
+                diagnostics.function_used(hash, self.ip())?;
+                diagnostics.function_called(hash, self.ip())?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Report a direct function call to diagnostics, resolving the hash of
+    /// the called function only if diagnostics are present.
+    fn direct_call_hook(&self, hash: impl FnOnce() -> Option<Hash>) -> Result<(), VmError> {
+        if !self.has_diagnostics {
+            return Ok(());
+        }
+
+        runtime::env::exclusive(|_, _, diagnostics| {
+            if let Some(diagnostics) = diagnostics {
+                if let Some(hash) = hash() {
+                    diagnostics.function_called(hash, self.ip())?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Report the outcome of a pattern match to diagnostics.
+    fn match_hook(&self, matched: bool) -> Result<(), VmError> {
+        if !self.has_diagnostics {
+            return Ok(());
+        }
+
+        runtime::env::exclusive(|_, _, diagnostics| {
+            if let Some(diagnostics) = diagnostics {
+                diagnostics.match_evaluated(self.last_ip(), matched)?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Report the values dropped by a drop set to diagnostics.
+    fn drop_hook(&self, addresses: &[Address]) -> Result<(), VmError> {
+        if !self.has_diagnostics {
+            return Ok(());
+        }
+
+        runtime::env::exclusive(|_, _, diagnostics| {
+            if let Some(diagnostics) = diagnostics {
+                for &addr in addresses {
+                    diagnostics.value_dropped(addr)?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Call a native function which has been resolved ahead of time through
+    /// [`ProtocolCaller::resolve_protocol_fn`], in the same way as if it was
+    /// looked up by [`Vm::call_instance_fn`].
+    pub(crate) fn call_resolved_fn(
+        &mut self,
+        protocol_fn: &ProtocolFn,
+        target: Value,
+        args: &mut dyn DynArgs,
+        out: Output,
+    ) -> Result<(), VmError> {
+        let count = args.count().wrapping_add(1);
+        let addr = self.stack.addr();
+
+        self.called_function_hook(protocol_fn.hash())?;
+        self.stack.push(target)?;
+        args.push_to_stack(&mut self.stack)?;
 
-            Ok(())
-        })
+        let result = protocol_fn
+            .handler()
+            .call(&mut self.stack, addr, count, out);
+        self.stack.truncate(addr);
+        result
     }
 
     #[inline(never)]
@@ -688,12 +2362,16 @@ impl Vm {
     ) -> Result<(), VmErrorKind> {
         tracing::trace!("pushing call frame");
 
+        #[cfg(debug_assertions)]
+        let size = self.stack.size();
         let top = self.stack.swap_top(addr, args)?;
         let ip = replace(&mut self.ip, ip);
 
         let frame = CallFrame {
             ip,
             top,
+            #[cfg(debug_assertions)]
+            size,
             isolated,
             out,
         };
@@ -711,6 +2389,10 @@ impl Vm {
         let frame = self.call_frames.pop()?;
         tracing::trace!(?frame);
         self.stack.pop_stack_top(frame.top);
+
+        #[cfg(debug_assertions)]
+        self.stack.set_size(frame.size);
+
         Some(replace(&mut self.ip, frame.ip))
     }
 
@@ -726,6 +2408,10 @@ impl Vm {
 
         tracing::trace!(?frame);
         self.stack.pop_stack_top(frame.top);
+
+        #[cfg(debug_assertions)]
+        self.stack.set_size(frame.size);
+
         self.ip = frame.ip;
         (frame.isolated, Some(frame.out))
     }
@@ -1124,9 +2810,12 @@ impl Vm {
     #[cfg_attr(feature = "bench", inline(never))]
     fn target_fallback_assign(
         &mut self,
+        op: ProtocolFallback,
         fallback: TargetFallback,
         protocol: &Protocol,
     ) -> Result<(), VmError> {
+        self.protocol_fallback_hook(op)?;
+
         match fallback {
             TargetFallback::Value(lhs, rhs) => {
                 let mut args = DynGuardedArgs::new((rhs.clone(),));
@@ -1182,6 +2871,26 @@ impl Vm {
         Ok(())
     }
 
+    /// Yield the value at the given address, or the unit value if `None`.
+    ///
+    /// Returns the halt to yield with unless a yield handler is set, in which
+    /// case its return value is stored in `out`.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_yield(&mut self, addr: Option<Address>, out: Output) -> Result<Option<VmHalt>, VmError> {
+        let Some(YieldHandler(handler)) = &mut self.yield_handler else {
+            return Ok(Some(VmHalt::Yielded(addr, out)));
+        };
+
+        let value = match addr {
+            Some(addr) => Some(self.stack.at(addr).clone()),
+            None => None,
+        };
+
+        let value = handler(value)?;
+        self.stack.store(out, value)?;
+        Ok(None)
+    }
+
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_await(&mut self, addr: Address) -> Result<Future, VmError> {
         Ok(self.stack.at(addr).clone().into_future()?)
@@ -1194,6 +2903,10 @@ impl Vm {
         len: usize,
         value: Output,
     ) -> Result<Option<Select>, VmError> {
+        if let Some(branch) = self.replay.next_select() {
+            return self.op_select_replay(addr, len, value, branch?);
+        }
+
         let futures = futures_util::stream::FuturesUnordered::new();
 
         for (branch, value) in self.stack.slice_at(addr, len)?.iter().enumerate() {
@@ -1205,12 +2918,52 @@ impl Vm {
         }
 
         if futures.is_empty() {
+            self.replay.record_select(None)?;
+            self.stack.store(value, ())?;
+            self.ip = self.ip.wrapping_add(len);
+            return Ok(None);
+        }
+
+        Ok(Some(Select::new(self.ip, futures)))
+    }
+
+    /// Perform a select which is forced to take the given replayed branch.
+    fn op_select_replay(
+        &mut self,
+        addr: Address,
+        len: usize,
+        value: Output,
+        branch: Option<usize>,
+    ) -> Result<Option<Select>, VmError> {
+        let futures = self.stack.slice_at(addr, len)?;
+
+        let Some(branch) = branch else {
+            // NB: The default branch is only taken if every future has
+            // completed.
+            for future in futures {
+                if !future.borrow_ref::<Future>()?.is_completed() {
+                    return Err(VmError::new(self.replay.diverged()));
+                }
+            }
+
             self.stack.store(value, ())?;
             self.ip = self.ip.wrapping_add(len);
             return Ok(None);
+        };
+
+        let Some(future) = futures.get(branch) else {
+            return Err(VmError::new(self.replay.diverged()));
+        };
+
+        let future = future.clone().into_mut::<Future>()?;
+
+        if future.is_completed() {
+            return Err(VmError::new(self.replay.diverged()));
         }
 
-        Ok(Some(Select::new(futures)))
+        let futures = futures_util::stream::FuturesUnordered::new();
+        futures.push(SelectFuture::new(self.ip + branch, future));
+        Ok(Some(Select::new(self.ip, futures)))
     }
 
     #[cfg_attr(feature = "bench", inline(never))]
@@ -1247,6 +3000,7 @@ impl Vm {
             *self.stack.at_mut(addr)? = Value::empty();
         }
 
+        self.drop_hook(addresses)?;
         Ok(())
     }
 
@@ -1268,10 +3022,7 @@ impl Vm {
     #[cfg_attr(feature = "bench", inline(never))]
     #[cfg_attr(not(feature = "bench"), inline)]
     fn op_jump_if(&mut self, cond: Address, jump: usize) -> Result<(), VmErrorKind> {
-        if matches!(
-            self.stack.at(cond).as_ref(),
-            Repr::Inline(Inline::Bool(true))
-        ) {
+        if self.condition(cond)? == Some(true) {
             self.ip = self.unit.translate(jump)?;
         }
 
@@ -1281,19 +3032,35 @@ impl Vm {
     /// pop-and-jump-if-not instruction.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_jump_if_not(&mut self, cond: Address, jump: usize) -> Result<(), VmErrorKind> {
-        if matches!(
-            self.stack.at(cond).as_ref(),
-            Repr::Inline(Inline::Bool(false))
-        ) {
+        if self.condition(cond)? == Some(false) {
             self.ip = self.unit.translate(jump)?;
         }
 
         Ok(())
     }
 
+    /// Read the condition of a conditional jump, which is `None` if it's not
+    /// a boolean.
+    ///
+    /// In strict conditions mode, a condition which is not a boolean errors.
+    #[inline]
+    fn condition(&self, cond: Address) -> Result<Option<bool>, VmErrorKind> {
+        let value = self.stack.at(cond);
+
+        match value.as_ref() {
+            Repr::Inline(Inline::Bool(value)) => Ok(Some(*value)),
+            _ if self.strict_conditions => Err(VmErrorKind::ExpectedBoolCondition {
+                actual: value.type_info(),
+            }),
+            _ => Ok(None),
+        }
+    }
+
     /// Construct a new vec.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_vec(&mut self, addr: Address, count: usize, out: Output) -> Result<(), VmError> {
+        self.check_values_allocation(count)?;
+        self.count_elements(count)?;
         let vec = self.stack.slice_at_mut(addr, count)?;
         let vec = vec
             .iter_mut()
@@ -1306,6 +3073,8 @@ impl Vm {
     /// Construct a new tuple.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_tuple(&mut self, addr: Address, count: usize, out: Output) -> Result<(), VmError> {
+        self.check_values_allocation(count)?;
+        self.count_elements(count)?;
         let tuple = self.stack.slice_at_mut(addr, count)?;
 
         let tuple = tuple
@@ -1320,6 +3089,7 @@ impl Vm {
     /// Construct a new tuple with a fixed number of arguments.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_tuple_n(&mut self, addr: &[Address], out: Output) -> Result<(), VmError> {
+        self.count_elements(addr.len())?;
         let mut tuple = alloc::Vec::<Value>::try_with_capacity(addr.len())?;
 
         for &arg in addr {
@@ -1357,31 +3127,45 @@ impl Vm {
 
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_allocate(&mut self, size: usize) -> Result<(), VmError> {
+        self.check_values_allocation(size)?;
         self.stack.resize(size)?;
         Ok(())
     }
 
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_not(&mut self, addr: Address, out: Output) -> Result<(), VmError> {
-        self.unary(addr, out, &Protocol::NOT, |inline| match *inline {
-            Inline::Bool(value) => Some(Inline::Bool(!value)),
-            Inline::Unsigned(value) => Some(Inline::Unsigned(!value)),
-            Inline::Signed(value) => Some(Inline::Signed(!value)),
-            _ => None,
-        })
+        self.unary(
+            ProtocolFallback::Not,
+            addr,
+            out,
+            &Protocol::NOT,
+            |inline| match *inline {
+                Inline::Bool(value) => Some(Inline::Bool(!value)),
+                Inline::Unsigned(value) => Some(Inline::Unsigned(!value)),
+                Inline::Signed(value) => Some(Inline::Signed(!value)),
+                _ => None,
+            },
+        )
     }
 
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_neg(&mut self, addr: Address, out: Output) -> Result<(), VmError> {
-        self.unary(addr, out, &Protocol::NEG, |inline| match *inline {
-            Inline::Signed(value) => Some(Inline::Signed(-value)),
-            Inline::Float(value) => Some(Inline::Float(-value)),
-            _ => None,
-        })
+        self.unary(
+            ProtocolFallback::Neg,
+            addr,
+            out,
+            &Protocol::NEG,
+            |inline| match *inline {
+                Inline::Signed(value) => Some(Inline::Signed(-value)),
+                Inline::Float(value) => Some(Inline::Float(-value)),
+                _ => None,
+            },
+        )
     }
 
     fn unary(
         &mut self,
+        fallback: ProtocolFallback,
         operand: Address,
         out: Output,
         protocol: &'static Protocol,
@@ -1408,6 +3192,7 @@ impl Vm {
         };
 
         let operand = operand.clone();
+        self.protocol_fallback_hook(fallback)?;
 
         if let CallResult::Unsupported(operand) =
             self.call_instance_fn(Isolated::None, operand, protocol, &mut (), out)?
@@ -1511,7 +3296,8 @@ impl Vm {
         rhs: Address,
         out: Output,
     ) -> Result<(), VmError> {
-        let ops = ArithmeticOps::from_op(op);
+        let ops = ArithmeticOps::from_op(op, self.overflow_mode);
+        let div_by_zero = self.div_by_zero;
 
         let lhs = self.stack.at(lhs);
         let rhs = self.stack.at(rhs);
@@ -1521,12 +3307,16 @@ impl Vm {
                 (Repr::Inline(lhs), Repr::Inline(rhs)) => match (lhs, rhs) {
                     (Inline::Unsigned(lhs), rhs) => {
                         let rhs = rhs.as_integer()?;
-                        let value = (ops.u64)(*lhs, rhs).ok_or_else(ops.error)?;
+                        let value = (ops.u64)(*lhs, rhs)
+                            .or_else(|| div_by_zero.unsigned(op, *lhs, rhs))
+                            .ok_or_else(ops.error)?;
                         Inline::Unsigned(value)
                     }
                     (Inline::Signed(lhs), rhs) => {
                         let rhs = rhs.as_integer()?;
-                        let value = (ops.i64)(*lhs, rhs).ok_or_else(ops.error)?;
+                        let value = (ops.i64)(*lhs, rhs)
+                            .or_else(|| div_by_zero.signed(op, *lhs, rhs))
+                            .ok_or_else(ops.error)?;
                         Inline::Signed(value)
                     }
                     (Inline::Float(lhs), Inline::Float(rhs)) => {
@@ -1559,6 +3349,7 @@ impl Vm {
 
         let lhs = lhs.clone();
         let rhs = rhs.clone();
+        self.protocol_fallback_hook(ProtocolFallback::Arithmetic)?;
 
         let mut args = DynGuardedArgs::new((rhs.clone(),));
 
@@ -1622,6 +3413,7 @@ impl Vm {
 
         let lhs = lhs.clone();
         let rhs = rhs.clone();
+        self.protocol_fallback_hook(ProtocolFallback::Bitwise)?;
 
         let mut args = DynGuardedArgs::new((&rhs,));
 
@@ -1700,6 +3492,8 @@ impl Vm {
             return Ok(());
         };
 
+        self.protocol_fallback_hook(ProtocolFallback::Shift)?;
+
         let mut args = DynGuardedArgs::new((rhs.clone(),));
 
         if let CallResult::Unsupported(lhs) =
@@ -1722,17 +3516,23 @@ impl Vm {
         target: InstTarget,
         rhs: Address,
     ) -> Result<(), VmError> {
-        let ops = AssignArithmeticOps::from_op(op);
+        self.check_read_only_target(target)?;
+        let ops = AssignArithmeticOps::from_op(op, self.overflow_mode);
+        let div_by_zero = self.div_by_zero;
 
         let fallback = match target_value(&mut self.stack, &self.unit, target, rhs)? {
             TargetValue::Same(value) => match value.as_mut() {
                 Repr::Inline(Inline::Signed(value)) => {
-                    let out = (ops.i64)(*value, *value).ok_or_else(ops.error)?;
+                    let out = (ops.i64)(*value, *value)
+                        .or_else(|| div_by_zero.signed(op, *value, *value))
+                        .ok_or_else(ops.error)?;
                     *value = out;
                     return Ok(());
                 }
                 Repr::Inline(Inline::Unsigned(value)) => {
-                    let out = (ops.u64)(*value, *value).ok_or_else(ops.error)?;
+                    let out = (ops.u64)(*value, *value)
+                        .or_else(|| div_by_zero.unsigned(op, *value, *value))
+                        .ok_or_else(ops.error)?;
                     *value = out;
                     return Ok(());
                 }
@@ -1753,13 +3553,17 @@ impl Vm {
             TargetValue::Pair(mut lhs, rhs) => match (lhs.as_mut(), rhs.as_ref()) {
                 (Repr::Inline(Inline::Signed(lhs)), Repr::Inline(rhs)) => {
                     let rhs = rhs.as_integer()?;
-                    let out = (ops.i64)(*lhs, rhs).ok_or_else(ops.error)?;
+                    let out = (ops.i64)(*lhs, rhs)
+                        .or_else(|| div_by_zero.signed(op, *lhs, rhs))
+                        .ok_or_else(ops.error)?;
                     *lhs = out;
                     return Ok(());
                 }
                 (Repr::Inline(Inline::Unsigned(lhs)), Repr::Inline(rhs)) => {
                     let rhs = rhs.as_integer()?;
-                    let out = (ops.u64)(*lhs, rhs).ok_or_else(ops.error)?;
+                    let out = (ops.u64)(*lhs, rhs)
+                        .or_else(|| div_by_zero.unsigned(op, *lhs, rhs))
+                        .ok_or_else(ops.error)?;
                     *lhs = out;
                     return Ok(());
                 }
@@ -1780,7 +3584,7 @@ impl Vm {
             TargetValue::Fallback(fallback) => fallback,
         };
 
-        self.target_fallback_assign(fallback, &ops.protocol)
+        self.target_fallback_assign(ProtocolFallback::AssignArithmetic, fallback, &ops.protocol)
     }
 
     #[cfg_attr(feature = "bench", inline(never))]
@@ -1790,6 +3594,7 @@ impl Vm {
         target: InstTarget,
         rhs: Address,
     ) -> Result<(), VmError> {
+        self.check_read_only_target(target)?;
         let ops = AssignBitwiseOps::from_ops(op);
 
         let fallback = match target_value(&mut self.stack, &self.unit, target, rhs)? {
@@ -1845,7 +3650,7 @@ impl Vm {
             TargetValue::Fallback(fallback) => fallback,
         };
 
-        self.target_fallback_assign(fallback, &ops.protocol)
+        self.target_fallback_assign(ProtocolFallback::AssignBitwise, fallback, &ops.protocol)
     }
 
     #[cfg_attr(feature = "bench", inline(never))]
@@ -1855,6 +3660,7 @@ impl Vm {
         target: InstTarget,
         rhs: Address,
     ) -> Result<(), VmError> {
+        self.check_read_only_target(target)?;
         let ops = AssignShiftOps::from_op(op);
 
         let fallback = match target_value(&mut self.stack, &self.unit, target, rhs)? {
@@ -1905,7 +3711,7 @@ impl Vm {
             TargetValue::Fallback(fallback) => fallback,
         };
 
-        self.target_fallback_assign(fallback, &ops.protocol)
+        self.target_fallback_assign(ProtocolFallback::AssignShift, fallback, &ops.protocol)
     }
 
     /// Perform an index set operation.
@@ -1917,6 +3723,8 @@ impl Vm {
         value: Address,
     ) -> Result<(), VmError> {
         let target = self.stack.at(target);
+        self.check_read_only(target)?;
+
         let index = self.stack.at(index);
         let value = self.stack.at(value);
 
@@ -1930,6 +3738,8 @@ impl Vm {
         let index = index.clone();
         let value = value.clone();
 
+        self.protocol_fallback_hook(ProtocolFallback::IndexSet)?;
+
         let mut args = DynGuardedArgs::new((&index, &value));
 
         if let CallResult::Unsupported(target) = self.call_instance_fn(
@@ -1952,6 +3762,7 @@ impl Vm {
     #[inline]
     #[tracing::instrument(skip(self, return_value))]
     fn op_return_internal(&mut self, return_value: Value) -> Result<Option<Output>, VmError> {
+        self.capture_residual()?;
         let (exit, out) = self.pop_call_frame();
 
         let out = if let Some(out) = out {
@@ -1966,6 +3777,22 @@ impl Vm {
         Ok(exit.then_some(out))
     }
 
+    /// Take the values left beyond the size of the frame being returned from
+    /// if they are being captured.
+    ///
+    /// Frame sizes are only tracked in debug builds, so this does nothing in
+    /// release builds.
+    fn capture_residual(&mut self) -> alloc::Result<()> {
+        #[cfg(debug_assertions)]
+        if let Some(residual) = &mut self.residual {
+            for value in self.stack.drain_residual() {
+                residual.try_push(value)?;
+            }
+        }
+
+        Ok(())
+    }
+
     fn lookup_function_by_hash(&self, hash: Hash) -> Result<Function, VmErrorKind> {
         let Some(info) = self.unit.function(&hash) else {
             let Some(handler) = self.context.function(&hash) else {
@@ -2014,6 +3841,7 @@ impl Vm {
     #[cfg_attr(feature = "bench", inline(never))]
     #[tracing::instrument(skip(self))]
     fn op_return_unit(&mut self) -> Result<Option<Output>, VmError> {
+        self.capture_residual()?;
         let (exit, out) = self.pop_call_frame();
 
         let out = if let Some(out) = out {
@@ -2077,6 +3905,8 @@ impl Vm {
             let target = target.clone();
             let index = index.clone();
 
+            self.protocol_fallback_hook(ProtocolFallback::IndexGet)?;
+
             let mut args = DynGuardedArgs::new((&index,));
 
             if let CallResult::Unsupported(target) =
@@ -2105,6 +3935,7 @@ impl Vm {
     ) -> Result<(), VmError> {
         let value = self.stack.at(value);
         let target = self.stack.at(target);
+        self.check_read_only(target)?;
 
         if Self::try_tuple_like_index_set(target, index, value)? {
             return Ok(());
@@ -2131,6 +3962,7 @@ impl Vm {
         }
 
         let value = value.clone();
+        self.protocol_fallback_hook(ProtocolFallback::TupleIndexGetAt)?;
 
         if let CallResult::Unsupported(value) =
             self.call_index_fn(&Protocol::GET, value, index, &mut (), out)?
@@ -2153,6 +3985,8 @@ impl Vm {
         value: Address,
     ) -> Result<(), VmError> {
         let target = self.stack.at(target);
+        self.check_read_only(target)?;
+
         let value = self.stack.at(value);
 
         let Some(field) = self.unit.lookup_string(slot) else {
@@ -2167,6 +4001,7 @@ impl Vm {
         let value = value.clone();
 
         let hash = field.hash();
+        self.protocol_fallback_hook(ProtocolFallback::ObjectIndexSet)?;
 
         let mut args = DynGuardedArgs::new((value,));
 
@@ -2232,6 +4067,7 @@ impl Vm {
         }
 
         let target = target.clone();
+        self.protocol_fallback_hook(ProtocolFallback::ObjectIndexGetAt)?;
 
         if let CallResult::Unsupported(target) =
             self.call_field_fn(&Protocol::GET, target, index.hash(), &mut (), out)?
@@ -2240,6 +4076,20 @@ impl Vm {
                 return Err(VmError::new(VmErrorKind::MissingStaticString { slot }));
             };
 
+            let type_hash = target.type_hash();
+
+            if let Ok(index) = self
+                .dynamic_field_handlers
+                .binary_search_by(|e| e.0.cmp(&type_hash))
+            {
+                let DynamicFieldHandler(handler) = &mut self.dynamic_field_handlers[index].1;
+
+                if let Some(value) = handler(&target, field.as_str())? {
+                    self.stack.store(out, value)?;
+                    return Ok(());
+                }
+            }
+
             return Err(VmError::new(VmErrorKind::UnsupportedObjectSlotIndexGet {
                 target: target.type_info(),
                 field: field.clone(),
@@ -2252,11 +4102,22 @@ impl Vm {
     /// Operation to allocate an object.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_object(&mut self, addr: Address, slot: usize, out: Output) -> Result<(), VmError> {
+        let Some(len) = self.unit.lookup_object_keys(slot).map(|keys| keys.len()) else {
+            return Err(VmError::new(VmErrorKind::MissingStaticObjectKeys { slot }));
+        };
+
+        self.count_elements(len)?;
+
         let Some(keys) = self.unit.lookup_object_keys(slot) else {
             return Err(VmError::new(VmErrorKind::MissingStaticObjectKeys { slot }));
         };
 
-        let mut object = Object::with_capacity(keys.len())?;
+        let mut object = if self.object_insertion_order {
+            Object::with_insertion_order(keys.len())?
+        } else {
+            Object::with_capacity(keys.len())?
+        };
+
         let values = self.stack.slice_at_mut(addr, keys.len())?;
 
         for (key, value) in keys.iter().zip(values) {
@@ -2368,16 +4229,23 @@ impl Vm {
         let values = self.stack.slice_at(addr, len)?;
         let values = values.iter().cloned().try_collect::<alloc::Vec<_>>()?;
 
-        let mut s = String::try_with_capacity(size_hint)?;
+        let reserve = self.string_concat_reserve.saturating_mul(len);
+        let reserve = size_hint.saturating_add(reserve);
+        self.check_allocation(reserve)?;
 
-        Formatter::format_with(&mut s, |f| {
-            for value in values {
-                value.display_fmt_with(f, &mut *self)?;
-            }
+        // NB: The size hint covers the literal parts of the string, which are
+        // always included.
+        self.check_string_len(size_hint)?;
 
-            Ok::<_, VmError>(())
-        })?;
+        let mut s = String::try_with_capacity(reserve)?;
+
+        for value in values {
+            Formatter::format_with(&mut s, |f| value.display_fmt_with(f, &mut *self))?;
+            self.check_string_len(s.len())?;
+        }
 
+        // NB: Formatting might have grown the string beyond its reservation.
+        self.check_allocation(s.capacity())?;
         self.stack.store(out, s)?;
         Ok(())
     }
@@ -2582,6 +4450,7 @@ impl Vm {
             }
         };
 
+        self.match_hook(is_match)?;
         self.stack.store(out, is_match)?;
         Ok(())
     }
@@ -2617,6 +4486,7 @@ impl Vm {
             actual >= len && (!exact || actual == len)
         };
 
+        self.match_hook(is_match)?;
         self.stack.store(out, is_match)?;
         Ok(())
     }
@@ -2651,6 +4521,7 @@ impl Vm {
             true
         };
 
+        self.match_hook(is_match)?;
         self.stack.store(out, is_match)?;
         Ok(())
     }
@@ -2719,15 +4590,26 @@ impl Vm {
         args: usize,
         out: Output,
     ) -> Result<(), VmError> {
+        if self.call_mock_hook(hash, addr, args, out)? {
+            return Ok(());
+        }
+
         let Some(info) = self.unit.function(&hash) else {
             let Some(handler) = self.context.function(&hash) else {
+                if self.missing_function_hook(hash, addr, args, out)? {
+                    return Ok(());
+                }
+
                 return Err(VmError::new(VmErrorKind::MissingFunction { hash }));
             };
 
+            self.direct_call_hook(|| Some(hash))?;
             handler.call(&mut self.stack, addr, args, out)?;
             return Ok(());
         };
 
+        self.direct_call_hook(|| Some(hash))?;
+
         match info {
             UnitFn::Offset {
                 offset,
@@ -2778,6 +4660,15 @@ impl Vm {
         args: usize,
         out: Output,
     ) -> Result<(), VmError> {
+        if self.call_offset_mock_hook(offset, addr, args, out)? {
+            return Ok(());
+        }
+
+        self.direct_call_hook(|| {
+            let debug = self.unit.debug_info()?;
+            debug.functions_rev.get(&offset).copied()
+        })?;
+
         self.call_offset_fn(offset, call, addr, args, Isolated::None, out)?;
         Ok(())
     }
@@ -2790,9 +4681,9 @@ impl Vm {
         args: usize,
         out: Output,
     ) -> Result<(), VmError> {
-        let instance = self.stack.at(addr);
-        let type_hash = instance.type_hash();
-        let hash = Hash::associated_function(type_hash, hash);
+        let type_hash = self.stack.at(addr).type_hash();
+        let name = hash;
+        let hash = Hash::associated_function(type_hash, name);
 
         if let Some(handler) = self.context.function(&hash) {
             self.called_function_hook(hash)?;
@@ -2813,8 +4704,12 @@ impl Vm {
             return Ok(());
         }
 
+        if self.missing_function_hook(name, addr, args, out)? {
+            return Ok(());
+        }
+
         Err(VmError::new(VmErrorKind::MissingInstanceFunction {
-            instance: instance.type_info(),
+            instance: self.stack.at(addr).type_info(),
             hash,
         }))
     }
@@ -2838,6 +4733,12 @@ impl Vm {
             Repr::Any(value) if value.type_hash() == Function::HASH => {
                 let value = value.clone();
                 let f = value.borrow_ref::<Function>()?;
+
+                if self.call_mock_hook(f.type_hash(), addr, args, out)? {
+                    return Ok(None);
+                }
+
+                self.direct_call_hook(|| Some(f.type_hash()))?;
                 f.call_with_vm(self, addr, args, out)
             }
             value => Err(VmError::new(VmErrorKind::UnsupportedCallFn {
@@ -2901,13 +4802,192 @@ impl Vm {
         f()
     }
 
+    /// Get a clone of the element at `index` in the given vector, or `None`
+    /// if the index is out of bounds.
+    ///
+    /// This accesses the vector directly without going through the
+    /// [`INDEX_GET`] protocol, which makes it a cheap way for hosts to
+    /// iterate over vectors produced by scripts. Errors if the value is not a
+    /// vector or if it's already mutably borrowed.
+    ///
+    /// [`INDEX_GET`]: Protocol::INDEX_GET
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::sync::Arc;
+    /// use rune::{Context, Vm};
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() {
+    ///             [1, 2, 3]
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::try_new(context.runtime()?)?;
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let unit = Arc::try_new(unit)?;
+    ///
+    /// let mut vm = Vm::new(runtime, unit);
+    /// let vec = vm.call(["main"], ())?;
+    ///
+    /// let mut sum = 0;
+    /// let mut index = 0;
+    ///
+    /// while let Some(value) = vm.vec_get(&vec, index)? {
+    ///     sum += rune::from_value::<i64>(value)?;
+    ///     index += 1;
+    /// }
+    ///
+    /// assert_eq!(sum, 6);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn vec_get(&self, vec: &Value, index: usize) -> Result<Option<Value>, VmError> {
+        let vec = vec.borrow_ref::<runtime::Vec>()?;
+        Ok(vec.get(index).cloned())
+    }
+
+    /// Dump the values in the current stack frame for debugging.
+    ///
+    /// For each slot in the current stack frame this produces its address,
+    /// the type of the value stored in it, and the value formatted using the
+    /// [`DEBUG_FMT`] protocol.
+    ///
+    /// [`DEBUG_FMT`]: Protocol::DEBUG_FMT
+    pub fn dump_stack(&self) -> Result<alloc::Vec<(Address, TypeInfo, String)>, VmError> {
+        let len = self.stack.addr().offset();
+        let mut dump = alloc::Vec::try_with_capacity(len)?;
+
+        self.with(|| {
+            for offset in 0..len {
+                let addr = Address::new(offset);
+                let value = self.stack.at(addr);
+
+                let mut debug = String::new();
+                Formatter::format_with(&mut debug, |f| value.debug_fmt(f))?;
+                dump.try_push((addr, value.type_info(), debug))?;
+            }
+
+            Ok::<_, VmError>(())
+        })?;
+
+        Ok(dump)
+    }
+
+    /// Run the virtual machine until the instruction at `target_ip` is about
+    /// to be executed, returning [`VmBreak::Breakpoint`] without executing it.
+    ///
+    /// If the virtual machine exits or runs out of budget before reaching the
+    /// breakpoint, [`VmBreak::Exited`] or [`VmBreak::Limited`] is returned
+    /// instead. Any other kind of halt, such as yielding or awaiting, results
+    /// in an error since it can only be handled by a [`VmExecution`].
+    ///
+    /// If the current instruction pointer is already at `target_ip`, that
+    /// instruction is executed before the breakpoint is checked again. This
+    /// allows for repeatedly calling this function to step through each time
+    /// the breakpoint is hit, such as in a loop.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::ops::ControlFlow;
+    ///
+    /// use rune::runtime::VmBreak;
+    /// use rune::sync::Arc;
+    /// use rune::{Context, Vm};
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main(a, b) {
+    ///             a + b
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::try_new(context.runtime()?)?;
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let unit = Arc::try_new(unit)?;
+    ///
+    /// let mut vm = Vm::new(runtime, unit);
+    ///
+    /// let mut last = None;
+    ///
+    /// vm.visit_instructions(|ip, _| {
+    ///     last = Some(ip);
+    ///     ControlFlow::Continue(())
+    /// })?;
+    ///
+    /// let ip = last.expect("missing instructions");
+    ///
+    /// let mut execution = vm.execute(["main"], (1i64, 2i64))?;
+    ///
+    /// let vm = execution.vm_mut();
+    /// assert_eq!(vm.run_until(ip)?, VmBreak::Breakpoint(ip));
+    /// assert_eq!(vm.ip(), ip);
+    ///
+    /// let output: i64 = rune::from_value(execution.complete()?)?;
+    /// assert_eq!(output, 3);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn run_until(&mut self, target_ip: usize) -> Result<VmBreak, VmError> {
+        let halt = match self.run_with::<true>(None, target_ip)? {
+            VmHalt::Breakpoint(ip) => return Ok(VmBreak::Breakpoint(ip)),
+            VmHalt::Exited(addr) => return Ok(VmBreak::Exited(addr)),
+            VmHalt::Limited => return Ok(VmBreak::Limited),
+            VmHalt::Yielded(..) => VmHaltInfo::Yielded,
+            VmHalt::Awaited(..) => VmHaltInfo::Awaited,
+            VmHalt::VmCall(..) => VmHaltInfo::VmCall,
+        };
+
+        Err(VmError::new(VmErrorKind::Halted { halt }))
+    }
+
     /// Evaluate a single instruction.
     pub(crate) fn run(
         &mut self,
         diagnostics: Option<&mut dyn VmDiagnostics>,
+    ) -> Result<VmHalt, VmError> {
+        self.run_with::<false>(diagnostics, 0)
+    }
+
+    /// Run the virtual machine, where the breakpoint is only checked if
+    /// `BREAKPOINT` is set so that it costs nothing in a regular run.
+    fn run_with<const BREAKPOINT: bool>(
+        &mut self,
+        diagnostics: Option<&mut dyn VmDiagnostics>,
+        breakpoint: usize,
+    ) -> Result<VmHalt, VmError> {
+        let result = self.run_inner::<BREAKPOINT>(diagnostics, breakpoint);
+
+        // NB: The instruction pointer has already advanced past the faulting
+        // instruction, so it's recovered through the last instruction.
+        match &result {
+            Ok(..) => self.error_ip = None,
+            Err(..) => self.error_ip = Some(self.last_ip()),
+        }
+
+        if self.capture_backtrace {
+            return VmError::with_backtrace(result, self);
+        }
+
+        result
+    }
+
+    fn run_inner<const BREAKPOINT: bool>(
+        &mut self,
+        diagnostics: Option<&mut dyn VmDiagnostics>,
+        breakpoint: usize,
     ) -> Result<VmHalt, VmError> {
         let mut vm_diagnostics_obj;
 
+        self.has_diagnostics = diagnostics.is_some();
+
         let diagnostics = match diagnostics {
             Some(diagnostics) => {
                 vm_diagnostics_obj = VmDiagnosticsObj::new(diagnostics);
@@ -2922,9 +5002,34 @@ impl Vm {
 
         let mut budget = budget::acquire();
 
+        // Every permit granted to the budget has been used by the time it's
+        // exhausted, so this is the number of instructions run by then.
+        let mut granted = budget.remaining();
+
+        // The instruction at the breakpoint is executed if it is where we
+        // start, so that repeated runs make progress.
+        let mut first = true;
+
         loop {
+            if BREAKPOINT {
+                if !first && self.ip == breakpoint {
+                    return Ok(VmHalt::Breakpoint(breakpoint));
+                }
+
+                first = false;
+            }
+
             if !budget.take() {
-                return Ok(VmHalt::Limited);
+                let BudgetAction::Extend(extension) = self.budget_exhausted_hook(granted)? else {
+                    return Ok(VmHalt::Limited);
+                };
+
+                budget.grant(extension);
+                granted = granted.wrapping_add(extension);
+
+                if !budget.take() {
+                    return Ok(VmHalt::Limited);
+                }
             }
 
             let Some((inst, inst_len)) = self.unit.instruction_at(self.ip)? else {
@@ -2936,6 +5041,14 @@ impl Vm {
 
             tracing::trace!(ip = ?self.ip, ?inst);
 
+            if let Some(TraceWriter(writer)) = &mut self.trace_writer {
+                writer(format_args!("{:04} = {inst}", self.ip));
+            }
+
+            if let Some(count) = &mut self.instruction_count {
+                *count = count.wrapping_add(1);
+            }
+
             self.ip = self.ip.wrapping_add(inst_len);
             self.last_ip_len = inst_len as u8;
 
@@ -3179,10 +5292,14 @@ impl Vm {
                     self.op_match_object(slot, exact, addr, out)?;
                 }
                 inst::Kind::Yield { addr, out } => {
-                    return Ok(VmHalt::Yielded(Some(addr), out));
+                    if let Some(halt) = self.op_yield(Some(addr), out)? {
+                        return Ok(halt);
+                    }
                 }
                 inst::Kind::YieldUnit { out } => {
-                    return Ok(VmHalt::Yielded(None, out));
+                    if let Some(halt) = self.op_yield(None, out)? {
+                        return Ok(halt);
+                    }
                 }
                 inst::Kind::Op { op, a, b, out } => {
                     self.op_op(op, a, b, out)?;
@@ -3225,8 +5342,38 @@ impl TryClone for Vm {
             unit: self.unit.clone(),
             ip: self.ip,
             last_ip_len: self.last_ip_len,
+            error_ip: self.error_ip,
             stack: self.stack.try_clone()?,
             call_frames: self.call_frames.try_clone()?,
+            string_concat_reserve: self.string_concat_reserve,
+            capture_backtrace: self.capture_backtrace,
+            max_allocation: self.max_allocation,
+            max_string_len: self.max_string_len,
+            max_live_elements: self.max_live_elements,
+            live_elements: self.live_elements,
+            overflow_mode: self.overflow_mode,
+            div_by_zero: self.div_by_zero,
+            read_only: self.read_only,
+            strict_conditions: self.strict_conditions,
+            object_insertion_order: self.object_insertion_order,
+            // NB: The handler can't be cloned.
+            yield_handler: None,
+            // NB: Recordings and replays are specific to one virtual machine.
+            replay: VmReplay::None,
+            // NB: The handler can't be cloned.
+            missing_function_handler: None,
+            // NB: The handlers can't be cloned.
+            dynamic_field_handlers: alloc::Vec::new(),
+            // NB: The mocks can't be cloned.
+            call_mocks: alloc::Vec::new(),
+            // NB: The writer can't be cloned.
+            trace_writer: None,
+            // NB: Diagnostics are installed for each run.
+            has_diagnostics: false,
+            // NB: Captures are specific to one call.
+            residual: None,
+            // NB: Counts are specific to one call.
+            instruction_count: None,
         })
     }
 }
@@ -3259,6 +5406,10 @@ pub struct CallFrame {
     /// I.e. a function should not be able to manipulate the size of any other
     /// stack than its own.
     pub top: usize,
+    /// The size of the stack frame being returned to, used in debug builds to
+    /// verify that the stack is balanced.
+    #[cfg(debug_assertions)]
+    pub(crate) size: usize,
     /// Indicates that the call frame is isolated and should force an exit into
     /// the vm execution context.
     pub isolated: Isolated,
@@ -3273,6 +5424,68 @@ impl TryClone for CallFrame {
     }
 }
 
+/// A handler invoked when the virtual machine yields.
+///
+/// See [`Vm::set_yield_handler`].
+struct YieldHandler(Box<dyn FnMut(Option<Value>) -> Result<Value, VmError> + Send>);
+
+impl fmt::Debug for YieldHandler {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("YieldHandler")
+    }
+}
+
+/// A handler consulted when a called function is missing.
+///
+/// See [`Vm::set_missing_function_handler`].
+struct MissingFunctionHandler(
+    Box<dyn FnMut(Hash, &mut Stack, Address, usize, Output) -> Result<bool, VmError> + Send>,
+);
+
+impl fmt::Debug for MissingFunctionHandler {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("MissingFunctionHandler")
+    }
+}
+
+/// A handler consulted when a field is missing.
+///
+/// See [`Vm::set_dynamic_field_handler`].
+struct DynamicFieldHandler(Box<dyn FnMut(&Value, &str) -> Result<Option<Value>, VmError> + Send>);
+
+impl fmt::Debug for DynamicFieldHandler {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DynamicFieldHandler")
+    }
+}
+
+/// A mock replacing a called function.
+///
+/// See [`Vm::set_call_mock`].
+struct CallMock(Box<dyn FnMut(&mut Stack, Address, usize, Output) -> Result<(), VmError> + Send>);
+
+impl fmt::Debug for CallMock {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CallMock")
+    }
+}
+
+/// A writer receiving a line for each executed instruction.
+///
+/// See [`Vm::set_trace_writer`].
+struct TraceWriter(Box<dyn FnMut(fmt::Arguments<'_>) + Send>);
+
+impl fmt::Debug for TraceWriter {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TraceWriter")
+    }
+}
+
 /// Clear stack on drop.
 struct ClearStack<'a>(&'a mut Vm);
 
@@ -2,7 +2,137 @@ use core::ops::{
     Add, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Div, Mul, Rem, Sub,
 };
 
-use crate::runtime::{InstArithmeticOp, InstBitwiseOp, InstShiftOp, Protocol, VmErrorKind};
+use core::cmp::Ordering;
+
+use crate::runtime::{
+    DivByZero, InstArithmeticOp, InstBitwiseOp, InstShiftOp, OverflowMode, Protocol, VmErrorKind,
+};
+
+/// Define total integer functions for the given overflow behavior, which
+/// only fail on division by zero.
+macro_rules! overflow_ops {
+    ($($name:ident: $ty:ty),*; $add:ident, $sub:ident, $mul:ident, $div:ident, $rem:ident) => {
+        $(
+            pub(crate) mod $name {
+                #[inline]
+                pub(crate) fn add(a: $ty, b: $ty) -> Option<$ty> {
+                    Some(a.$add(b))
+                }
+
+                #[inline]
+                pub(crate) fn sub(a: $ty, b: $ty) -> Option<$ty> {
+                    Some(a.$sub(b))
+                }
+
+                #[inline]
+                pub(crate) fn mul(a: $ty, b: $ty) -> Option<$ty> {
+                    Some(a.$mul(b))
+                }
+
+                #[inline]
+                pub(crate) fn div(a: $ty, b: $ty) -> Option<$ty> {
+                    if b == 0 {
+                        return None;
+                    }
+
+                    Some(a.$div(b))
+                }
+
+                #[inline]
+                pub(crate) fn rem(a: $ty, b: $ty) -> Option<$ty> {
+                    if b == 0 {
+                        return None;
+                    }
+
+                    Some(a.$rem(b))
+                }
+            }
+        )*
+    };
+}
+
+mod wrapping {
+    overflow_ops! {
+        signed: i64, unsigned: u64;
+        wrapping_add, wrapping_sub, wrapping_mul, wrapping_div, wrapping_rem
+    }
+}
+
+mod saturating {
+    // NB: The remainder can't overflow except for `MIN % -1`, where the
+    // saturated and wrapped results are both zero.
+    overflow_ops! {
+        signed: i64, unsigned: u64;
+        saturating_add, saturating_sub, saturating_mul, saturating_div, wrapping_rem
+    }
+}
+
+/// Select the arithmetic table for an operation based on the overflow mode.
+macro_rules! arithmetic_ops {
+    ($mode:expr, $protocol:expr, $error:expr, $f64:path, $checked:ident, $op:ident) => {
+        match $mode {
+            OverflowMode::Checked => &Self {
+                protocol: $protocol,
+                error: $error,
+                i64: i64::$checked,
+                u64: u64::$checked,
+                f64: $f64,
+            },
+            OverflowMode::Wrapping => &Self {
+                protocol: $protocol,
+                error: $error,
+                i64: wrapping::signed::$op,
+                u64: wrapping::unsigned::$op,
+                f64: $f64,
+            },
+            OverflowMode::Saturating => &Self {
+                protocol: $protocol,
+                error: $error,
+                i64: saturating::signed::$op,
+                u64: saturating::unsigned::$op,
+                f64: $f64,
+            },
+        }
+    };
+}
+
+impl DivByZero {
+    /// Compute the result of a signed integer operation which failed, or
+    /// `None` if it should raise an error.
+    pub(super) fn signed(self, op: InstArithmeticOp, lhs: i64, rhs: i64) -> Option<i64> {
+        if rhs != 0 {
+            return None;
+        }
+
+        match (self, op) {
+            (DivByZero::Zero, InstArithmeticOp::Div | InstArithmeticOp::Rem) => Some(0),
+            (DivByZero::Saturate, InstArithmeticOp::Div) => Some(match lhs.cmp(&0) {
+                Ordering::Less => i64::MIN,
+                Ordering::Equal => 0,
+                Ordering::Greater => i64::MAX,
+            }),
+            (DivByZero::Saturate, InstArithmeticOp::Rem) => Some(0),
+            _ => None,
+        }
+    }
+
+    /// Compute the result of an unsigned integer operation which failed, or
+    /// `None` if it should raise an error.
+    pub(super) fn unsigned(self, op: InstArithmeticOp, lhs: u64, rhs: u64) -> Option<u64> {
+        if rhs != 0 {
+            return None;
+        }
+
+        match (self, op) {
+            (DivByZero::Zero, InstArithmeticOp::Div | InstArithmeticOp::Rem) => Some(0),
+            (DivByZero::Saturate, InstArithmeticOp::Div) => {
+                Some(if lhs == 0 { 0 } else { u64::MAX })
+            }
+            (DivByZero::Saturate, InstArithmeticOp::Rem) => Some(0),
+            _ => None,
+        }
+    }
+}
 
 pub(super) struct ArithmeticOps {
     pub(super) protocol: Protocol,
@@ -13,43 +143,48 @@ pub(super) struct ArithmeticOps {
 }
 
 impl ArithmeticOps {
-    pub(super) fn from_op(op: InstArithmeticOp) -> &'static Self {
+    pub(super) fn from_op(op: InstArithmeticOp, mode: OverflowMode) -> &'static Self {
         match op {
-            InstArithmeticOp::Add => &Self {
-                protocol: Protocol::ADD,
-                error: || VmErrorKind::Overflow,
-                i64: i64::checked_add,
-                u64: u64::checked_add,
-                f64: f64::add,
-            },
-            InstArithmeticOp::Sub => &Self {
-                protocol: Protocol::SUB,
-                error: || VmErrorKind::Underflow,
-                i64: i64::checked_sub,
-                u64: u64::checked_sub,
-                f64: f64::sub,
-            },
-            InstArithmeticOp::Mul => &Self {
-                protocol: Protocol::MUL,
-                error: || VmErrorKind::Overflow,
-                i64: i64::checked_mul,
-                u64: u64::checked_mul,
-                f64: f64::mul,
-            },
-            InstArithmeticOp::Div => &Self {
-                protocol: Protocol::DIV,
-                error: || VmErrorKind::DivideByZero,
-                i64: i64::checked_div,
-                u64: u64::checked_div,
-                f64: f64::div,
-            },
-            InstArithmeticOp::Rem => &Self {
-                protocol: Protocol::REM,
-                error: || VmErrorKind::DivideByZero,
-                i64: i64::checked_rem,
-                u64: u64::checked_rem,
-                f64: f64::rem,
-            },
+            InstArithmeticOp::Add => arithmetic_ops!(
+                mode,
+                Protocol::ADD,
+                || VmErrorKind::Overflow,
+                f64::add,
+                checked_add,
+                add
+            ),
+            InstArithmeticOp::Sub => arithmetic_ops!(
+                mode,
+                Protocol::SUB,
+                || VmErrorKind::Underflow,
+                f64::sub,
+                checked_sub,
+                sub
+            ),
+            InstArithmeticOp::Mul => arithmetic_ops!(
+                mode,
+                Protocol::MUL,
+                || VmErrorKind::Overflow,
+                f64::mul,
+                checked_mul,
+                mul
+            ),
+            InstArithmeticOp::Div => arithmetic_ops!(
+                mode,
+                Protocol::DIV,
+                || VmErrorKind::DivideByZero,
+                f64::div,
+                checked_div,
+                div
+            ),
+            InstArithmeticOp::Rem => arithmetic_ops!(
+                mode,
+                Protocol::REM,
+                || VmErrorKind::DivideByZero,
+                f64::rem,
+                checked_rem,
+                rem
+            ),
         }
     }
 }
@@ -63,43 +198,51 @@ pub(super) struct AssignArithmeticOps {
 }
 
 impl AssignArithmeticOps {
-    pub(super) fn from_op(op: InstArithmeticOp) -> &'static AssignArithmeticOps {
+    pub(super) fn from_op(
+        op: InstArithmeticOp,
+        mode: OverflowMode,
+    ) -> &'static AssignArithmeticOps {
         match op {
-            InstArithmeticOp::Add => &Self {
-                protocol: Protocol::ADD_ASSIGN,
-                error: || VmErrorKind::Overflow,
-                i64: i64::checked_add,
-                u64: u64::checked_add,
-                f64: f64::add,
-            },
-            InstArithmeticOp::Sub => &Self {
-                protocol: Protocol::SUB_ASSIGN,
-                error: || VmErrorKind::Underflow,
-                i64: i64::checked_sub,
-                u64: u64::checked_sub,
-                f64: f64::sub,
-            },
-            InstArithmeticOp::Mul => &Self {
-                protocol: Protocol::MUL_ASSIGN,
-                error: || VmErrorKind::Overflow,
-                i64: i64::checked_mul,
-                u64: u64::checked_mul,
-                f64: f64::mul,
-            },
-            InstArithmeticOp::Div => &Self {
-                protocol: Protocol::DIV_ASSIGN,
-                error: || VmErrorKind::DivideByZero,
-                i64: i64::checked_div,
-                u64: u64::checked_div,
-                f64: f64::div,
-            },
-            InstArithmeticOp::Rem => &Self {
-                protocol: Protocol::REM_ASSIGN,
-                error: || VmErrorKind::DivideByZero,
-                i64: i64::checked_rem,
-                u64: u64::checked_rem,
-                f64: f64::rem,
-            },
+            InstArithmeticOp::Add => arithmetic_ops!(
+                mode,
+                Protocol::ADD_ASSIGN,
+                || VmErrorKind::Overflow,
+                f64::add,
+                checked_add,
+                add
+            ),
+            InstArithmeticOp::Sub => arithmetic_ops!(
+                mode,
+                Protocol::SUB_ASSIGN,
+                || VmErrorKind::Underflow,
+                f64::sub,
+                checked_sub,
+                sub
+            ),
+            InstArithmeticOp::Mul => arithmetic_ops!(
+                mode,
+                Protocol::MUL_ASSIGN,
+                || VmErrorKind::Overflow,
+                f64::mul,
+                checked_mul,
+                mul
+            ),
+            InstArithmeticOp::Div => arithmetic_ops!(
+                mode,
+                Protocol::DIV_ASSIGN,
+                || VmErrorKind::DivideByZero,
+                f64::div,
+                checked_div,
+                div
+            ),
+            InstArithmeticOp::Rem => arithmetic_ops!(
+                mode,
+                Protocol::REM_ASSIGN,
+                || VmErrorKind::DivideByZero,
+                f64::rem,
+                checked_rem,
+                rem
+            ),
         }
     }
 }
@@ -1,19 +1,159 @@
+use core::fmt;
+use core::marker::PhantomData;
 use core::ptr::NonNull;
 
+use crate::alloc::{self, HashMap};
 use crate::hash::Hash;
-use crate::runtime::VmError;
+use crate::runtime::{Address, VmError};
 use crate::Diagnostics;
 
 /// A trait for runtime diagnostics in the virtual machine.
 pub trait VmDiagnostics {
     /// Mark that a function has been used.
-    fn function_used(&mut self, hash: Hash, at: usize) -> Result<(), VmError>;
+    ///
+    /// By default this does nothing.
+    #[inline]
+    fn function_used(&mut self, hash: Hash, at: usize) -> Result<(), VmError> {
+        _ = (hash, at);
+        Ok(())
+    }
+
+    /// Mark that a function is being called by the virtual machine.
+    ///
+    /// In contrast to [`VmDiagnostics::function_used`] this is called for
+    /// every function call, including calls to functions in the unit. By
+    /// default this does nothing.
+    #[inline]
+    fn function_called(&mut self, hash: Hash, at: usize) -> Result<(), VmError> {
+        _ = (hash, at);
+        Ok(())
+    }
+
+    /// Mark that a pattern match on a type, sequence or object has been
+    /// evaluated by the instruction at `ip`, and whether it matched.
+    ///
+    /// This makes it possible to observe which arms of a `match` expression
+    /// were tried. By default this does nothing.
+    #[inline]
+    fn match_evaluated(&mut self, ip: usize, matched: bool) -> Result<(), VmError> {
+        _ = (ip, matched);
+        Ok(())
+    }
+
+    /// Mark that the value at the given stack address has been dropped by a
+    /// drop instruction.
+    ///
+    /// This is called once for each address in the drop set, which makes it
+    /// possible to track the lifetime of values in a unit. By default this
+    /// does nothing.
+    #[inline]
+    fn value_dropped(&mut self, addr: Address) -> Result<(), VmError> {
+        _ = addr;
+        Ok(())
+    }
+
+    /// Mark that the instruction `op` couldn't be performed directly on its
+    /// operands and fell back to calling a protocol function, such as when
+    /// adding values of a type which implements the `ADD` protocol.
+    ///
+    /// By default this does nothing.
+    #[inline]
+    fn protocol_fallback(&mut self, op: ProtocolFallback) -> Result<(), VmError> {
+        _ = op;
+        Ok(())
+    }
+
+    /// Called when the instruction budget of the virtual machine has been
+    /// exhausted, with the number of instructions run since execution was
+    /// last resumed.
+    ///
+    /// Returning [`BudgetAction::Extend`] grants additional instructions to
+    /// the budget so that execution continues without returning to the
+    /// caller, which is cheaper than resuming it. By default this returns
+    /// [`BudgetAction::Halt`].
+    ///
+    /// See [`budget`] for how to limit execution.
+    ///
+    /// [`budget`]: crate::runtime::budget
+    #[inline]
+    fn budget_exhausted(&mut self, instructions_run: usize) -> BudgetAction {
+        _ = instructions_run;
+        BudgetAction::Halt
+    }
 
     /// Returns the vtable for this diagnostics object.
     #[doc(hidden)]
     fn vtable(&self) -> &'static VmDiagnosticsObjVtable;
 }
 
+/// The action to take when the instruction budget of the virtual machine has
+/// been exhausted.
+///
+/// See [`VmDiagnostics::budget_exhausted`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BudgetAction {
+    /// Halt execution, which can be resumed by the caller.
+    Halt,
+    /// Grant the given number of instructions to the budget and continue
+    /// execution.
+    Extend(usize),
+}
+
+/// An instruction which fell back to calling a protocol function.
+///
+/// See [`VmDiagnostics::protocol_fallback`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ProtocolFallback {
+    /// A logical or bitwise negation through the `NOT` protocol.
+    Not,
+    /// A numerical negation through the `NEG` protocol.
+    Neg,
+    /// An arithmetic operation, like `a + b`.
+    Arithmetic,
+    /// An arithmetic assignment, like `a += b`.
+    AssignArithmetic,
+    /// A bitwise operation, like `a & b`.
+    Bitwise,
+    /// A bitwise assignment, like `a &= b`.
+    AssignBitwise,
+    /// A shift operation, like `a << b`.
+    Shift,
+    /// A shift assignment, like `a <<= b`.
+    AssignShift,
+    /// Setting an index, like `a[b] = c`.
+    IndexSet,
+    /// Getting an index, like `a[b]`.
+    IndexGet,
+    /// Getting a tuple index, like `a.0`.
+    TupleIndexGetAt,
+    /// Setting a field, like `a.b = c`.
+    ObjectIndexSet,
+    /// Getting a field, like `a.b`.
+    ObjectIndexGetAt,
+}
+
+impl fmt::Display for ProtocolFallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Not => write!(f, "not"),
+            Self::Neg => write!(f, "neg"),
+            Self::Arithmetic => write!(f, "arithmetic"),
+            Self::AssignArithmetic => write!(f, "assign arithmetic"),
+            Self::Bitwise => write!(f, "bitwise"),
+            Self::AssignBitwise => write!(f, "assign bitwise"),
+            Self::Shift => write!(f, "shift"),
+            Self::AssignShift => write!(f, "assign shift"),
+            Self::IndexSet => write!(f, "index set"),
+            Self::IndexGet => write!(f, "index get"),
+            Self::TupleIndexGetAt => write!(f, "tuple index get"),
+            Self::ObjectIndexSet => write!(f, "object index set"),
+            Self::ObjectIndexGetAt => write!(f, "object index get"),
+        }
+    }
+}
+
 impl VmDiagnostics for Diagnostics {
     #[inline]
     fn function_used(&mut self, hash: Hash, at: usize) -> Result<(), VmError> {
@@ -23,22 +163,627 @@ impl VmDiagnostics for Diagnostics {
 
     #[inline]
     fn vtable(&self) -> &'static VmDiagnosticsObjVtable {
-        fn function_used_impl<T>(ptr: NonNull<()>, hash: Hash, at: usize) -> Result<(), VmError>
-        where
-            T: VmDiagnostics,
-        {
-            unsafe { VmDiagnostics::function_used(ptr.cast::<T>().as_mut(), hash, at) }
+        vtable::<Self>()
+    }
+}
+
+/// Diagnostics which count the number of times each function is called.
+///
+/// Calls to functions in the unit are only attributed to the function if the
+/// unit has been built with debug info, which is the default.
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::FunctionProfiler;
+/// use rune::sync::Arc;
+/// use rune::{Context, Hash, Vm};
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         fn add(a, b) {
+///             a + b
+///         }
+///
+///         pub fn main() {
+///             add(add(1, 2), 3)
+///         }
+///     }
+/// };
+///
+/// let context = Context::with_default_modules()?;
+/// let runtime = Arc::try_new(context.runtime()?)?;
+///
+/// let unit = rune::prepare(&mut sources).build()?;
+/// let unit = Arc::try_new(unit)?;
+///
+/// let mut vm = Vm::new(runtime, unit);
+/// let mut profiler = FunctionProfiler::new();
+///
+/// vm.call_with_diagnostics(["main"], (), &mut profiler)?;
+///
+/// assert_eq!(profiler.count(Hash::type_hash(["add"])), 2);
+/// assert_eq!(profiler.report()?, [(Hash::type_hash(["add"]), 2)]);
+/// # Ok::<_, rune::support::Error>(())
+/// ```
+#[derive(Default, Debug)]
+pub struct FunctionProfiler {
+    counts: HashMap<Hash, u64>,
+}
+
+impl FunctionProfiler {
+    /// Construct a new empty profiler.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of times the function with the given hash has been
+    /// called.
+    #[inline]
+    pub fn count(&self, hash: Hash) -> u64 {
+        self.counts.get(&hash).copied().unwrap_or_default()
+    }
+
+    /// Report the number of times each function has been called, sorted by
+    /// the number of calls in descending order.
+    ///
+    /// Functions which have been called the same number of times are sorted
+    /// by hash.
+    pub fn report(&self) -> alloc::Result<alloc::Vec<(Hash, u64)>> {
+        let mut report = alloc::Vec::try_with_capacity(self.counts.len())?;
+
+        for (&hash, &count) in &self.counts {
+            report.try_push((hash, count))?;
         }
 
-        &VmDiagnosticsObjVtable {
-            function_used: function_used_impl::<Self>,
+        report.sort_unstable_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Ok(report)
+    }
+
+    /// Clear all collected call counts.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.counts.clear();
+    }
+}
+
+impl VmDiagnostics for FunctionProfiler {
+    #[inline]
+    fn function_called(&mut self, hash: Hash, _: usize) -> Result<(), VmError> {
+        *self.counts.entry(hash).or_try_insert(0)? += 1;
+        Ok(())
+    }
+
+    #[inline]
+    fn vtable(&self) -> &'static VmDiagnosticsObjVtable {
+        vtable::<Self>()
+    }
+}
+
+/// Diagnostics which record the outcome of every pattern match evaluated by
+/// the virtual machine, such as the arms tried by a `match` expression.
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::MatchTracer;
+/// use rune::sync::Arc;
+/// use rune::{Context, Vm};
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         pub fn main(value) {
+///             match value {
+///                 [a, b] => a + b,
+///                 Some(value) => value,
+///                 _ => 0,
+///             }
+///         }
+///     }
+/// };
+///
+/// let context = Context::with_default_modules()?;
+/// let runtime = Arc::try_new(context.runtime()?)?;
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+/// let unit = Arc::try_new(unit)?;
+///
+/// let mut vm = Vm::new(runtime, unit);
+/// let mut tracer = MatchTracer::new();
+///
+/// vm.call_with_diagnostics(["main"], (Some(42i64),), &mut tracer)?;
+///
+/// let matched = tracer.outcomes().iter().map(|&(_, matched)| matched);
+/// assert!(matched.eq([false, true]));
+/// # Ok::<_, rune::support::Error>(())
+/// ```
+#[derive(Default, Debug)]
+pub struct MatchTracer {
+    outcomes: alloc::Vec<(usize, bool)>,
+}
+
+impl MatchTracer {
+    /// Construct a new empty tracer.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the recorded outcomes in the order they were evaluated, as the
+    /// instruction pointer of the match and whether it matched.
+    #[inline]
+    pub fn outcomes(&self) -> &[(usize, bool)] {
+        &self.outcomes
+    }
+
+    /// Clear all recorded outcomes.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.outcomes.clear();
+    }
+}
+
+impl VmDiagnostics for MatchTracer {
+    #[inline]
+    fn match_evaluated(&mut self, ip: usize, matched: bool) -> Result<(), VmError> {
+        self.outcomes.try_push((ip, matched))?;
+        Ok(())
+    }
+
+    #[inline]
+    fn vtable(&self) -> &'static VmDiagnosticsObjVtable {
+        vtable::<Self>()
+    }
+}
+
+/// Diagnostics which record the stack address of every value dropped by the
+/// virtual machine.
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::DropTracer;
+/// use rune::sync::Arc;
+/// use rune::{Context, Vm};
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         pub fn main() {
+///             let a = {
+///                 let values = [1, 2];
+///                 values.len()
+///             };
+///
+///             a + 1
+///         }
+///     }
+/// };
+///
+/// let context = Context::with_default_modules()?;
+/// let runtime = Arc::try_new(context.runtime()?)?;
+///
+/// let unit = rune::prepare(&mut sources).build()?;
+/// let unit = Arc::try_new(unit)?;
+///
+/// let mut vm = Vm::new(runtime, unit);
+/// let mut tracer = DropTracer::new();
+///
+/// vm.call_with_diagnostics(["main"], (), &mut tracer)?;
+/// assert!(!tracer.dropped().is_empty());
+/// # Ok::<_, rune::support::Error>(())
+/// ```
+#[derive(Default, Debug)]
+pub struct DropTracer {
+    dropped: alloc::Vec<Address>,
+}
+
+impl DropTracer {
+    /// Construct a new empty tracer.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the addresses of the dropped values in the order they were
+    /// dropped.
+    #[inline]
+    pub fn dropped(&self) -> &[Address] {
+        &self.dropped
+    }
+
+    /// Clear all recorded addresses.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.dropped.clear();
+    }
+}
+
+impl VmDiagnostics for DropTracer {
+    #[inline]
+    fn value_dropped(&mut self, addr: Address) -> Result<(), VmError> {
+        self.dropped.try_push(addr)?;
+        Ok(())
+    }
+
+    #[inline]
+    fn vtable(&self) -> &'static VmDiagnosticsObjVtable {
+        vtable::<Self>()
+    }
+}
+
+/// Diagnostics which count how many times each instruction fell back to
+/// calling a protocol function.
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::{ProtocolFallback, ProtocolFallbackCounter};
+/// use rune::sync::Arc;
+/// use rune::{Context, Vm};
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         pub fn main(a, b) {
+///             let n = 1 + 2;
+///             a + b
+///         }
+///     }
+/// };
+///
+/// let context = Context::with_default_modules()?;
+/// let runtime = Arc::try_new(context.runtime()?)?;
+///
+/// let unit = rune::prepare(&mut sources).build()?;
+/// let unit = Arc::try_new(unit)?;
+///
+/// let mut vm = Vm::new(runtime, unit);
+/// let mut counter = ProtocolFallbackCounter::new();
+///
+/// vm.call_with_diagnostics(["main"], ("Hello ", "World"), &mut counter)?;
+///
+/// // Strings are added through the `ADD` protocol.
+/// assert_eq!(counter.count(ProtocolFallback::Arithmetic), 1);
+/// assert_eq!(counter.total(), 1);
+/// # Ok::<_, rune::support::Error>(())
+/// ```
+#[derive(Default, Debug)]
+pub struct ProtocolFallbackCounter {
+    counts: HashMap<ProtocolFallback, u64>,
+}
+
+impl ProtocolFallbackCounter {
+    /// Construct a new empty counter.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of times the given instruction fell back to calling a
+    /// protocol function.
+    #[inline]
+    pub fn count(&self, op: ProtocolFallback) -> u64 {
+        self.counts.get(&op).copied().unwrap_or_default()
+    }
+
+    /// Get the total number of times any instruction fell back to calling a
+    /// protocol function.
+    #[inline]
+    pub fn total(&self) -> u64 {
+        self.counts.values().sum()
+    }
+
+    /// Clear all collected counts.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.counts.clear();
+    }
+}
+
+impl VmDiagnostics for ProtocolFallbackCounter {
+    #[inline]
+    fn protocol_fallback(&mut self, op: ProtocolFallback) -> Result<(), VmError> {
+        *self.counts.entry(op).or_try_insert(0)? += 1;
+        Ok(())
+    }
+
+    #[inline]
+    fn vtable(&self) -> &'static VmDiagnosticsObjVtable {
+        vtable::<Self>()
+    }
+}
+
+/// Diagnostics which call the wrapped function when the instruction budget of
+/// the virtual machine has been exhausted, deciding whether execution should
+/// continue.
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::{budget, BudgetAction, BudgetHandler};
+/// use rune::sync::Arc;
+/// use rune::{Context, Vm};
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         pub fn main() {
+///             let n = 0;
+///
+///             for i in 0..100 {
+///                 n += i;
+///             }
+///
+///             n
+///         }
+///     }
+/// };
+///
+/// let context = Context::with_default_modules()?;
+/// let runtime = Arc::try_new(context.runtime()?)?;
+///
+/// let unit = rune::prepare(&mut sources).build()?;
+/// let unit = Arc::try_new(unit)?;
+///
+/// let mut vm = Vm::new(runtime, unit);
+///
+/// // Grant ten more instructions each time the budget is exhausted.
+/// let mut handler = BudgetHandler::new(|_| BudgetAction::Extend(10));
+///
+/// let output = budget::with(10, || vm.call_with_diagnostics(["main"], (), &mut handler)).call()?;
+/// let output: i64 = rune::from_value(output)?;
+/// assert_eq!(output, 4950);
+/// # Ok::<_, rune::support::Error>(())
+/// ```
+pub struct BudgetHandler<F> {
+    handler: F,
+}
+
+impl<F> BudgetHandler<F>
+where
+    F: FnMut(usize) -> BudgetAction,
+{
+    /// Construct new diagnostics calling the given function when the budget
+    /// has been exhausted.
+    ///
+    /// The function is called with the number of instructions run since
+    /// execution was last resumed.
+    #[inline]
+    pub fn new(handler: F) -> Self {
+        Self { handler }
+    }
+}
+
+impl<F> fmt::Debug for BudgetHandler<F> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BudgetHandler")
+    }
+}
+
+impl<F> VmDiagnostics for BudgetHandler<F>
+where
+    F: FnMut(usize) -> BudgetAction,
+{
+    #[inline]
+    fn budget_exhausted(&mut self, instructions_run: usize) -> BudgetAction {
+        (self.handler)(instructions_run)
+    }
+
+    #[inline]
+    fn vtable(&self) -> &'static VmDiagnosticsObjVtable {
+        vtable::<Self>()
+    }
+}
+
+/// Diagnostics which forward to both of the wrapped diagnostics, which makes
+/// it possible to use several of them at once.
+///
+/// When the budget has been exhausted, the first diagnostics are consulted
+/// before the second, and the first action which doesn't halt is used.
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::{FunctionProfiler, MatchTracer};
+/// use rune::sync::Arc;
+/// use rune::{Context, Hash, Vm};
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         fn value() {
+///             Some(42)
+///         }
+///
+///         pub fn main() {
+///             match value() {
+///                 Some(value) => value,
+///                 _ => 0,
+///             }
+///         }
+///     }
+/// };
+///
+/// let context = Context::with_default_modules()?;
+/// let runtime = Arc::try_new(context.runtime()?)?;
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+/// let unit = Arc::try_new(unit)?;
+///
+/// let mut vm = Vm::new(runtime, unit);
+/// let mut profiler = FunctionProfiler::new();
+/// let mut tracer = MatchTracer::new();
+///
+/// vm.call_with_diagnostics(["main"], (), &mut (&mut profiler, &mut tracer))?;
+///
+/// assert_eq!(profiler.count(Hash::type_hash(["value"])), 1);
+/// assert_eq!(tracer.outcomes().len(), 1);
+/// # Ok::<_, rune::support::Error>(())
+/// ```
+impl<A, B> VmDiagnostics for (A, B)
+where
+    A: VmDiagnostics,
+    B: VmDiagnostics,
+{
+    #[inline]
+    fn function_used(&mut self, hash: Hash, at: usize) -> Result<(), VmError> {
+        self.0.function_used(hash, at)?;
+        self.1.function_used(hash, at)
+    }
+
+    #[inline]
+    fn function_called(&mut self, hash: Hash, at: usize) -> Result<(), VmError> {
+        self.0.function_called(hash, at)?;
+        self.1.function_called(hash, at)
+    }
+
+    #[inline]
+    fn match_evaluated(&mut self, ip: usize, matched: bool) -> Result<(), VmError> {
+        self.0.match_evaluated(ip, matched)?;
+        self.1.match_evaluated(ip, matched)
+    }
+
+    #[inline]
+    fn value_dropped(&mut self, addr: Address) -> Result<(), VmError> {
+        self.0.value_dropped(addr)?;
+        self.1.value_dropped(addr)
+    }
+
+    #[inline]
+    fn protocol_fallback(&mut self, op: ProtocolFallback) -> Result<(), VmError> {
+        self.0.protocol_fallback(op)?;
+        self.1.protocol_fallback(op)
+    }
+
+    #[inline]
+    fn budget_exhausted(&mut self, instructions_run: usize) -> BudgetAction {
+        match self.0.budget_exhausted(instructions_run) {
+            BudgetAction::Halt => self.1.budget_exhausted(instructions_run),
+            action => action,
         }
     }
+
+    #[inline]
+    fn vtable(&self) -> &'static VmDiagnosticsObjVtable {
+        vtable::<Self>()
+    }
+}
+
+impl<T> VmDiagnostics for &mut T
+where
+    T: ?Sized + VmDiagnostics,
+{
+    #[inline]
+    fn function_used(&mut self, hash: Hash, at: usize) -> Result<(), VmError> {
+        (**self).function_used(hash, at)
+    }
+
+    #[inline]
+    fn function_called(&mut self, hash: Hash, at: usize) -> Result<(), VmError> {
+        (**self).function_called(hash, at)
+    }
+
+    #[inline]
+    fn match_evaluated(&mut self, ip: usize, matched: bool) -> Result<(), VmError> {
+        (**self).match_evaluated(ip, matched)
+    }
+
+    #[inline]
+    fn value_dropped(&mut self, addr: Address) -> Result<(), VmError> {
+        (**self).value_dropped(addr)
+    }
+
+    #[inline]
+    fn protocol_fallback(&mut self, op: ProtocolFallback) -> Result<(), VmError> {
+        (**self).protocol_fallback(op)
+    }
+
+    #[inline]
+    fn budget_exhausted(&mut self, instructions_run: usize) -> BudgetAction {
+        (**self).budget_exhausted(instructions_run)
+    }
+
+    #[inline]
+    fn vtable(&self) -> &'static VmDiagnosticsObjVtable {
+        vtable::<Self>()
+    }
+}
+
+/// Get the vtable for the given diagnostics, which is only generated once for
+/// each type.
+#[inline]
+fn vtable<T>() -> &'static VmDiagnosticsObjVtable
+where
+    T: VmDiagnostics,
+{
+    &Vtable::<T>::VTABLE
+}
+
+struct Vtable<T>(PhantomData<T>);
+
+impl<T> Vtable<T>
+where
+    T: VmDiagnostics,
+{
+    const VTABLE: VmDiagnosticsObjVtable = VmDiagnosticsObjVtable {
+        function_used: function_used_impl::<T>,
+        function_called: function_called_impl::<T>,
+        match_evaluated: match_evaluated_impl::<T>,
+        value_dropped: value_dropped_impl::<T>,
+        protocol_fallback: protocol_fallback_impl::<T>,
+        budget_exhausted: budget_exhausted_impl::<T>,
+    };
 }
 
 #[derive(Debug)]
 pub struct VmDiagnosticsObjVtable {
     function_used: unsafe fn(NonNull<()>, hash: Hash, at: usize) -> Result<(), VmError>,
+    function_called: unsafe fn(NonNull<()>, hash: Hash, at: usize) -> Result<(), VmError>,
+    match_evaluated: unsafe fn(NonNull<()>, ip: usize, matched: bool) -> Result<(), VmError>,
+    value_dropped: unsafe fn(NonNull<()>, addr: Address) -> Result<(), VmError>,
+    protocol_fallback: unsafe fn(NonNull<()>, op: ProtocolFallback) -> Result<(), VmError>,
+    budget_exhausted: unsafe fn(NonNull<()>, instructions_run: usize) -> BudgetAction,
+}
+
+fn function_used_impl<T>(ptr: NonNull<()>, hash: Hash, at: usize) -> Result<(), VmError>
+where
+    T: VmDiagnostics,
+{
+    unsafe { VmDiagnostics::function_used(ptr.cast::<T>().as_mut(), hash, at) }
+}
+
+fn function_called_impl<T>(ptr: NonNull<()>, hash: Hash, at: usize) -> Result<(), VmError>
+where
+    T: VmDiagnostics,
+{
+    unsafe { VmDiagnostics::function_called(ptr.cast::<T>().as_mut(), hash, at) }
+}
+
+fn match_evaluated_impl<T>(ptr: NonNull<()>, ip: usize, matched: bool) -> Result<(), VmError>
+where
+    T: VmDiagnostics,
+{
+    unsafe { VmDiagnostics::match_evaluated(ptr.cast::<T>().as_mut(), ip, matched) }
+}
+
+fn value_dropped_impl<T>(ptr: NonNull<()>, addr: Address) -> Result<(), VmError>
+where
+    T: VmDiagnostics,
+{
+    unsafe { VmDiagnostics::value_dropped(ptr.cast::<T>().as_mut(), addr) }
+}
+
+fn protocol_fallback_impl<T>(ptr: NonNull<()>, op: ProtocolFallback) -> Result<(), VmError>
+where
+    T: VmDiagnostics,
+{
+    unsafe { VmDiagnostics::protocol_fallback(ptr.cast::<T>().as_mut(), op) }
+}
+
+fn budget_exhausted_impl<T>(ptr: NonNull<()>, instructions_run: usize) -> BudgetAction
+where
+    T: VmDiagnostics,
+{
+    unsafe { VmDiagnostics::budget_exhausted(ptr.cast::<T>().as_mut(), instructions_run) }
 }
 
 #[repr(C)]
@@ -51,7 +796,9 @@ pub(crate) struct VmDiagnosticsObj {
 impl VmDiagnosticsObj {
     #[inline]
     pub(crate) fn new(trait_obj: &mut dyn VmDiagnostics) -> Self {
-        let vtable = trait_obj.vtable();
+        // NB: This must be called on the trait object, since calling it on
+        // the reference would resolve to the implementation for `&mut T`.
+        let vtable = VmDiagnostics::vtable(&*trait_obj);
 
         Self {
             ptr: unsafe { NonNull::new_unchecked(trait_obj as *mut _ as *mut ()) },
@@ -63,4 +810,29 @@ impl VmDiagnosticsObj {
     pub(crate) fn function_used(&mut self, hash: Hash, at: usize) -> Result<(), VmError> {
         unsafe { (self.vtable.function_used)(self.ptr, hash, at) }
     }
+
+    #[inline]
+    pub(crate) fn function_called(&mut self, hash: Hash, at: usize) -> Result<(), VmError> {
+        unsafe { (self.vtable.function_called)(self.ptr, hash, at) }
+    }
+
+    #[inline]
+    pub(crate) fn match_evaluated(&mut self, ip: usize, matched: bool) -> Result<(), VmError> {
+        unsafe { (self.vtable.match_evaluated)(self.ptr, ip, matched) }
+    }
+
+    #[inline]
+    pub(crate) fn value_dropped(&mut self, addr: Address) -> Result<(), VmError> {
+        unsafe { (self.vtable.value_dropped)(self.ptr, addr) }
+    }
+
+    #[inline]
+    pub(crate) fn protocol_fallback(&mut self, op: ProtocolFallback) -> Result<(), VmError> {
+        unsafe { (self.vtable.protocol_fallback)(self.ptr, op) }
+    }
+
+    #[inline]
+    pub(crate) fn budget_exhausted(&mut self, instructions_run: usize) -> BudgetAction {
+        unsafe { (self.vtable.budget_exhausted)(self.ptr, instructions_run) }
+    }
 }
@@ -52,6 +52,7 @@ impl VmError {
                 },
                 chain: rust_alloc::vec::Vec::new(),
                 stacktrace: rust_alloc::vec::Vec::new(),
+                backtrace: rust_alloc::vec::Vec::new(),
             }),
         }
     }
@@ -99,6 +100,16 @@ impl VmError {
         &self.inner.stacktrace
     }
 
+    /// Get the call-frame backtrace captured when the error was raised,
+    /// starting with the innermost frame.
+    ///
+    /// This is only populated if the virtual machine which raised the error
+    /// had backtrace capturing enabled through [`Vm::set_capture_backtrace`].
+    #[inline]
+    pub fn backtrace(&self) -> &[BacktraceFrame] {
+        &self.inner.backtrace
+    }
+
     /// Construct an overflow error.
     #[inline]
     pub fn overflow() -> Self {
@@ -132,6 +143,35 @@ impl VmError {
         }
     }
 
+    /// Capture the call-frame backtrace of the given virtual machine into the
+    /// error, unless one has already been captured by an inner virtual
+    /// machine.
+    pub(crate) fn with_backtrace<T>(result: Result<T, Self>, vm: &Vm) -> Result<T, Self> {
+        match result {
+            Ok(ok) => Ok(ok),
+            Err(mut err) => {
+                if err.inner.backtrace.is_empty() {
+                    let unit = vm.unit();
+
+                    let frames = vm.call_frames().iter().rev();
+                    let frames = frames.map(|frame| BacktraceFrame {
+                        unit: unit.clone(),
+                        ip: frame.ip,
+                    });
+
+                    err.inner.backtrace.push(BacktraceFrame {
+                        unit: unit.clone(),
+                        ip: vm.last_ip(),
+                    });
+
+                    err.inner.backtrace.extend(frames);
+                }
+
+                Err(err)
+            }
+        }
+    }
+
     /// Add auxilliary errors if appropriate.
     #[inline]
     pub(crate) fn with_error<E>(mut self, error: E) -> Self
@@ -164,6 +204,7 @@ impl fmt::Debug for VmError {
             .field("error", &self.inner.error)
             .field("chain", &self.inner.chain)
             .field("stacktrace", &self.inner.stacktrace)
+            .field("backtrace", &self.inner.backtrace)
             .finish()
     }
 }
@@ -182,6 +223,20 @@ pub struct VmErrorLocation {
     pub frames: rust_alloc::vec::Vec<CallFrame>,
 }
 
+/// A single frame in the backtrace of a [`VmError`].
+///
+/// See [`VmError::backtrace`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct BacktraceFrame {
+    /// The unit the frame is executing in.
+    pub unit: Arc<Unit>,
+    /// The instruction pointer of the frame. For the innermost frame this is
+    /// the instruction which raised the error, for any other frame it is the
+    /// instruction which execution resumes at once the call it made returns.
+    pub ip: usize,
+}
+
 #[derive(Debug)]
 #[non_exhaustive]
 pub struct VmErrorAt {
@@ -217,6 +272,7 @@ pub(crate) struct VmErrorInner {
     pub(crate) error: VmErrorAt,
     pub(crate) chain: rust_alloc::vec::Vec<VmErrorAt>,
     pub(crate) stacktrace: rust_alloc::vec::Vec<VmErrorLocation>,
+    pub(crate) backtrace: rust_alloc::vec::Vec<BacktraceFrame>,
 }
 
 /// A result produced by the virtual machine.
@@ -273,6 +329,7 @@ impl<const N: usize> From<[VmErrorKind; N]> for VmError {
                 },
                 chain,
                 stacktrace: rust_alloc::vec::Vec::new(),
+                backtrace: rust_alloc::vec::Vec::new(),
             }),
         }
     }
@@ -615,6 +672,9 @@ pub(crate) enum VmErrorKind {
     ExpectedEmpty {
         actual: TypeInfo,
     },
+    ExpectedBoolCondition {
+        actual: TypeInfo,
+    },
     ExpectedTuple {
         actual: TypeInfo,
     },
@@ -647,8 +707,12 @@ pub(crate) enum VmErrorKind {
     ExpectedExitedExecutionState {
         actual: ExecutionState,
     },
+    ExpectedAwaitingExecutionState {
+        actual: ExecutionState,
+    },
     GeneratorComplete,
     FutureCompleted,
+    WouldBlock,
     // Used in rune-macros.
     MissingVariant {
         name: String,
@@ -681,6 +745,28 @@ pub(crate) enum VmErrorKind {
     },
     MissingCallFrame,
     IllegalFormat,
+    AllocationLimitExceeded {
+        requested: usize,
+        limit: usize,
+    },
+    ReplayDiverged {
+        index: usize,
+    },
+    ReadOnlyViolation {
+        target: TypeInfo,
+    },
+    StringLengthExceeded {
+        length: usize,
+        limit: usize,
+    },
+    ElementLimitExceeded {
+        limit: usize,
+    },
+    ArgumentTypeMismatch {
+        position: usize,
+        expected: Hash,
+        actual: TypeInfo,
+    },
 }
 
 impl fmt::Display for VmErrorKind {
@@ -835,6 +921,9 @@ impl fmt::Display for VmErrorKind {
             VmErrorKind::ExpectedEmpty { actual } => {
                 write!(f, "Expected empty, but found `{actual}`")
             }
+            VmErrorKind::ExpectedBoolCondition { actual } => {
+                write!(f, "Expected condition to be `bool`, but found `{actual}`")
+            }
             VmErrorKind::ExpectedTuple { actual } => {
                 write!(f, "Expected tuple, but found `{actual}`")
             }
@@ -867,10 +956,14 @@ impl fmt::Display for VmErrorKind {
             VmErrorKind::ExpectedExitedExecutionState { actual } => {
                 write!(f, "Expected exited execution state, but was {actual}")
             }
+            VmErrorKind::ExpectedAwaitingExecutionState { actual } => {
+                write!(f, "Expected awaiting execution state, but was {actual}")
+            }
             VmErrorKind::GeneratorComplete => {
                 write!(f, "Cannot resume a generator that has completed")
             }
             VmErrorKind::FutureCompleted => write!(f, "Future already completed"),
+            VmErrorKind::WouldBlock => write!(f, "Future would block the current thread"),
             VmErrorKind::MissingVariant { name } => write!(f, "No variant matching `{name}`"),
             VmErrorKind::MissingField { target, field } => {
                 write!(f, "Missing field `{field}` on `{target}`")
@@ -908,6 +1001,34 @@ impl fmt::Display for VmErrorKind {
             VmErrorKind::IllegalFormat => {
                 write!(f, "Value cannot be formatted")
             }
+            VmErrorKind::AllocationLimitExceeded { requested, limit } => write!(
+                f,
+                "Allocation of {requested} bytes exceeds the limit of {limit} bytes"
+            ),
+            VmErrorKind::ReplayDiverged { index } => write!(
+                f,
+                "Execution diverged from the replay log at decision {index}"
+            ),
+            VmErrorKind::ReadOnlyViolation { target } => write!(
+                f,
+                "Cannot mutate externally owned value of type `{target}` in read-only mode"
+            ),
+            VmErrorKind::StringLengthExceeded { length, limit } => write!(
+                f,
+                "String of length {length} exceeds the limit of {limit} bytes"
+            ),
+            VmErrorKind::ElementLimitExceeded { limit } => write!(
+                f,
+                "Collections constructed by the virtual machine exceed the limit of {limit} elements"
+            ),
+            VmErrorKind::ArgumentTypeMismatch {
+                position,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Argument #{position} has type `{actual}` but expected type with hash `{expected}`"
+            ),
         }
     }
 }
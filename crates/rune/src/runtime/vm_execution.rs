@@ -12,8 +12,8 @@ use crate::shared::AssertSend;
 use crate::sync::Arc;
 
 use super::{
-    Address, GeneratorState, Output, RuntimeContext, Unit, Value, Vm, VmDiagnostics, VmError,
-    VmErrorKind, VmHalt, VmHaltInfo,
+    Address, GeneratorState, Output, RuntimeContext, Select, Unit, Value, Vm, VmDiagnostics,
+    VmError, VmErrorKind, VmHalt, VmHaltInfo,
 };
 
 static COMPLETE_WAKER_VTABLE: RawWakerVTable = RawWakerVTable::new(
@@ -40,6 +40,9 @@ pub(crate) enum ExecutionState {
     Resumed(Output),
     /// Suspended execution.
     Suspended,
+    /// Execution is suspended on an await which didn't complete
+    /// synchronously, and whose result should be stored in the given output.
+    Awaiting(Output),
     /// Execution exited.
     Exited(Option<Address>),
 }
@@ -50,6 +53,7 @@ impl fmt::Display for ExecutionState {
             ExecutionState::Initial => write!(f, "initial"),
             ExecutionState::Resumed(out) => write!(f, "resumed({out})"),
             ExecutionState::Suspended => write!(f, "suspended"),
+            ExecutionState::Awaiting(out) => write!(f, "awaiting({out})"),
             ExecutionState::Exited(..) => write!(f, "exited"),
         }
     }
@@ -238,6 +242,81 @@ where
         }
     }
 
+    /// Resume an execution which was halted by [`VmResume::complete`]
+    /// because it awaited a future which didn't complete synchronously,
+    /// using `value` as the result of the await.
+    ///
+    /// The awaited future is not polled again. This makes it possible to
+    /// drive the logic of asynchronous scripts synchronously, such as in
+    /// tests.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the execution is not halted on an await.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::VmOutcome;
+    /// use rune::sync::Arc;
+    /// use rune::{Context, Module, Vm};
+    ///
+    /// #[rune::function]
+    /// async fn fetch() -> i64 {
+    ///     core::future::pending().await
+    /// }
+    ///
+    /// let mut module = Module::new();
+    /// module.function_meta(fetch)?;
+    ///
+    /// let mut context = Context::with_default_modules()?;
+    /// context.install(module)?;
+    ///
+    /// let runtime = Arc::try_new(context.runtime()?)?;
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub async fn main() {
+    ///             fetch().await + 1
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    /// let unit = Arc::try_new(unit)?;
+    ///
+    /// let mut vm = Vm::new(runtime, unit);
+    /// let mut execution = vm.execute(["main"], ())?;
+    ///
+    /// // Completing the execution synchronously halts on the await.
+    /// assert!(execution.resume().complete().is_err());
+    ///
+    /// let outcome = execution
+    ///     .resume_with_await_result(rune::to_value(41i64)?)?
+    ///     .complete()?;
+    ///
+    /// let VmOutcome::Complete(value) = outcome else {
+    ///     panic!("expected the execution to complete");
+    /// };
+    ///
+    /// assert_eq!(rune::from_value::<i64>(value)?, 42);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn resume_with_await_result(
+        &mut self,
+        value: Value,
+    ) -> Result<VmResume<'_, 'static, T>, VmError> {
+        let ExecutionState::Awaiting(out) = self.state else {
+            return Err(VmError::new(VmErrorKind::ExpectedAwaitingExecutionState {
+                actual: self.state,
+            }));
+        };
+
+        self.vm.as_mut().stack_mut().store(out, value)?;
+        self.state = ExecutionState::Suspended;
+        Ok(self.resume())
+    }
+
     /// End execution and perform debug checks.
     pub(crate) fn end(&mut self) -> Result<Value, VmError> {
         let ExecutionState::Exited(addr) = self.state else {
@@ -393,6 +472,61 @@ pub enum VmOutcome {
 }
 
 impl VmOutcome {
+    /// Get the value produced if the execution returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::sync::Arc;
+    /// use rune::{Context, Vm};
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() {
+    ///             yield 1;
+    ///             2
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::try_new(context.runtime()?)?;
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let unit = Arc::try_new(unit)?;
+    ///
+    /// let mut vm = Vm::new(runtime, unit);
+    /// let mut execution = vm.execute(["main"], ())?;
+    ///
+    /// let outcome = execution.resume().complete()?;
+    /// assert!(outcome.is_yielded());
+    /// assert!(outcome.as_complete().is_none());
+    ///
+    /// let outcome = execution.resume().complete()?;
+    /// let value = outcome.as_complete().expect("execution returned");
+    /// assert_eq!(value.as_signed()?, 2);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    #[inline]
+    pub fn as_complete(&self) -> Option<&Value> {
+        match self {
+            VmOutcome::Complete(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Test if the execution yielded a value.
+    #[inline]
+    pub fn is_yielded(&self) -> bool {
+        matches!(self, VmOutcome::Yielded(..))
+    }
+
+    /// Test if the execution was limited, such as by running out of budget.
+    #[inline]
+    pub fn is_limited(&self) -> bool {
+        matches!(self, VmOutcome::Limited)
+    }
+
     /// Convert the outcome into a [`GeneratorState`].
     ///
     /// # Errors
@@ -457,6 +591,20 @@ impl<'this, 'diag, T> VmResume<'this, 'diag, T> {
         }
     }
 
+    /// Get the `select` expression the execution is waiting on, if any.
+    ///
+    /// This is available while polling the execution returns
+    /// [`Poll::Pending`] because it's parked on a `select` expression, which
+    /// makes it possible to inspect which branches are still pending, such as
+    /// when diagnosing a script that hangs.
+    #[inline]
+    pub fn awaited_select(&self) -> Option<&Select> {
+        match &self.awaited {
+            Some(Awaited::Select(select, _)) => Some(select),
+            _ => None,
+        }
+    }
+
     /// Associate diagnostics with the execution.
     pub fn with_diagnostics<'a>(
         self,
@@ -479,14 +627,26 @@ where
     ///
     /// This will error if the execution is suspended through awaiting.
     pub fn complete(self) -> Result<VmOutcome, VmError> {
-        let this = pin!(self);
+        let mut this = pin!(self);
         let mut cx = Context::from_waker(&COMPLETE_WAKER);
 
-        match this.poll(&mut cx) {
+        match this.as_mut().poll(&mut cx) {
             Poll::Ready(result) => result,
-            Poll::Pending => Err(VmError::new(VmErrorKind::Halted {
-                halt: VmHaltInfo::Awaited,
-            })),
+            Poll::Pending => {
+                // SAFETY: Nothing is moved out of the pinned value.
+                let this = unsafe { Pin::get_unchecked_mut(this) };
+
+                // NB: Remember where the result of the await should be
+                // stored, so that it can be provided through
+                // `VmExecution::resume_with_await_result`.
+                if let Some(Awaited::Future(_, out)) = &this.awaited {
+                    this.execution.state = ExecutionState::Awaiting(*out);
+                }
+
+                Err(VmError::new(VmErrorKind::Halted {
+                    halt: VmHaltInfo::Awaited,
+                }))
+            }
         }
     }
 }
@@ -550,6 +710,11 @@ where
                 VmHalt::Limited => {
                     return Poll::Ready(Ok(VmOutcome::Limited));
                 }
+                VmHalt::Breakpoint(..) => {
+                    return Poll::Ready(Err(VmError::new(VmErrorKind::Halted {
+                        halt: VmHaltInfo::Breakpoint,
+                    })));
+                }
             }
 
             if this.execution.states.is_empty() {
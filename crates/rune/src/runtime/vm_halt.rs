@@ -15,6 +15,26 @@ pub(crate) enum VmHalt {
     Awaited(Awaited),
     /// Call into a new virtual machine.
     VmCall(VmCall),
+    /// The virtual machine reached the breakpoint at the given instruction
+    /// pointer without executing it.
+    Breakpoint(usize),
+}
+
+/// The reason why [`Vm::run_until`] stopped.
+///
+/// [`Vm::run_until`]: crate::Vm::run_until
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VmBreak {
+    /// The instruction at the given instruction pointer is the next one to be
+    /// executed.
+    Breakpoint(usize),
+    /// The virtual machine exited by running out of call frames, with the
+    /// returned value stored at the given address if any.
+    Exited(Option<Address>),
+    /// The virtual machine ran out of execution budget before reaching the
+    /// breakpoint.
+    Limited,
 }
 
 /// The reason why the virtual machine execution stopped.
@@ -26,6 +46,10 @@ pub(crate) enum VmHaltInfo {
     Yielded,
     /// The virtual machine awaited on the given future.
     Awaited,
+    /// The virtual machine reached a breakpoint.
+    Breakpoint,
+    /// The virtual machine called into a new virtual machine.
+    VmCall,
 }
 
 impl fmt::Display for VmHaltInfo {
@@ -34,6 +58,8 @@ impl fmt::Display for VmHaltInfo {
             Self::Limited => write!(f, "limited"),
             Self::Yielded => write!(f, "yielded"),
             Self::Awaited => write!(f, "awaited"),
+            Self::Breakpoint => write!(f, "breakpoint"),
+            Self::VmCall => write!(f, "vm call"),
         }
     }
 }
@@ -0,0 +1,106 @@
+use crate::alloc;
+use crate::alloc::prelude::*;
+use crate::runtime::VmErrorKind;
+
+/// A log of the nondeterministic decisions made by a virtual machine.
+///
+/// A log is recorded through [`Vm::start_recording`] and can be replayed
+/// through [`Vm::start_replay`] to force a virtual machine to make the same
+/// decisions again.
+///
+/// Awaits in a single virtual machine are resolved in program order, so the
+/// only decisions which need to be recorded are the branches taken by
+/// `select` expressions.
+///
+/// [`Vm::start_recording`]: crate::Vm::start_recording
+/// [`Vm::start_replay`]: crate::Vm::start_replay
+#[derive(Default, Debug, TryClone)]
+#[try_clone(crate)]
+pub struct ReplayLog {
+    selects: alloc::Vec<Option<usize>>,
+}
+
+impl ReplayLog {
+    /// Construct a new empty replay log.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            selects: alloc::Vec::new(),
+        }
+    }
+
+    /// Get the branches taken by each recorded `select` expression in the
+    /// order that they were taken.
+    ///
+    /// `None` indicates that the default branch was taken, because every
+    /// future in the select had already completed.
+    #[inline]
+    pub fn selects(&self) -> &[Option<usize>] {
+        &self.selects
+    }
+
+    /// Get the number of decisions in the log.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.selects.len()
+    }
+
+    /// Test if the log is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.selects.is_empty()
+    }
+}
+
+/// The recording or replay state of a virtual machine.
+#[derive(Debug)]
+pub(crate) enum VmReplay {
+    /// Neither recording nor replaying.
+    None,
+    /// Decisions are being recorded into the log.
+    Record(ReplayLog),
+    /// Decisions are being replayed from the log, starting at `index`.
+    Replay { log: ReplayLog, index: usize },
+}
+
+impl VmReplay {
+    /// Record that a select took the given branch.
+    #[inline]
+    pub(crate) fn record_select(&mut self, branch: Option<usize>) -> alloc::Result<()> {
+        if let Self::Record(log) = self {
+            log.selects.try_push(branch)?;
+        }
+
+        Ok(())
+    }
+
+    /// Get the next select decision to replay, or `None` if the virtual
+    /// machine isn't replaying.
+    ///
+    /// Errors if the log has been exhausted.
+    #[inline]
+    pub(crate) fn next_select(&mut self) -> Option<Result<Option<usize>, VmErrorKind>> {
+        let Self::Replay { log, index } = self else {
+            return None;
+        };
+
+        let Some(&branch) = log.selects.get(*index) else {
+            return Some(Err(VmErrorKind::ReplayDiverged { index: *index }));
+        };
+
+        *index += 1;
+        Some(Ok(branch))
+    }
+
+    /// Construct an error indicating that execution diverged from the last
+    /// replayed decision.
+    #[inline]
+    pub(crate) fn diverged(&self) -> VmErrorKind {
+        let index = match self {
+            Self::Replay { index, .. } => index.saturating_sub(1),
+            _ => 0,
+        };
+
+        VmErrorKind::ReplayDiverged { index }
+    }
+}
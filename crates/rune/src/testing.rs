@@ -0,0 +1,3 @@
+//! Utilities for testing rune.
+
+pub(crate) mod counting_alloc;
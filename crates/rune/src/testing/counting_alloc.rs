@@ -0,0 +1,80 @@
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::Cell;
+
+use std::alloc::System;
+
+#[global_allocator]
+static GLOBAL: CountingGlobal = CountingGlobal;
+
+std::thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn allocations() -> usize {
+    ALLOCATIONS.try_with(Cell::get).unwrap_or_default()
+}
+
+fn count_allocation() {
+    // NB: Allocations made while the thread is being torn down are not
+    // counted.
+    _ = ALLOCATIONS.try_with(|c| c.set(c.get().wrapping_add(1)));
+}
+
+/// The global allocator used by tests, which counts the allocations made on
+/// each thread.
+struct CountingGlobal;
+
+unsafe impl GlobalAlloc for CountingGlobal {
+    #[inline]
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        System.alloc(layout)
+    }
+
+    #[inline]
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        System.alloc_zeroed(layout)
+    }
+
+    #[inline]
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    #[inline]
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count_allocation();
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Counts the number of allocations made by the current thread since it was
+/// constructed or last reset.
+///
+/// Since this observes the global allocator, it also counts allocations made
+/// through [`Global`], where growing an allocation makes a new one.
+///
+/// [`Global`]: crate::alloc::alloc::Global
+pub(crate) struct CountingAlloc {
+    start: usize,
+}
+
+impl CountingAlloc {
+    /// Start counting allocations made by the current thread.
+    pub(crate) fn new() -> Self {
+        Self {
+            start: allocations(),
+        }
+    }
+
+    /// The number of allocations made.
+    pub(crate) fn allocations(&self) -> usize {
+        allocations().wrapping_sub(self.start)
+    }
+
+    /// Reset the count.
+    pub(crate) fn reset(&mut self) {
+        self.start = allocations();
+    }
+}
@@ -464,6 +464,8 @@ mod compiler_visibility;
 #[cfg(not(miri))]
 mod compiler_warnings;
 #[cfg(not(miri))]
+mod context_items;
+#[cfg(not(miri))]
 mod continue_;
 #[cfg(not(miri))]
 mod core_macros;
@@ -487,10 +489,14 @@ mod external_generic;
 mod external_match;
 #[cfg(not(miri))]
 mod external_ops;
+#[cfg(not(miri))]
+mod futures;
 mod function_guardedargs;
 #[cfg(not(miri))]
 mod getter_setter;
 #[cfg(not(miri))]
+mod homogeneous_fmt;
+#[cfg(not(miri))]
 mod iterator;
 #[cfg(not(miri))]
 mod macros;
@@ -527,26 +533,106 @@ mod vm_assign_exprs;
 #[cfg(not(miri))]
 mod vm_async_block;
 #[cfg(not(miri))]
+mod vm_block_on_call;
+#[cfg(not(miri))]
 mod vm_blocks;
 #[cfg(not(miri))]
+mod vm_breakpoint;
+#[cfg(not(miri))]
+mod vm_budget_handler;
+#[cfg(not(miri))]
+mod vm_call_and_capture;
+#[cfg(not(miri))]
+mod vm_call_function;
+#[cfg(not(miri))]
+mod vm_call_hash;
+#[cfg(not(miri))]
+mod vm_call_mock;
+#[cfg(not(miri))]
+mod vm_capture_backtrace;
+#[cfg(not(miri))]
 mod vm_closures;
 #[cfg(not(miri))]
 mod vm_const_exprs;
 #[cfg(not(miri))]
+mod vm_current_instruction;
+#[cfg(not(miri))]
+mod vm_div_by_zero;
+#[cfg(not(miri))]
+mod vm_drop_set;
+#[cfg(not(miri))]
+mod vm_drop_tracer;
+#[cfg(not(miri))]
+mod vm_dump_stack;
+#[cfg(not(miri))]
+mod vm_dynamic_field_handler;
+#[cfg(not(miri))]
 mod vm_early_termination;
 #[cfg(not(miri))]
+mod vm_error_ip;
+#[cfg(not(miri))]
+mod vm_execute_checked;
+#[cfg(not(miri))]
+mod vm_execute_counting;
+#[cfg(not(miri))]
+mod vm_execute_with_captures;
+#[cfg(not(miri))]
 mod vm_function;
 #[cfg(not(miri))]
 mod vm_function_pointers;
 #[cfg(not(miri))]
+mod vm_function_profiler;
+#[cfg(not(miri))]
 mod vm_general;
 #[cfg(not(miri))]
+mod vm_generator_iter;
+#[cfg(not(miri))]
+mod vm_generator_resume;
+#[cfg(not(miri))]
+mod vm_is_async_function;
+#[cfg(not(miri))]
 mod vm_literals;
 #[cfg(not(miri))]
+mod vm_match_tracer;
+#[cfg(not(miri))]
+mod vm_max_allocation;
+#[cfg(not(miri))]
+mod vm_max_live_elements;
+#[cfg(not(miri))]
+mod vm_max_string_len;
+#[cfg(not(miri))]
+mod vm_missing_function_handler;
+#[cfg(not(miri))]
 mod vm_not_used;
 #[cfg(not(miri))]
+mod vm_object_insertion_order;
+#[cfg(not(miri))]
+mod vm_outcome;
+#[cfg(not(miri))]
+mod vm_overflow_mode;
+#[cfg(not(miri))]
+mod vm_prepared_call;
+#[cfg(not(miri))]
+mod vm_protocol_fallback;
+#[cfg(not(miri))]
+mod vm_read_only;
+#[cfg(not(miri))]
+mod vm_replay;
+#[cfg(not(miri))]
 mod vm_result;
 #[cfg(not(miri))]
+mod vm_resume_with_await_result;
+#[cfg(not(miri))]
+mod vm_return_balance;
+#[cfg(not(miri))]
+mod vm_select_pending;
+#[cfg(not(miri))]
+mod vm_slice_args;
+#[cfg(not(miri))]
+mod vm_strict_conditions;
+#[cfg(not(miri))]
+mod vm_string_concat_reserve;
+#[cfg(not(miri))]
 mod vm_test_from_value_derive;
 #[cfg(not(miri))]
 mod vm_test_imports;
@@ -557,6 +643,14 @@ mod vm_test_linked_list;
 #[cfg(not(miri))]
 mod vm_test_mod;
 #[cfg(not(miri))]
+mod vm_trace_writer;
+#[cfg(not(miri))]
 mod vm_try;
 #[cfg(not(miri))]
+mod vm_vec_get;
+#[cfg(not(miri))]
+mod vm_visit_instructions;
+#[cfg(not(miri))]
+mod vm_yield_handler;
+#[cfg(not(miri))]
 mod wildcard_imports;
@@ -0,0 +1,30 @@
+prelude!();
+
+#[test]
+fn context_items() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = context.runtime()?;
+
+    let (_, max) = context
+        .items()
+        .find(|(item, _)| *item == rune::item!(::std::i64::max))
+        .expect("missing std::i64::max");
+
+    // Items which are functions can be looked up in the runtime context.
+    assert!(runtime.function(&max).is_some());
+
+    let (_, drop) = context
+        .items()
+        .find(|(item, _)| *item == rune::item!(::std::mem::drop))
+        .expect("missing std::mem::drop");
+
+    assert_eq!(drop, Hash::type_hash(rune::item!(::std::mem::drop)));
+    assert!(runtime.function(&drop).is_some());
+
+    assert!(context
+        .items()
+        .any(|(item, _)| item == rune::item!(::std::i64)));
+
+    assert!(Context::new().items().next().is_none());
+    Ok(())
+}
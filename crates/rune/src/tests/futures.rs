@@ -0,0 +1,36 @@
+//! Futures shared by tests which exercise asynchronous execution.
+
+prelude!();
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context as TaskContext, Poll};
+
+/// A function whose future never completes.
+#[rune::function]
+pub(crate) async fn pending() -> i64 {
+    core::future::pending().await
+}
+
+/// A future which wakes itself up once before completing.
+pub(crate) struct YieldNow(bool);
+
+impl YieldNow {
+    pub(crate) fn new() -> Self {
+        Self(false)
+    }
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
@@ -0,0 +1,152 @@
+prelude!();
+
+use crate::alloc;
+use crate::runtime::{
+    to_value, CallResultOnly, DynArgs, EnvProtocolCaller, Formatter, Object, Protocol,
+    ProtocolCaller, ProtocolFn, Unit, Value, VmError,
+};
+
+/// A protocol caller which counts how many times a protocol function is
+/// looked up.
+struct CountingCaller {
+    lookups: usize,
+}
+
+impl ProtocolCaller for CountingCaller {
+    fn try_call_protocol_fn(
+        &mut self,
+        protocol: &'static Protocol,
+        target: Value,
+        args: &mut dyn DynArgs,
+    ) -> Result<CallResultOnly<Value>, VmError> {
+        self.lookups += 1;
+        EnvProtocolCaller.try_call_protocol_fn(protocol, target, args)
+    }
+
+    fn resolve_protocol_fn(
+        &mut self,
+        protocol: &'static Protocol,
+        type_hash: Hash,
+    ) -> Result<Option<ProtocolFn>, VmError> {
+        self.lookups += 1;
+        EnvProtocolCaller.resolve_protocol_fn(protocol, type_hash)
+    }
+
+    fn call_resolved_protocol_fn(
+        &mut self,
+        protocol_fn: &ProtocolFn,
+        target: Value,
+        args: &mut dyn DynArgs,
+    ) -> Result<Value, VmError> {
+        EnvProtocolCaller.call_resolved_protocol_fn(protocol_fn, target, args)
+    }
+}
+
+fn format(
+    f: impl FnOnce(&mut Formatter, &mut CountingCaller) -> Result<(), VmError>,
+) -> Result<(String, usize)> {
+    let mut caller = CountingCaller { lookups: 0 };
+    let mut out = alloc::String::new();
+    Formatter::format_with(&mut out, |fmt| f(fmt, &mut caller))?;
+    Ok((String::from(out.as_str()), caller.lookups))
+}
+
+#[test]
+fn homogeneous_fmt() -> Result<()> {
+    let (debug, object, mixed): (String, String, String) = rune! {
+        let values = [];
+        let object = #{};
+
+        for n in 0..1000 {
+            values.push(format!("value {}", n));
+        }
+
+        object.value = "value";
+
+        // Adding a value of a different type means every element has its
+        // protocol function looked up individually.
+        let mixed = values.clone();
+        mixed.push(0);
+
+        (format!("{:?}", values), format!("{:?}", object), format!("{:?}", mixed))
+    };
+
+    let expected = (0..1000)
+        .map(|n| format!("{:?}", format!("value {n}")))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    assert_eq!(debug, format!("[{expected}]"));
+    assert_eq!(mixed, format!("[{expected}, 0]"));
+    assert_eq!(object, "{\"value\": \"value\"}");
+    Ok(())
+}
+
+#[test]
+fn homogeneous_fmt_lookups() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+    let unit = Arc::try_new(Unit::default())?;
+    let vm = Vm::new(runtime, unit);
+
+    let mut values = Vec::new();
+    let mut object = Object::with_insertion_order(10)?;
+
+    for n in 0..10 {
+        let key = alloc::String::try_from(format!("key {n}"))?;
+        let value = to_value(alloc::String::try_from(format!("value {n}"))?)?;
+        values.push(value.clone());
+        object.insert(key, value)?;
+    }
+
+    let mut mixed = values.clone();
+    mixed.push(to_value(0i64)?);
+
+    let integers = (0..10i64).map(to_value).collect::<Result<Vec<_>, _>>()?;
+
+    vm.with(|| -> Result<()> {
+        let (debug, lookups) =
+            format(|f, caller| crate::runtime::Vec::debug_fmt_with(&values, f, caller))?;
+        assert_eq!(lookups, 1);
+
+        let (display, lookups) =
+            format(|f, caller| crate::runtime::Vec::display_fmt_with(&values, f, caller))?;
+        assert_eq!(lookups, 1);
+
+        let (slow, lookups) =
+            format(|f, caller| crate::runtime::Vec::debug_fmt_with(&mixed, f, caller))?;
+        assert_eq!(lookups, 10);
+
+        // Inline values are formatted without looking up a protocol function.
+        let (inline, lookups) =
+            format(|f, caller| crate::runtime::Vec::debug_fmt_with(&integers, f, caller))?;
+        assert_eq!(lookups, 0);
+        assert_eq!(inline, "[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]");
+
+        let (object_debug, lookups) = format(|f, caller| object.debug_fmt_with(f, caller))?;
+        assert_eq!(lookups, 1);
+
+        let expected = (0..10)
+            .map(|n| format!("{:?}", format!("value {n}")))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        assert_eq!(debug, format!("[{expected}]"));
+        assert_eq!(slow, format!("[{expected}, 0]"));
+
+        let expected = (0..10)
+            .map(|n| format!("value {n}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        assert_eq!(display, format!("[{expected}]"));
+
+        let expected = (0..10)
+            .map(|n| format!("{:?}: {:?}", format!("key {n}"), format!("value {n}")))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        assert_eq!(object_debug, format!("{{{expected}}}"));
+        Ok(())
+    })
+}
@@ -73,3 +73,37 @@ fn test_get_const_nested() -> Result<()> {
     );
     Ok(())
 }
+
+#[test]
+fn test_vm_constant() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub const NAME = "rune";
+            pub const ANSWER = 40 + 2;
+
+            pub mod inner {
+                pub const FLAGS = (true, false);
+            }
+        },
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let vm = Vm::new(runtime, unit);
+
+    let name: String = from_value(vm.constant(["NAME"])?.context("missing NAME")?)?;
+    assert_eq!(name, "rune");
+
+    let answer: i64 = from_value(vm.constant(["ANSWER"])?.context("missing ANSWER")?)?;
+    assert_eq!(answer, 42);
+
+    let flags: (bool, bool) =
+        from_value(vm.constant(["inner", "FLAGS"])?.context("missing FLAGS")?)?;
+    assert_eq!(flags, (true, false));
+
+    assert!(vm.constant(["MISSING"])?.is_none());
+    Ok(())
+}
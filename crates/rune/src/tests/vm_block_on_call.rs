@@ -0,0 +1,68 @@
+prelude!();
+
+use crate::tests::futures::{pending, YieldNow};
+
+/// A function which yields once before producing its argument.
+#[rune::function]
+async fn yield_now(value: i64) -> i64 {
+    YieldNow::new().await;
+    value
+}
+
+#[test]
+fn block_on_call() -> Result<()> {
+    let mut module = Module::new();
+    module.function_meta(pending)?;
+    module.function_meta(yield_now)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            async fn add(a, b) {
+                a + b
+            }
+
+            pub async fn sync(a, b) {
+                add(a, b).await
+            }
+
+            pub async fn yielding(a) {
+                yield_now(a).await * 2
+            }
+
+            pub async fn blocking() {
+                pending().await
+            }
+
+            pub fn not_async() {
+                42
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    let output: i64 = from_value(vm.block_on_call(["sync"], (1i64, 2i64))?)?;
+    assert_eq!(output, 3);
+
+    let output: i64 = from_value(vm.block_on_call(["yielding"], (21i64,))?)?;
+    assert_eq!(output, 42);
+
+    let error = vm.block_on_call(["blocking"], ()).unwrap_err();
+    assert_matches!(error.into_kind(), VmErrorKind::WouldBlock);
+
+    // Functions which aren't async are called as-is, like with `async_call`.
+    let output: i64 = from_value(vm.block_on_call(["not_async"], ())?)?;
+    assert_eq!(output, 42);
+
+    // The virtual machine can be used after a failed call.
+    let output: i64 = from_value(vm.block_on_call(["sync"], (3i64, 4i64))?)?;
+    assert_eq!(output, 7);
+    Ok(())
+}
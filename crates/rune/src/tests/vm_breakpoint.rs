@@ -0,0 +1,66 @@
+prelude!();
+
+use core::ops::ControlFlow;
+
+use crate::runtime::{inst, VmBreak};
+
+#[test]
+fn breakpoint_in_loop() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main() {
+                let n = 0;
+
+                for i in 0..10 {
+                    n += i;
+                }
+
+                n
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    let mut breakpoint = None;
+
+    vm.visit_instructions(|ip, inst| {
+        if let inst::Kind::AssignArithmetic { rhs, .. } = inst.kind {
+            breakpoint = Some((ip, rhs));
+            return ControlFlow::Break(());
+        }
+
+        ControlFlow::Continue(())
+    })?;
+
+    let (ip, rhs) = breakpoint.context("missing assignment in loop body")?;
+
+    let mut execution = vm.execute(["main"], ())?;
+    let vm = execution.vm_mut();
+
+    let mut iterations = Vec::new();
+
+    let output = loop {
+        match vm.run_until(ip)? {
+            VmBreak::Breakpoint(at) => {
+                assert_eq!(at, ip);
+                assert_eq!(vm.ip(), ip);
+                iterations.push(vm.stack().at(rhs).as_signed()?);
+            }
+            VmBreak::Exited(addr) => {
+                let addr = addr.context("missing return value")?;
+                break vm.stack().at(addr).as_signed()?;
+            }
+            halt => panic!("unexpected halt {halt:?}"),
+        }
+    };
+
+    assert_eq!(iterations, (0..10).collect::<Vec<i64>>());
+    assert_eq!(output, 45);
+    Ok(())
+}
@@ -0,0 +1,102 @@
+prelude!();
+
+use crate::runtime::{budget, BudgetAction, BudgetHandler};
+
+#[test]
+fn budget_handler() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main() {
+                let n = 0;
+
+                for i in 0..100 {
+                    n += i;
+                }
+
+                n
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    // Without extending the budget, execution is limited.
+    let mut handler = BudgetHandler::new(|_| BudgetAction::Halt);
+    let result = budget::with(10, || vm.call_with_diagnostics(["main"], (), &mut handler)).call();
+    assert!(result.is_err());
+
+    let mut calls = Vec::new();
+
+    let mut handler = BudgetHandler::new(|instructions_run| {
+        calls.push(instructions_run);
+        BudgetAction::Extend(10)
+    });
+
+    let output =
+        budget::with(10, || vm.call_with_diagnostics(["main"], (), &mut handler)).call()?;
+    let output: i64 = from_value(output)?;
+    assert_eq!(output, 4950);
+
+    // The handler is consulted each time the budget runs out, and execution
+    // continues within the same run.
+    assert!(!calls.is_empty());
+
+    for (n, instructions_run) in calls.iter().enumerate() {
+        assert_eq!(*instructions_run, (n + 1) * 10);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn combined_budget_handlers() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main() {
+                let n = 0;
+
+                for i in 0..100 {
+                    n += i;
+                }
+
+                n
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    let mut halted = 0;
+    let mut extended = 0;
+
+    // The second handler is consulted since the first one halts.
+    let mut handlers = (
+        BudgetHandler::new(|_| {
+            halted += 1;
+            BudgetAction::Halt
+        }),
+        BudgetHandler::new(|_| {
+            extended += 1;
+            BudgetAction::Extend(10)
+        }),
+    );
+
+    let output =
+        budget::with(10, || vm.call_with_diagnostics(["main"], (), &mut handlers)).call()?;
+    let output: i64 = from_value(output)?;
+    assert_eq!(output, 4950);
+
+    assert!(extended > 0);
+    assert_eq!(halted, extended);
+    Ok(())
+}
@@ -0,0 +1,110 @@
+prelude!();
+
+use rust_alloc::boxed::Box;
+
+use crate::runtime::{to_value, VmError};
+
+#[test]
+fn call_and_capture() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            fn add(a, b) {
+                a + b
+            }
+
+            pub fn main(n) {
+                let values = [];
+
+                for i in 0..n {
+                    values.push(add(i, 1));
+                }
+
+                let total = 0;
+
+                for value in values {
+                    total += value;
+                }
+
+                total
+            }
+
+            pub fn unit() {
+                let a = 1;
+                let b = (a, a);
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    let (output, residual) = vm.call_and_capture(["main"], (10i64,))?;
+    let output: i64 = from_value(output)?;
+    assert_eq!(output, 55);
+    assert!(residual.is_empty());
+
+    let (output, residual) = vm.call_and_capture(["unit"], ())?;
+    let () = from_value(output)?;
+    assert!(residual.is_empty());
+
+    // The stack is cleared once the call has completed.
+    assert_eq!(vm.stack().len(), 0);
+    Ok(())
+}
+
+#[test]
+fn call_and_capture_imbalance() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            fn inner() {
+                leak() + 1
+            }
+
+            pub fn main() {
+                let a = leak();
+                a + inner()
+            }
+
+            fn leak() {
+                1
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    // Leave a stray value behind in the frame of the caller.
+    vm.set_call_mock(
+        Hash::type_hash(["leak"]),
+        Some(Box::new(
+            |stack: &mut Stack, _: Address, _: usize, out: Output| {
+                stack.store(out, to_value(1i64)?)?;
+                stack.push(to_value(42i64)?)?;
+                Ok::<_, VmError>(())
+            },
+        )),
+    )?;
+
+    let (output, residual) = vm.call_and_capture(["main"], ())?;
+    let output: i64 = from_value(output)?;
+    assert_eq!(output, 3);
+
+    // One value is left behind by `inner`, and one by `main`.
+    let residual = residual
+        .into_iter()
+        .map(from_value::<i64>)
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(residual, [42, 42]);
+
+    assert_eq!(vm.stack().len(), 0);
+    Ok(())
+}
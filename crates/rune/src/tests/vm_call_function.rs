@@ -0,0 +1,56 @@
+prelude!();
+
+use crate::runtime::Function;
+
+fn vm() -> Result<Vm> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            fn add(a, b) {
+                a + b
+            }
+
+            pub fn functions(n) {
+                (add, |a| a * n, std::cmp::max)
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    Ok(Vm::new(runtime, unit))
+}
+
+#[test]
+fn call_function() -> Result<()> {
+    let mut vm = vm()?;
+
+    let (add, mul, max): (Function, Function, Function) =
+        from_value(vm.call(["functions"], (10i64,))?)?;
+
+    let output: i64 = from_value(vm.call_function(&add, (1i64, 2i64))?)?;
+    assert_eq!(output, 3);
+
+    let output: i64 = from_value(vm.call_function(&mul, (4i64,))?)?;
+    assert_eq!(output, 40);
+
+    let output: i64 = from_value(vm.call_function(&max, (4i64, 7i64))?)?;
+    assert_eq!(output, 7);
+
+    let error = vm.call_function(&add, (1i64,)).unwrap_err();
+
+    assert_eq!(
+        error.into_kind(),
+        VmErrorKind::BadArgumentCount {
+            actual: 1,
+            expected: 2
+        }
+    );
+
+    // The vm is still usable after a failed call.
+    let output: i64 = from_value(vm.call_function(&add, (2i64, 3i64))?)?;
+    assert_eq!(output, 5);
+    Ok(())
+}
@@ -0,0 +1,34 @@
+prelude!();
+
+#[test]
+fn call_hash() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main(a, b) {
+                a * 10 + b
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    let hash = Hash::type_hash(["main"]);
+
+    for n in 0..4i64 {
+        let expected: i64 = from_value(vm.call(["main"], (n, 2i64))?)?;
+        let actual: i64 = from_value(vm.call_hash(hash, (n, 2i64))?)?;
+        assert_eq!(actual, expected);
+
+        let executed: i64 = from_value(vm.execute_hash(hash, (n, 2i64))?.complete()?)?;
+        assert_eq!(executed, expected);
+    }
+
+    let error = vm.call_hash(Hash::type_hash(["missing"]), ()).unwrap_err();
+    assert_matches!(error.into_kind(), VmErrorKind::MissingEntryHash { .. });
+    Ok(())
+}
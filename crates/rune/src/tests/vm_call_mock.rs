@@ -0,0 +1,114 @@
+prelude!();
+
+use rust_alloc::boxed::Box;
+
+use crate::runtime::{inst, to_value, VmError, VmErrorKind};
+
+#[test]
+fn call_mock() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            fn fetch(n) {
+                n * 2
+            }
+
+            pub fn main() {
+                fetch(1) + fetch(2)
+            }
+
+            pub fn indirect() {
+                let f = fetch;
+                f(10)
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    let output: i64 = from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, 6);
+
+    vm.set_call_mock(
+        Hash::type_hash(["fetch"]),
+        Some(Box::new(
+            |stack: &mut Stack, addr: Address, args: usize, out: Output| {
+                let [n] = stack.slice_at(addr, args)? else {
+                    return Err(VmError::new(VmErrorKind::BadArgumentCount {
+                        actual: args,
+                        expected: 1,
+                    }));
+                };
+
+                let n = from_value::<i64>(n.clone())?;
+                stack.store(out, to_value(n + 100)?)?;
+                Ok::<_, VmError>(())
+            },
+        )),
+    )?;
+
+    // The stub replaces the function in the unit.
+    let output: i64 = from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, 203);
+
+    // Calls through function values are mocked as well.
+    let output: i64 = from_value(vm.call(["indirect"], ())?)?;
+    assert_eq!(output, 110);
+
+    vm.set_call_mock(Hash::type_hash(["fetch"]), None)?;
+
+    let output: i64 = from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, 6);
+    Ok(())
+}
+
+#[test]
+fn call_mock_compiled_callee() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn sum(n) {
+                if n == 0 {
+                    0
+                } else {
+                    n + sum(n - 1)
+                }
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+
+    // The recursive call is compiled after the function it calls, so it's
+    // encoded as a direct call to its offset.
+    assert!(unit
+        .iter_instructions()
+        .any(|(_, inst)| matches!(inst.kind, inst::Kind::CallOffset { .. })));
+
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    let output: i64 = from_value(vm.call(["sum"], (3i64,))?)?;
+    assert_eq!(output, 6);
+
+    vm.set_call_mock(
+        Hash::type_hash(["sum"]),
+        Some(Box::new(
+            |stack: &mut Stack, _: Address, _: usize, out: Output| {
+                stack.store(out, to_value(1000i64)?)?;
+                Ok::<_, VmError>(())
+            },
+        )),
+    )?;
+
+    // The entry call is not mocked, but the recursive call is.
+    let output: i64 = from_value(vm.call(["sum"], (3i64,))?)?;
+    assert_eq!(output, 1003);
+    Ok(())
+}
@@ -0,0 +1,58 @@
+prelude!();
+
+fn vm() -> Result<Vm> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            fn inner(value) {
+                value + "not a number"
+            }
+
+            fn middle(value) {
+                let output = inner(value);
+                output
+            }
+
+            pub fn main(value) {
+                let output = middle(value);
+                output
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    Ok(Vm::new(runtime, unit))
+}
+
+#[test]
+fn capture_backtrace() -> Result<()> {
+    let mut vm = vm()?;
+
+    let error = vm.call(["main"], (1i64,)).unwrap_err();
+    assert!(error.backtrace().is_empty());
+
+    vm.set_capture_backtrace(true);
+
+    let error = vm.call(["main"], (1i64,)).unwrap_err();
+    let backtrace = error.backtrace();
+
+    // The innermost frame is where the error was raised, followed by the
+    // frames of `middle` and `main` which made the calls.
+    assert_eq!(backtrace.len(), 3);
+
+    for frame in backtrace {
+        assert!(Arc::ptr_eq(&frame.unit, vm.unit()));
+    }
+
+    let ips = backtrace.iter().map(|frame| frame.ip).collect::<Vec<_>>();
+    assert!(ips[0] != ips[1] && ips[1] != ips[2] && ips[0] != ips[2]);
+
+    vm.set_capture_backtrace(false);
+
+    let error = vm.call(["main"], (1i64,)).unwrap_err();
+    assert!(error.backtrace().is_empty());
+    Ok(())
+}
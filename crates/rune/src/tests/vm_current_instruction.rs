@@ -0,0 +1,60 @@
+prelude!();
+
+use core::ops::ControlFlow;
+
+use crate::runtime::{inst, VmBreak};
+
+#[test]
+fn current_instruction() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main() {
+                let n = 0;
+
+                for i in 0..10 {
+                    n += i;
+                }
+
+                n
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    let mut breakpoint = None;
+
+    vm.visit_instructions(|ip, inst| {
+        if let inst::Kind::AssignArithmetic { .. } = inst.kind {
+            breakpoint = Some(ip);
+            return ControlFlow::Break(());
+        }
+
+        ControlFlow::Continue(())
+    })?;
+
+    let ip = breakpoint.context("missing assignment in loop body")?;
+
+    let mut execution = vm.execute(["main"], ())?;
+    let vm = execution.vm_mut();
+
+    let first = vm
+        .current_instruction()?
+        .context("missing first instruction")?;
+    assert!(!matches!(first.kind, inst::Kind::AssignArithmetic { .. }));
+
+    let halt = vm.run_until(ip)?;
+    assert!(matches!(halt, VmBreak::Breakpoint(at) if at == ip));
+
+    // The instruction at the breakpoint is the next one to be executed.
+    let next = vm
+        .current_instruction()?
+        .context("missing next instruction")?;
+    assert!(matches!(next.kind, inst::Kind::AssignArithmetic { .. }));
+    Ok(())
+}
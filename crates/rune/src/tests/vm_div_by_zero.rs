@@ -0,0 +1,87 @@
+prelude!();
+
+use crate::runtime::DivByZero;
+
+#[test]
+fn div_by_zero() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn div(a, b) {
+                a / b
+            }
+
+            pub fn rem(a, b) {
+                a % b
+            }
+
+            pub fn div_assign(a, b) {
+                a /= b;
+                a
+            }
+
+            pub fn rem_assign(a, b) {
+                a %= b;
+                a
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    assert_eq!(vm.div_by_zero(), DivByZero::Error);
+
+    for name in ["div", "rem", "div_assign", "rem_assign"] {
+        let error = vm.call([name], (5i64, 0i64)).unwrap_err();
+        assert_matches!(error.into_kind(), VmErrorKind::DivideByZero);
+
+        let error = vm.call([name], (5u64, 0u64)).unwrap_err();
+        assert_matches!(error.into_kind(), VmErrorKind::DivideByZero);
+    }
+
+    let cases: [(DivByZero, [i64; 4], [u64; 2]); 2] = [
+        (DivByZero::Zero, [0, 0, 0, 0], [0, 0]),
+        (
+            DivByZero::Saturate,
+            [i64::MAX, i64::MIN, 0, 0],
+            [u64::MAX, 0],
+        ),
+    ];
+
+    for (mode, [div, neg_div, zero_div, rem], [unsigned_div, unsigned_rem]) in cases {
+        vm.set_div_by_zero(mode);
+
+        for (div_name, rem_name) in [("div", "rem"), ("div_assign", "rem_assign")] {
+            let output: i64 = from_value(vm.call([div_name], (5i64, 0i64))?)?;
+            assert_eq!(output, div, "{mode:?}: 5 / 0");
+
+            let output: i64 = from_value(vm.call([div_name], (-5i64, 0i64))?)?;
+            assert_eq!(output, neg_div, "{mode:?}: -5 / 0");
+
+            let output: i64 = from_value(vm.call([div_name], (0i64, 0i64))?)?;
+            assert_eq!(output, zero_div, "{mode:?}: 0 / 0");
+
+            let output: i64 = from_value(vm.call([rem_name], (5i64, 0i64))?)?;
+            assert_eq!(output, rem, "{mode:?}: 5 % 0");
+
+            let output: u64 = from_value(vm.call([div_name], (5u64, 0u64))?)?;
+            assert_eq!(output, unsigned_div, "{mode:?}: 5u64 / 0");
+
+            let output: u64 = from_value(vm.call([rem_name], (5u64, 0u64))?)?;
+            assert_eq!(output, unsigned_rem, "{mode:?}: 5u64 % 0");
+        }
+
+        // Division by a non-zero value is unaffected.
+        let output: i64 = from_value(vm.call(["div"], (7i64, 2i64))?)?;
+        assert_eq!(output, 3);
+
+        let output: i64 = from_value(vm.call(["rem"], (7i64, 2i64))?)?;
+        assert_eq!(output, 1);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+prelude!();
+
+use core::ops::ControlFlow;
+
+use crate::runtime::inst;
+
+#[test]
+fn drop_set() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main() {
+                let total = 0;
+
+                for value in [1, 2, 3] {
+                    total += value;
+                }
+
+                total
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let vm = Vm::new(runtime, unit);
+
+    let mut sets = Vec::new();
+
+    vm.visit_instructions(|_, inst| {
+        if let inst::Kind::Drop { set } = inst.kind {
+            sets.push(set);
+        }
+
+        ControlFlow::Continue(())
+    })?;
+
+    assert!(!sets.is_empty());
+
+    for set in sets {
+        let Some(addresses) = vm.drop_set(set) else {
+            panic!("missing drop set {set}");
+        };
+
+        assert!(!addresses.is_empty());
+    }
+
+    assert!(vm.drop_set(usize::MAX).is_none());
+    Ok(())
+}
@@ -0,0 +1,62 @@
+prelude!();
+
+use core::ops::ControlFlow;
+
+use crate::runtime::{inst, DropTracer};
+
+#[test]
+fn drop_tracer() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main() {
+                let a = {
+                    let values = [1, 2, 3];
+                    values.len()
+                };
+
+                let b = {
+                    let values = [4, 5];
+                    values.len()
+                };
+
+                a + b
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    // NB: The script neither branches nor loops, so every drop set is
+    // executed exactly once and in the order it appears.
+    let mut expected = Vec::new();
+
+    vm.visit_instructions(|_, inst| {
+        if let inst::Kind::Drop { set } = inst.kind {
+            expected.push(set);
+        }
+
+        ControlFlow::Continue(())
+    })?;
+
+    let expected = expected
+        .into_iter()
+        .map(|set| vm.drop_set(set).context("missing drop set"))
+        .collect::<Result<Vec<_>>>()?
+        .concat();
+
+    let mut tracer = DropTracer::new();
+    let output: i64 = from_value(vm.call_with_diagnostics(["main"], (), &mut tracer)?)?;
+    assert_eq!(output, 5);
+
+    assert!(!expected.is_empty());
+    assert_eq!(tracer.dropped(), expected);
+
+    tracer.clear();
+    assert!(tracer.dropped().is_empty());
+    Ok(())
+}
@@ -0,0 +1,68 @@
+prelude!();
+
+use core::ops::ControlFlow;
+
+use crate::runtime::{inst, TypeInfo, TypeOf, VmBreak};
+
+#[test]
+fn dump_stack() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main(a) {
+                let b = "hello";
+                let c = [1, 2];
+                let d = a + 1;
+                d
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    let mut breakpoint = None;
+
+    vm.visit_instructions(|ip, inst| {
+        if let inst::Kind::Return { .. } = inst.kind {
+            breakpoint = Some(ip);
+            return ControlFlow::Break(());
+        }
+
+        ControlFlow::Continue(())
+    })?;
+
+    let ip = breakpoint.context("missing return")?;
+
+    let mut execution = vm.execute(["main"], (41i64,))?;
+    let vm = execution.vm_mut();
+
+    let VmBreak::Breakpoint(..) = vm.run_until(ip)? else {
+        panic!("expected the breakpoint to be hit");
+    };
+
+    let dump = vm.dump_stack()?;
+    assert!(dump.len() >= 4, "expected at least four slots: {dump:?}");
+
+    let (addr, type_info, debug) = &dump[0];
+    assert_eq!(*addr, Address::ZERO);
+    assert_eq!(*type_info, i64::type_info());
+    assert_eq!(debug.as_str(), "41");
+
+    let find = |expected: &TypeInfo| {
+        dump.iter()
+            .find(|(_, type_info, _)| type_info == expected)
+            .map(|(_, _, debug)| debug.as_str())
+    };
+
+    assert_eq!(find(&crate::alloc::String::type_info()), Some("\"hello\""));
+    assert_eq!(find(&crate::runtime::Vec::type_info()), Some("[1, 2]"));
+
+    assert!(dump
+        .iter()
+        .any(|(_, type_info, debug)| *type_info == i64::type_info() && debug.as_str() == "42"));
+    Ok(())
+}
@@ -0,0 +1,84 @@
+prelude!();
+
+use rust_alloc::boxed::Box;
+
+use crate::runtime::{to_value, VmError};
+
+#[derive(Any, Debug)]
+#[rune(item = ::module)]
+struct Celsius {
+    #[rune(get)]
+    degrees: f64,
+}
+
+#[test]
+fn dynamic_field_handler() -> Result<()> {
+    let mut m = Module::with_crate("module")?;
+    m.ty::<Celsius>()?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(m)?;
+
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn degrees(value) {
+                value.degrees
+            }
+
+            pub fn fahrenheit(value) {
+                value.fahrenheit
+            }
+
+            pub fn kelvin(value) {
+                value.kelvin
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    vm.set_dynamic_field_handler(
+        Celsius::HASH,
+        Some(Box::new(|value: &Value, field: &str| {
+            if field != "fahrenheit" {
+                return Ok(None);
+            }
+
+            let value = value.borrow_ref::<Celsius>()?;
+            Ok::<_, VmError>(Some(to_value(value.degrees * 1.8 + 32.0)?))
+        })),
+    )?;
+
+    let output: f64 = from_value(vm.call(["degrees"], (Celsius { degrees: 100.0 },))?)?;
+    assert_eq!(output, 100.0);
+
+    let output: f64 = from_value(vm.call(["fahrenheit"], (Celsius { degrees: 100.0 },))?)?;
+    assert_eq!(output, 212.0);
+
+    // Fields which are not serviced by the handler still error.
+    let error = vm
+        .call(["kelvin"], (Celsius { degrees: 100.0 },))
+        .unwrap_err();
+
+    assert_matches!(
+        error.into_kind(),
+        VmErrorKind::UnsupportedObjectSlotIndexGet { .. }
+    );
+
+    vm.set_dynamic_field_handler(Celsius::HASH, None)?;
+
+    let error = vm
+        .call(["fahrenheit"], (Celsius { degrees: 100.0 },))
+        .unwrap_err();
+
+    assert_matches!(
+        error.into_kind(),
+        VmErrorKind::UnsupportedObjectSlotIndexGet { .. }
+    );
+
+    Ok(())
+}
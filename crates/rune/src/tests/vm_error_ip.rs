@@ -0,0 +1,55 @@
+prelude!();
+
+use core::ops::ControlFlow;
+
+use crate::runtime::inst;
+
+#[test]
+fn error_ip() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main(a, b) {
+                a / b
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    let mut div = None;
+
+    vm.visit_instructions(|ip, inst| {
+        if let inst::Kind::Arithmetic {
+            op: inst::InstArithmeticOp::Div,
+            ..
+        } = inst.kind
+        {
+            div = Some(ip);
+            return ControlFlow::Break(());
+        }
+
+        ControlFlow::Continue(())
+    })?;
+
+    let div = div.context("missing division")?;
+
+    assert_eq!(vm.error_ip(), None);
+
+    let output: i64 = from_value(vm.call(["main"], (10i64, 2i64))?)?;
+    assert_eq!(output, 5);
+    assert_eq!(vm.error_ip(), None);
+
+    assert!(vm.call(["main"], (10i64, 0i64)).is_err());
+    assert_eq!(vm.error_ip(), Some(div));
+    assert_ne!(vm.ip(), div);
+
+    let output: i64 = from_value(vm.call(["main"], (9i64, 3i64))?)?;
+    assert_eq!(output, 3);
+    assert_eq!(vm.error_ip(), None);
+    Ok(())
+}
@@ -0,0 +1,61 @@
+prelude!();
+
+use crate::runtime::TypeHash;
+
+#[test]
+fn execute_checked() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main(a, b) {
+                a + b
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    let expected = [i64::HASH, i64::HASH];
+
+    let mut output = vm.execute_checked(["main"], (1i64, 2i64), &expected)?;
+    let output: i64 = from_value(output.complete()?)?;
+    assert_eq!(output, 3);
+
+    let Some(error) = vm.execute_checked(["main"], (1i64, "two"), &expected).err() else {
+        panic!("expected an error");
+    };
+
+    let VmErrorKind::ArgumentTypeMismatch {
+        position,
+        expected: expected_hash,
+        actual,
+    } = error.into_kind()
+    else {
+        panic!("expected an argument type mismatch");
+    };
+
+    assert_eq!(position, 1);
+    assert_eq!(expected_hash, i64::HASH);
+    assert_eq!(actual.type_hash(), String::HASH);
+
+    let Some(error) = vm
+        .execute_checked(["main"], (1i64, 2i64), &expected[..1])
+        .err()
+    else {
+        panic!("expected an error");
+    };
+
+    assert_matches!(
+        error.into_kind(),
+        VmErrorKind::BadArgumentCount {
+            actual: 2,
+            expected: 1
+        }
+    );
+
+    Ok(())
+}
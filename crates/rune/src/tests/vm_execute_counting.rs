@@ -0,0 +1,43 @@
+prelude!();
+
+use crate::runtime::{budget, VmError};
+
+#[test]
+fn execute_counting() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main(a, b) {
+                a * b + a
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    // The function allocates its stack, performs two operations and returns.
+    for _ in 0..4 {
+        let (output, count) = vm.execute_counting(["main"], (3i64, 4i64))?;
+        let output: i64 = from_value(output)?;
+        assert_eq!(output, 15);
+        assert_eq!(count, 4);
+    }
+
+    // A budget set by the caller is consumed independently of counting.
+    let (count, remaining) = budget::with(100, || {
+        let (_, count) = vm.execute_counting(["main"], (3i64, 4i64))?;
+        Ok::<_, VmError>((count, budget::acquire().remaining()))
+    })
+    .call()?;
+
+    assert_eq!(count, 4);
+    assert_eq!(remaining, 96);
+
+    // The budget is unlimited again after counting.
+    assert_eq!(budget::acquire().remaining(), usize::MAX);
+    Ok(())
+}
@@ -0,0 +1,75 @@
+prelude!();
+
+fn vm() -> Result<Vm> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main() {
+                let a = 1;
+                let b = 2;
+                |c| a * 100 + b * 10 + c
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    Ok(Vm::new(runtime, unit))
+}
+
+#[test]
+fn execute_with_captures() -> Result<()> {
+    let mut vm = vm()?;
+
+    let closure: Function = from_value(vm.call(["main"], ())?)?;
+    let output: i64 = closure.call((3i64,))?;
+    assert_eq!(output, 123);
+
+    let output = vm
+        .execute_with_captures(closure.type_hash(), (4i64, 5i64), (6i64,))?
+        .complete()?;
+    let output: i64 = from_value(output)?;
+    assert_eq!(output, 456);
+    Ok(())
+}
+
+#[test]
+fn execute_with_captures_errors() -> Result<()> {
+    let mut vm = vm()?;
+
+    let closure: Function = from_value(vm.call(["main"], ())?)?;
+
+    let Err(error) = vm.execute_with_captures(closure.type_hash(), (4i64,), (6i64,)) else {
+        panic!("expected bad environment count");
+    };
+
+    assert_eq!(
+        error.into_kind(),
+        VmErrorKind::BadEnvironmentCount {
+            actual: 1,
+            expected: 2
+        }
+    );
+
+    let Err(error) = vm.execute_with_captures(closure.type_hash(), (4i64, 5i64), ()) else {
+        panic!("expected bad argument count");
+    };
+
+    assert_eq!(
+        error.into_kind(),
+        VmErrorKind::BadArgumentCount {
+            actual: 1,
+            expected: 2
+        }
+    );
+
+    // Functions which are not closures are not supported.
+    let Err(error) = vm.execute_with_captures(["main"], (), ()) else {
+        panic!("expected missing function");
+    };
+
+    assert_matches!(error.into_kind(), VmErrorKind::MissingFunction { .. });
+    Ok(())
+}
@@ -0,0 +1,68 @@
+prelude!();
+
+use crate::runtime::FunctionProfiler;
+
+#[test]
+fn function_profiler() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    // NB: `g` is declared after `main` so that it is called by hash, while
+    // `f` is called directly by offset. Both function pointers and closures
+    // are called through a value.
+    let mut sources = sources! {
+        entry => {
+            fn f(n) {
+                n + 1
+            }
+
+            pub fn main() {
+                let n = 0;
+
+                while n < 10 {
+                    n = f(n);
+                }
+
+                g();
+                g();
+
+                let h = g;
+                h();
+
+                let c = |n| n + 1;
+                n = c(n);
+                n = c(n);
+                n
+            }
+
+            fn g() {
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    let mut profiler = FunctionProfiler::new();
+    let output = vm.call_with_diagnostics(["main"], (), &mut profiler)?;
+    assert_eq!(from_value::<i64>(output)?, 12);
+
+    let f = Hash::type_hash(["f"]);
+    let g = Hash::type_hash(["g"]);
+
+    assert_eq!(profiler.count(f), 10);
+    assert_eq!(profiler.count(g), 3);
+
+    let report = profiler.report()?;
+    assert_eq!(report.len(), 3);
+    assert_eq!(report[..2], [(f, 10), (g, 3)]);
+
+    // The closure doesn't have a name, so it's only identified by its count.
+    assert_eq!(report[2].1, 2);
+
+    profiler.clear();
+    assert_eq!(profiler.count(f), 0);
+    assert!(profiler.report()?.is_empty());
+    Ok(())
+}
@@ -0,0 +1,90 @@
+prelude!();
+
+use crate::runtime::{budget, Generator};
+
+fn vm() -> Result<Vm> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            fn numbers() {
+                for n in 0..5 {
+                    yield n;
+                }
+            }
+
+            fn failing() {
+                yield 1;
+                yield 1 + "not a number";
+                yield 2;
+            }
+
+            pub fn main() {
+                numbers()
+            }
+
+            pub fn error() {
+                failing()
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    Ok(Vm::new(runtime, unit))
+}
+
+#[test]
+fn generator_iter() -> Result<()> {
+    let mut vm = vm()?;
+
+    let generator: Generator = from_value(vm.call(["main"], ())?)?;
+
+    let values = generator
+        .into_iter()
+        .map(|value| Ok(from_value::<i64>(value?)?))
+        .collect::<Result<Vec<_>>>()?;
+
+    assert_eq!(values, [0, 1, 2, 3, 4]);
+    Ok(())
+}
+
+#[test]
+fn generator_iter_error() -> Result<()> {
+    let mut vm = vm()?;
+
+    let generator: Generator = from_value(vm.call(["error"], ())?)?;
+    let mut iter = generator.into_iter();
+
+    // NB: `Iter` has an inherent `next` method used by scripts, so the
+    // `Iterator` implementation is called explicitly.
+    let value: i64 = from_value(Iterator::next(&mut iter).expect("first value")?)?;
+    assert_eq!(value, 1);
+
+    assert!(Iterator::next(&mut iter).expect("error").is_err());
+
+    // The iterator is fused after an error.
+    assert!(Iterator::next(&mut iter).is_none());
+    assert!(Iterator::next(&mut iter).is_none());
+    Ok(())
+}
+
+#[test]
+fn generator_iter_limited() -> Result<()> {
+    let mut vm = vm()?;
+
+    let generator: Generator = from_value(vm.call(["main"], ())?)?;
+    let mut iter = generator.into_iter();
+
+    // Running out of budget doesn't fuse the iterator.
+    let result = budget::with(1, || Iterator::next(&mut iter)).call();
+    assert!(result.expect("limited").is_err());
+
+    let values = iter
+        .map(|value| Ok(from_value::<i64>(value?)?))
+        .collect::<Result<Vec<_>>>()?;
+
+    assert_eq!(values, [0, 1, 2, 3, 4]);
+    Ok(())
+}
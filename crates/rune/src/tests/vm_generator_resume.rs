@@ -0,0 +1,58 @@
+prelude!();
+
+use crate::runtime::{to_value, Generator, GeneratorState};
+
+#[test]
+fn generator_resume() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            fn add(x) {
+                let resumed = yield x;
+
+                while resumed != 0 {
+                    resumed = yield x + resumed;
+                }
+
+                x
+            }
+
+            pub fn main(x) {
+                add(x)
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    let mut generator: Generator = from_value(vm.call(["main"], (10i64,))?)?;
+
+    // The value passed when starting the generator is ignored.
+    let GeneratorState::Yielded(value) = generator.resume(to_value(100i64)?)? else {
+        panic!("expected generator to yield");
+    };
+
+    assert_eq!(from_value::<i64>(value)?, 10);
+
+    for n in [1i64, 5, 32] {
+        let GeneratorState::Yielded(value) = generator.resume(to_value(n)?)? else {
+            panic!("expected generator to yield");
+        };
+
+        assert_eq!(from_value::<i64>(value)?, 10 + n);
+    }
+
+    let GeneratorState::Complete(value) = generator.resume(to_value(0i64)?)? else {
+        panic!("expected generator to complete");
+    };
+
+    assert_eq!(from_value::<i64>(value)?, 10);
+
+    let error = generator.resume(to_value(1i64)?).unwrap_err();
+    assert_matches!(error.into_kind(), VmErrorKind::GeneratorComplete);
+    Ok(())
+}
@@ -0,0 +1,44 @@
+prelude!();
+
+#[test]
+fn is_async_function() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            struct Empty;
+
+            pub fn regular() {
+                1
+            }
+
+            pub async fn asynchronous() {
+                2
+            }
+
+            pub fn generator() {
+                yield 3;
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let vm = Vm::new(runtime, unit);
+
+    assert_eq!(vm.is_async_function(["regular"])?, Some(false));
+    assert_eq!(vm.is_async_function(["asynchronous"])?, Some(true));
+    assert_eq!(vm.is_async_function(["generator"])?, Some(false));
+    assert_eq!(vm.is_async_function(["Empty"])?, Some(false));
+    assert_eq!(vm.is_async_function(rune::item!(::std::i64::max))?, None);
+
+    let error = vm.is_async_function(["missing"]).unwrap_err();
+
+    assert_matches!(
+        error.into_kind(),
+        VmErrorKind::MissingContextFunction { .. }
+    );
+
+    Ok(())
+}
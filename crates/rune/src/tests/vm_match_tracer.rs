@@ -0,0 +1,78 @@
+prelude!();
+
+use crate::runtime::MatchTracer;
+
+#[test]
+fn match_tracer() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            fn classify(value) {
+                match value {
+                    [a, b] => 1,
+                    #{a} => 2,
+                    Some(a) => 3,
+                    _ => 4,
+                }
+            }
+
+            pub fn main(n) {
+                let value = if n == 0 {
+                    [1, 2]
+                } else if n == 1 {
+                    #{a: 1}
+                } else if n == 2 {
+                    Some(1)
+                } else {
+                    42
+                };
+
+                classify(value)
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    let mut tracer = MatchTracer::new();
+    let output: i64 = from_value(vm.call_with_diagnostics(["main"], (3i64,), &mut tracer)?)?;
+    assert_eq!(output, 4);
+
+    // Every arm except the wildcard is tried in order.
+    let ips = tracer
+        .outcomes()
+        .iter()
+        .map(|&(ip, matched)| {
+            assert!(!matched);
+            ip
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(ips.len(), 3);
+    assert!(ips.windows(2).all(|w| w[0] < w[1]));
+
+    for n in 0..3i64 {
+        tracer.clear();
+
+        let output: i64 = from_value(vm.call_with_diagnostics(["main"], (n,), &mut tracer)?)?;
+        assert_eq!(output, n + 1);
+
+        let mut expected = ips[..n as usize]
+            .iter()
+            .map(|&ip| (ip, false))
+            .collect::<Vec<_>>();
+
+        expected.push((ips[n as usize], true));
+        assert_eq!(tracer.outcomes(), &expected[..]);
+    }
+
+    // Without diagnostics matches are not traced.
+    tracer.clear();
+    vm.call(["main"], (0i64,))?;
+    assert!(tracer.outcomes().is_empty());
+    Ok(())
+}
@@ -0,0 +1,68 @@
+prelude!();
+
+use core::mem::size_of;
+
+use crate::runtime::VmError;
+
+fn vm() -> Result<Vm> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new(
+        "entry",
+        r#"
+        pub fn small() {
+            [1, 2]
+        }
+
+        pub fn large(a) {
+            [a, a, a, a, a, a, a, a, a, a, a, a, a, a, a, a]
+        }
+
+        pub fn template(a) {
+            `${a}${a}${a}${a}`
+        }
+        "#,
+    )?)?;
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    Ok(Vm::new(runtime, unit))
+}
+
+fn assert_limit_exceeded(error: VmError, expected: usize) {
+    let VmErrorKind::AllocationLimitExceeded { requested, limit } = error.into_kind() else {
+        panic!("expected allocation limit to be exceeded");
+    };
+
+    assert_eq!(limit, expected);
+    assert!(requested > limit);
+}
+
+#[test]
+fn max_allocation() -> Result<()> {
+    let mut vm = vm()?;
+    assert_eq!(vm.max_allocation(), None);
+
+    vm.call(["large"], (1i64,))?;
+
+    let limit = size_of::<Value>() * 8;
+    vm.set_max_allocation(Some(limit));
+    assert_eq!(vm.max_allocation(), Some(limit));
+
+    let output: Vec<i64> = from_value(vm.call(["small"], ())?)?;
+    assert_eq!(output, [1, 2]);
+
+    let error = vm.call(["large"], (1i64,)).unwrap_err();
+    assert_limit_exceeded(error, limit);
+
+    // Strings which grow beyond the limit while formatting error as well.
+    let fragment = "x".repeat(limit);
+    let error = vm.call(["template"], (fragment.as_str(),)).unwrap_err();
+    assert_limit_exceeded(error, limit);
+
+    vm.set_max_allocation(None);
+    vm.call(["large"], (1i64,))?;
+    Ok(())
+}
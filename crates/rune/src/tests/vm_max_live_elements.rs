@@ -0,0 +1,64 @@
+prelude!();
+
+#[test]
+fn max_live_elements() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main(n) {
+                let sum = 0;
+
+                for i in 0..n {
+                    let vec = [i, i];
+                    let tuple = (i, i, i);
+                    let object = #{a: i};
+                    sum += vec[0] + tuple.0 + object.a;
+                }
+
+                sum
+            }
+
+            pub fn object() {
+                #{a: 1, b: 2}
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    assert_eq!(vm.max_live_elements(), None);
+
+    vm.call(["main"], (100i64,))?;
+    assert_eq!(vm.live_elements(), 600);
+
+    vm.set_max_live_elements(Some(60));
+    assert_eq!(vm.max_live_elements(), Some(60));
+
+    // Ten iterations construct exactly sixty elements.
+    vm.call(["main"], (10i64,))?;
+    assert_eq!(vm.live_elements(), 60);
+
+    // The count is reset for each call.
+    vm.call(["main"], (10i64,))?;
+
+    let error = vm.call(["main"], (11i64,)).unwrap_err();
+
+    assert_matches!(
+        error.into_kind(),
+        VmErrorKind::ElementLimitExceeded { limit: 60 }
+    );
+
+    vm.set_max_live_elements(Some(1));
+    let error = vm.call(["object"], ()).unwrap_err();
+
+    assert_matches!(
+        error.into_kind(),
+        VmErrorKind::ElementLimitExceeded { limit: 1 }
+    );
+
+    Ok(())
+}
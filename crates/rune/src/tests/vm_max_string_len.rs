@@ -0,0 +1,68 @@
+prelude!();
+
+#[test]
+fn max_string_len() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new(
+        "entry",
+        r#"
+        pub fn repeat(n) {
+            let s = "";
+
+            for _ in 0..n {
+                s = `${s}x`;
+            }
+
+            s
+        }
+
+        pub fn literal(a) {
+            `hello ${a}`
+        }
+        "#,
+    )?)?;
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+    assert_eq!(vm.max_string_len(), None);
+
+    let output: String = from_value(vm.call(["repeat"], (100i64,))?)?;
+    assert_eq!(output.len(), 100);
+
+    vm.set_max_string_len(Some(10));
+    assert_eq!(vm.max_string_len(), Some(10));
+
+    let output: String = from_value(vm.call(["repeat"], (10i64,))?)?;
+    assert_eq!(output, "x".repeat(10));
+
+    // The eleventh iteration is the first one to exceed the limit.
+    let error = vm.call(["repeat"], (11i64,)).unwrap_err();
+
+    assert_matches!(
+        error.into_kind(),
+        VmErrorKind::StringLengthExceeded {
+            length: 11,
+            limit: 10
+        }
+    );
+
+    vm.set_max_string_len(Some(4));
+    let error = vm.call(["literal"], (1i64,)).unwrap_err();
+
+    assert_matches!(
+        error.into_kind(),
+        VmErrorKind::StringLengthExceeded {
+            length: 6,
+            limit: 4
+        }
+    );
+
+    vm.set_max_string_len(None);
+    let output: String = from_value(vm.call(["literal"], (1i64,))?)?;
+    assert_eq!(output, "hello 1");
+    Ok(())
+}
@@ -0,0 +1,65 @@
+prelude!();
+
+use rust_alloc::boxed::Box;
+
+use crate::runtime::{to_value, VmError};
+
+#[test]
+fn missing_function_handler() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main() {
+                "hi".shout(2)
+            }
+
+            pub fn other() {
+                "hi".whisper()
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    vm.set_missing_function_handler(Some(Box::new(
+        |hash: Hash, stack: &mut Stack, addr: Address, args: usize, out: Output| {
+            if hash != Hash::ident("shout") {
+                return Ok(false);
+            }
+
+            let [this, times] = stack.slice_at(addr, args)? else {
+                return Ok(false);
+            };
+
+            let this = from_value::<String>(this.clone())?;
+            let times = from_value::<usize>(times.clone())?;
+            let value = to_value(this.to_uppercase().repeat(times))?;
+            stack.store(out, value)?;
+            Ok::<_, VmError>(true)
+        },
+    )));
+
+    let output: String = from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, "HIHI");
+
+    // Calls which are not serviced by the handler still error.
+    let error = vm.call(["other"], ()).unwrap_err();
+    assert_matches!(
+        error.into_kind(),
+        VmErrorKind::MissingInstanceFunction { .. }
+    );
+
+    vm.set_missing_function_handler(None);
+
+    let error = vm.call(["main"], ()).unwrap_err();
+    assert_matches!(
+        error.into_kind(),
+        VmErrorKind::MissingInstanceFunction { .. }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+prelude!();
+
+use crate::runtime::Object;
+
+#[test]
+fn object_insertion_order() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn display() {
+                let object = #{zeta: 1, alpha: 2, mu: 3, beta: 4};
+                format!("{object:?}")
+            }
+
+            pub fn modified() {
+                let object = #{zeta: 1, alpha: 2, mu: 3};
+                object.remove("alpha");
+                object.omega = 4;
+                object.zeta = 5;
+                object
+            }
+
+            pub fn keys() {
+                let object = #{zeta: 1, alpha: 2, mu: 3};
+                object.remove("alpha");
+                object.omega = 4;
+                object.keys().collect::<Vec>()
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    assert!(!vm.object_preserve_insertion_order());
+
+    let object: Object = from_value(vm.call(["modified"], ())?)?;
+    assert!(!object.tracks_insertion_order());
+
+    vm.set_object_preserve_insertion_order(true);
+    assert!(vm.object_preserve_insertion_order());
+
+    let output: String = from_value(vm.call(["display"], ())?)?;
+
+    let positions = ["zeta", "alpha", "mu", "beta"]
+        .map(|key| output.find(key).expect("key should be displayed"));
+
+    assert!(positions.is_sorted(), "{output}");
+
+    let object: Object = from_value(vm.call(["modified"], ())?)?;
+    assert!(object.tracks_insertion_order());
+
+    let keys = object
+        .iter_ordered()
+        .map(|(key, _)| key.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(keys, ["zeta", "mu", "omega"]);
+
+    let values = object
+        .iter_ordered()
+        .map(|(_, value)| value.as_integer::<i64>())
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(values, [5, 3, 4]);
+
+    let keys: Vec<String> = from_value(vm.call(["keys"], ())?)?;
+    assert_eq!(keys, ["zeta", "mu", "omega"]);
+
+    Ok(())
+}
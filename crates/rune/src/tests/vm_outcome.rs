@@ -0,0 +1,55 @@
+prelude!();
+
+use crate::runtime::budget;
+
+#[test]
+fn outcome_predicates() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main() {
+                yield 1;
+                2
+            }
+
+            pub fn looping() {
+                let n = 0;
+
+                for i in 0..100 {
+                    n += i;
+                }
+
+                n
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    let mut execution = vm.execute(["main"], ())?;
+
+    let outcome = execution.resume().complete()?;
+    assert!(outcome.is_yielded());
+    assert!(!outcome.is_limited());
+    assert!(outcome.as_complete().is_none());
+
+    let outcome = execution.resume().complete()?;
+    assert!(!outcome.is_yielded());
+    assert!(!outcome.is_limited());
+    let value = outcome
+        .as_complete()
+        .context("expected a completed outcome")?;
+    assert_eq!(value.as_signed()?, 2);
+
+    let mut execution = vm.execute(["looping"], ())?;
+
+    let outcome = budget::with(10, || execution.resume().complete()).call()?;
+    assert!(outcome.is_limited());
+    assert!(!outcome.is_yielded());
+    assert!(outcome.as_complete().is_none());
+    Ok(())
+}
@@ -0,0 +1,97 @@
+prelude!();
+
+use crate::runtime::{OverflowMode, VmError};
+
+fn vm() -> Result<Vm> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn add(a) {
+                a + 1
+            }
+
+            pub fn add_assign(a) {
+                a += 1;
+                a
+            }
+
+            pub fn sub(a) {
+                a - 1
+            }
+
+            pub fn div(a, b) {
+                a / b
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    Ok(Vm::new(runtime, unit))
+}
+
+fn call(vm: &mut Vm, name: &str, a: i64) -> Result<i64, VmError> {
+    Ok(from_value(vm.call([name], (a,))?)?)
+}
+
+#[test]
+fn checked() -> Result<()> {
+    let mut vm = vm()?;
+    assert_eq!(vm.integer_overflow_mode(), OverflowMode::Checked);
+
+    for name in ["add", "add_assign"] {
+        let error = call(&mut vm, name, i64::MAX).unwrap_err();
+        assert_matches!(error.into_kind(), VmErrorKind::Overflow);
+    }
+
+    let error = call(&mut vm, "sub", i64::MIN).unwrap_err();
+    assert_matches!(error.into_kind(), VmErrorKind::Underflow);
+    Ok(())
+}
+
+#[test]
+fn wrapping() -> Result<()> {
+    let mut vm = vm()?;
+    vm.set_integer_overflow_mode(OverflowMode::Wrapping);
+
+    assert_eq!(call(&mut vm, "add", i64::MAX)?, i64::MIN);
+    assert_eq!(call(&mut vm, "add_assign", i64::MAX)?, i64::MIN);
+    assert_eq!(call(&mut vm, "sub", i64::MIN)?, i64::MAX);
+
+    let output: i64 = from_value(vm.call(["div"], (i64::MIN, -1i64))?)?;
+    assert_eq!(output, i64::MIN);
+    Ok(())
+}
+
+#[test]
+fn saturating() -> Result<()> {
+    let mut vm = vm()?;
+    vm.set_integer_overflow_mode(OverflowMode::Saturating);
+
+    assert_eq!(call(&mut vm, "add", i64::MAX)?, i64::MAX);
+    assert_eq!(call(&mut vm, "add_assign", i64::MAX)?, i64::MAX);
+    assert_eq!(call(&mut vm, "sub", i64::MIN)?, i64::MIN);
+
+    let output: i64 = from_value(vm.call(["div"], (i64::MIN, -1i64))?)?;
+    assert_eq!(output, i64::MAX);
+    Ok(())
+}
+
+#[test]
+fn divide_by_zero() -> Result<()> {
+    let mut vm = vm()?;
+
+    for mode in [
+        OverflowMode::Checked,
+        OverflowMode::Wrapping,
+        OverflowMode::Saturating,
+    ] {
+        vm.set_integer_overflow_mode(mode);
+        let error = vm.call(["div"], (1i64, 0i64)).unwrap_err();
+        assert_matches!(error.into_kind(), VmErrorKind::DivideByZero);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+prelude!();
+
+use crate::runtime::to_value;
+
+const ITERATIONS: i64 = 100;
+
+fn vm() -> Result<Vm> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn step(position, velocity) {
+                position + velocity
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    Ok(Vm::new(runtime, unit))
+}
+
+#[test]
+fn prepared_call_matches_call() -> Result<()> {
+    let mut vm = vm()?;
+    let step = vm.prepare_call(["step"])?;
+
+    assert_eq!(step.hash(), Hash::type_hash(["step"]));
+    assert_eq!(step.args(), 2);
+
+    let mut position = 0i64;
+
+    for velocity in 0..ITERATIONS {
+        position = from_value(vm.call(["step"], (position, velocity))?)?;
+    }
+
+    let mut prepared_position = 0i64;
+
+    for velocity in 0..ITERATIONS {
+        prepared_position = from_value(step.call(&mut vm, (prepared_position, velocity))?)?;
+        assert_eq!(vm.stack().len(), 0);
+    }
+
+    assert_eq!(position, prepared_position);
+    assert_eq!(position, ITERATIONS * (ITERATIONS - 1) / 2);
+    Ok(())
+}
+
+#[test]
+fn prepared_call_clears_stack() -> Result<()> {
+    let mut vm = vm()?;
+    let step = vm.prepare_call(["step"])?;
+
+    // Values left behind by something else are cleared before the call.
+    vm.stack_mut().push(to_value(42i64)?)?;
+
+    let output: i64 = from_value(step.call(&mut vm, (1i64, 2i64))?)?;
+    assert_eq!(output, 3);
+    assert_eq!(vm.stack().len(), 0);
+    Ok(())
+}
+
+#[test]
+fn prepared_call_other_unit() -> Result<()> {
+    let vm1 = vm()?;
+    let mut vm2 = vm()?;
+
+    // The function is looked up again by hash in a different unit.
+    let step = vm1.prepare_call(["step"])?;
+    let output: i64 = from_value(step.call(&mut vm2, (1i64, 2i64))?)?;
+    assert_eq!(output, 3);
+    Ok(())
+}
+
+#[test]
+fn prepared_call_errors() -> Result<()> {
+    let mut vm = vm()?;
+
+    let error = vm.prepare_call(["missing"]).unwrap_err();
+    assert_matches!(error.into_kind(), VmErrorKind::MissingEntry { .. });
+
+    let step = vm.prepare_call(["step"])?;
+    let error = step.call(&mut vm, (1i64,)).unwrap_err();
+
+    assert_eq!(
+        error.into_kind(),
+        VmErrorKind::BadArgumentCount {
+            actual: 1,
+            expected: 2
+        }
+    );
+
+    // The vm is still usable after a failed call.
+    let output: i64 = from_value(step.call(&mut vm, (1i64, 2i64))?)?;
+    assert_eq!(output, 3);
+    Ok(())
+}
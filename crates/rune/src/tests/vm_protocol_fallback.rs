@@ -0,0 +1,62 @@
+prelude!();
+
+use crate::runtime::{ProtocolFallback, ProtocolFallbackCounter};
+
+#[derive(Any, Debug)]
+#[rune(item = ::module)]
+struct Meters(i64);
+
+impl Meters {
+    fn add(&self, other: &Self) -> Self {
+        Self(self.0 + other.0)
+    }
+}
+
+#[test]
+fn protocol_fallback() -> Result<()> {
+    let mut m = Module::with_crate("module")?;
+    m.ty::<Meters>()?;
+    m.associated_function(&Protocol::ADD, Meters::add)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(m)?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main(a, b) {
+                a + b
+            }
+
+            pub fn integers() {
+                let n = 1 + 2;
+                n * 3
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    let mut counter = ProtocolFallbackCounter::new();
+
+    let output = vm.call_with_diagnostics(["integers"], (), &mut counter)?;
+    let output: i64 = from_value(output)?;
+    assert_eq!(output, 9);
+
+    // Integer arithmetic is handled inline.
+    assert_eq!(counter.count(ProtocolFallback::Arithmetic), 0);
+    assert_eq!(counter.total(), 0);
+
+    let output = vm.call_with_diagnostics(["main"], (Meters(1), Meters(2)), &mut counter)?;
+    let output: Meters = from_value(output)?;
+    assert_eq!(output.0, 3);
+
+    assert_eq!(counter.count(ProtocolFallback::Arithmetic), 1);
+    assert_eq!(counter.total(), 1);
+
+    counter.clear();
+    assert_eq!(counter.total(), 0);
+    Ok(())
+}
@@ -0,0 +1,88 @@
+prelude!();
+
+use crate::runtime::VmError;
+
+#[derive(Any, Debug)]
+#[rune(item = ::module)]
+struct Counter {
+    #[rune(get, set, add_assign)]
+    value: i64,
+}
+
+fn vm() -> Result<Vm> {
+    let mut m = Module::with_crate("module")?;
+    m.ty::<Counter>()?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(m)?;
+
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn set(counter) {
+                counter.value = 2;
+            }
+
+            pub fn add_assign(counter) {
+                counter.value += 1;
+            }
+
+            pub fn local() {
+                let object = #{ value: 1 };
+                object.value += 1;
+                object["value"] = object.value * 10;
+                object.value
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    Ok(Vm::new(runtime, unit))
+}
+
+fn call_mut(vm: &mut Vm, name: &str, counter: &mut Counter) -> Result<(), VmError> {
+    // SAFETY: The value is only used while the guard is alive.
+    unsafe {
+        let (value, _guard) = Value::from_mut(counter)?;
+        vm.call([name], (value,))?;
+    }
+
+    Ok(())
+}
+
+fn assert_read_only(error: VmError) {
+    let VmErrorKind::ReadOnlyViolation { target } = error.into_kind() else {
+        panic!("expected a read-only violation");
+    };
+
+    assert_eq!(target, Counter::type_info());
+}
+
+#[test]
+fn read_only() -> Result<()> {
+    let mut vm = vm()?;
+    assert!(!vm.is_read_only());
+
+    let mut counter = Counter { value: 0 };
+
+    call_mut(&mut vm, "set", &mut counter)?;
+    assert_eq!(counter.value, 2);
+    call_mut(&mut vm, "add_assign", &mut counter)?;
+    assert_eq!(counter.value, 3);
+
+    vm.set_read_only(true);
+    assert!(vm.is_read_only());
+
+    assert_read_only(call_mut(&mut vm, "set", &mut counter).unwrap_err());
+    assert_read_only(call_mut(&mut vm, "add_assign", &mut counter).unwrap_err());
+    assert_eq!(counter.value, 3);
+
+    // Values owned by the virtual machine can still be mutated.
+    vm.call(["add_assign"], (Counter { value: 3 },))?;
+
+    let output: i64 = from_value(vm.call(["local"], ())?)?;
+    assert_eq!(output, 20);
+    Ok(())
+}
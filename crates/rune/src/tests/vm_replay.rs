@@ -0,0 +1,131 @@
+prelude!();
+
+use crate::runtime::ReplayLog;
+use crate::tests::futures::pending;
+
+#[test]
+fn replay_select() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            async fn foo(n) {
+                n
+            }
+
+            pub async fn main() {
+                let a = foo(1);
+                let b = foo(2);
+                let order = [];
+
+                loop {
+                    let value = select {
+                        value = a => value,
+                        value = b => value,
+                    };
+
+                    if let () = value {
+                        break;
+                    }
+
+                    order.push(value);
+                }
+
+                order
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    vm.start_recording();
+    let recorded: Vec<i64> = from_value(block_on(vm.async_call(["main"], ()))?)?;
+    let log = vm.stop_recording();
+
+    // Two selects taking each branch once, followed by one taking the default
+    // branch once every future has completed.
+    assert_eq!(log.len(), 3);
+    assert_eq!(log.selects()[2], None);
+
+    let mut taken = log.selects()[..2].to_vec();
+    taken.sort();
+    assert_eq!(taken, [Some(0), Some(1)]);
+
+    let expected = log.selects()[..2]
+        .iter()
+        .map(|branch| match branch {
+            Some(0) => 1,
+            _ => 2,
+        })
+        .collect::<Vec<i64>>();
+
+    assert_eq!(recorded, expected);
+
+    vm.start_replay(log.try_clone()?);
+    let replayed: Vec<i64> = from_value(block_on(vm.async_call(["main"], ()))?)?;
+    assert_eq!(replayed, recorded);
+
+    // Replaying an exhausted log diverges at the first select.
+    vm.start_replay(ReplayLog::new());
+    let error = block_on(vm.async_call(["main"], ())).unwrap_err();
+    assert_matches!(error.into_kind(), VmErrorKind::ReplayDiverged { index: 0 });
+
+    // Without a replay, selects are decided as they happen.
+    vm.stop_replay();
+    let output: Vec<i64> = from_value(block_on(vm.async_call(["main"], ()))?)?;
+    assert_eq!(output.len(), 2);
+    Ok(())
+}
+
+#[test]
+fn replay_overrides_branch() -> Result<()> {
+    let mut module = Module::new();
+    module.function_meta(pending)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            async fn foo(n) {
+                n
+            }
+
+            pub async fn main(stall) {
+                let a = if stall { pending() } else { foo(1) };
+                let b = foo(2);
+
+                select {
+                    value = a => value,
+                    value = b => value,
+                }
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    // The first branch never completes, so the second one is recorded.
+    vm.start_recording();
+    let recorded: i64 = from_value(block_on(vm.async_call(["main"], (true,)))?)?;
+    let log = vm.stop_recording();
+    assert_eq!(recorded, 2);
+    assert_eq!(log.selects(), [Some(1)]);
+
+    // When both branches are ready the first one is taken live.
+    let live: i64 = from_value(block_on(vm.async_call(["main"], (false,)))?)?;
+    assert_eq!(live, 1);
+
+    // Replaying forces the recorded branch instead.
+    vm.start_replay(log);
+    let replayed: i64 = from_value(block_on(vm.async_call(["main"], (false,)))?)?;
+    assert_eq!(replayed, 2);
+    assert_ne!(replayed, live);
+    Ok(())
+}
@@ -0,0 +1,79 @@
+prelude!();
+
+use crate::runtime::{VmHaltInfo, VmOutcome};
+use crate::tests::futures::pending;
+
+#[test]
+fn resume_with_await_result() -> Result<()> {
+    let mut module = Module::new();
+    module.function_meta(pending)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub async fn main() {
+                let a = pending().await;
+                let b = pending().await;
+                a * 10 + b
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    let mut execution = vm.execute(["main"], ())?;
+
+    // Nothing is awaited yet.
+    let error = execution
+        .resume_with_await_result(Value::from(1i64))
+        .err()
+        .expect("execution is not awaiting");
+
+    assert_matches!(
+        error.into_kind(),
+        VmErrorKind::ExpectedAwaitingExecutionState { .. }
+    );
+
+    let error = execution
+        .resume()
+        .complete()
+        .err()
+        .expect("execution is awaiting");
+
+    assert_matches!(
+        error.into_kind(),
+        VmErrorKind::Halted {
+            halt: VmHaltInfo::Awaited
+        }
+    );
+
+    let error = execution
+        .resume_with_await_result(Value::from(4i64))?
+        .complete()
+        .err()
+        .expect("execution is awaiting");
+
+    assert_matches!(
+        error.into_kind(),
+        VmErrorKind::Halted {
+            halt: VmHaltInfo::Awaited
+        }
+    );
+
+    let outcome = execution
+        .resume_with_await_result(Value::from(2i64))?
+        .complete()?;
+
+    let VmOutcome::Complete(value) = outcome else {
+        panic!("expected the execution to complete");
+    };
+
+    assert_eq!(from_value::<i64>(value)?, 42);
+    Ok(())
+}
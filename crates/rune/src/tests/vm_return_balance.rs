@@ -0,0 +1,107 @@
+//! Returning from a call frame verifies in debug builds that the frame holds
+//! no values beyond the size it was allocated with.
+
+prelude!();
+
+use rust_alloc::boxed::Box;
+
+use crate::runtime::{to_value, VmError};
+
+#[test]
+fn return_balance() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            fn fib(n) {
+                if n < 2 {
+                    return n;
+                }
+
+                fib(n - 1) + fib(n - 2)
+            }
+
+            fn nothing(a, b, c) {
+            }
+
+            pub fn main(n) {
+                let add = |a| a + n;
+                let total = 0;
+
+                for i in 0..n {
+                    nothing(i, i, i);
+                    total += add(fib(i));
+                }
+
+                total
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    for n in 0..16i64 {
+        let output: i64 = from_value(vm.call(["main"], (n,))?)?;
+        let expected = (0..n).map(|i| fib(i) + n).sum::<i64>();
+        assert_eq!(output, expected);
+    }
+
+    Ok(())
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "stack imbalance on return")]
+fn return_imbalance() {
+    // NB: Tests which should panic can't return a result.
+    leave_stray_value().unwrap();
+}
+
+#[cfg(debug_assertions)]
+fn leave_stray_value() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main() {
+                let a = leak();
+                a + 1
+            }
+
+            fn leak() {
+                1
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    // Leave a stray value behind in the frame of the caller.
+    vm.set_call_mock(
+        Hash::type_hash(["leak"]),
+        Some(Box::new(
+            |stack: &mut Stack, _: Address, _: usize, out: Output| {
+                stack.store(out, to_value(1i64)?)?;
+                stack.push(to_value(2i64)?)?;
+                Ok::<_, VmError>(())
+            },
+        )),
+    )?;
+
+    _ = vm.call(["main"], ());
+    Ok(())
+}
+
+fn fib(n: i64) -> i64 {
+    if n < 2 {
+        n
+    } else {
+        fib(n - 1) + fib(n - 2)
+    }
+}
@@ -0,0 +1,72 @@
+prelude!();
+
+use core::future::Future;
+use core::pin::pin;
+use core::task::{Context as TaskContext, Poll, Waker};
+
+use crate::runtime::VmOutcome;
+use crate::tests::futures::{pending, YieldNow};
+
+/// A function which yields once before producing its argument.
+#[rune::function]
+async fn later(value: i64) -> i64 {
+    YieldNow::new().await;
+    value
+}
+
+#[test]
+fn select_pending() -> Result<()> {
+    let mut module = Module::new();
+    module.function_meta(pending)?;
+    module.function_meta(later)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub async fn main() {
+                let a = pending();
+                let b = later(2);
+
+                select {
+                    value = a => value,
+                    value = b => value * 10,
+                }
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    let mut execution = vm.execute(["main"], ())?;
+    let mut resume = pin!(execution.resume());
+    let mut cx = TaskContext::from_waker(Waker::noop());
+
+    assert!(resume.awaited_select().is_none());
+    assert!(resume.as_mut().poll(&mut cx).is_pending());
+
+    // Neither branch has completed on the first poll.
+    let select = resume.awaited_select().context("expected a select")?;
+    assert_eq!(select.pending_len(), 2);
+
+    let mut branches = select.pending_branches().collect::<Vec<_>>();
+    branches.sort();
+    assert_eq!(branches, [0, 1]);
+
+    let Poll::Ready(outcome) = resume.as_mut().poll(&mut cx) else {
+        panic!("expected the select to complete");
+    };
+
+    let VmOutcome::Complete(value) = outcome? else {
+        panic!("expected the execution to complete");
+    };
+
+    assert_eq!(from_value::<i64>(value)?, 20);
+    assert!(resume.awaited_select().is_none());
+    Ok(())
+}
@@ -0,0 +1,63 @@
+prelude!();
+
+use crate::testing::counting_alloc::CountingAlloc;
+
+#[test]
+fn slice_args() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn sum(a, b, c) {
+                a + b + c
+            }
+
+            pub fn push(values, value) {
+                values.push(value);
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    let args = [Value::from(1i64), Value::from(2i64), Value::from(3i64)];
+
+    let output: i64 = from_value(vm.call(["sum"], &args[..])?)?;
+    assert_eq!(output, 6);
+
+    let output: i64 = from_value(vm.execute(["sum"], &args[..])?.complete()?)?;
+    assert_eq!(output, 6);
+
+    // Calling with a slice allocates no more than calling with a tuple, which
+    // pushes its values onto the stack directly.
+    let mut counting = CountingAlloc::new();
+    vm.call(["sum"], (1i64, 2i64, 3i64))?;
+    let tuple = counting.allocations();
+
+    counting.reset();
+    vm.call(["sum"], &args[..])?;
+    assert_eq!(counting.allocations(), tuple);
+
+    let error = vm.call(["sum"], &args[..2]).unwrap_err();
+
+    assert_matches!(
+        error.into_kind(),
+        VmErrorKind::BadArgumentCount {
+            actual: 2,
+            expected: 3
+        }
+    );
+
+    // Values in the slice are shared with the call rather than deeply
+    // cloned, so mutations made by the script are visible afterwards.
+    let values = crate::to_value(crate::runtime::Vec::new())?;
+    let args = [values.clone(), Value::from(42i64)];
+    vm.call(["push"], &args[..])?;
+
+    let values: Vec<i64> = from_value(values)?;
+    assert_eq!(values, [42]);
+    Ok(())
+}
@@ -0,0 +1,48 @@
+prelude!();
+
+#[test]
+fn strict_conditions() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn if_else(cond) {
+                if cond { 1 } else { 2 }
+            }
+
+            pub fn or(cond) {
+                cond || false
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    assert!(!vm.is_strict_conditions());
+
+    // A condition which isn't a boolean silently doesn't jump by default,
+    // which here skips the jump into the `if` branch.
+    let output: i64 = from_value(vm.call(["if_else"], (42i64,))?)?;
+    assert_eq!(output, 2);
+    let output: bool = from_value(vm.call(["or"], (42i64,))?)?;
+    assert!(!output);
+
+    vm.set_strict_conditions(true);
+    assert!(vm.is_strict_conditions());
+
+    let output: i64 = from_value(vm.call(["if_else"], (false,))?)?;
+    assert_eq!(output, 2);
+    let output: bool = from_value(vm.call(["or"], (true,))?)?;
+    assert!(output);
+
+    for name in ["if_else", "or"] {
+        let error = vm.call([name], (42i64,)).unwrap_err();
+
+        assert_matches!(error.into_kind(), VmErrorKind::ExpectedBoolCondition { .. });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+prelude!();
+
+use crate::testing::counting_alloc::CountingAlloc;
+
+fn vm() -> Result<Vm> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new(
+        "entry",
+        r#"
+        pub fn main(value) {
+            `<${value}>${value}<${value}>`
+        }
+        "#,
+    )?)?;
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    Ok(Vm::new(runtime, unit))
+}
+
+#[test]
+fn string_concat_reserve() -> Result<()> {
+    const FRAGMENT: &str = "0123456789";
+    const EXPECTED: &str = "<0123456789>0123456789<0123456789>";
+
+    let mut vm = vm()?;
+    assert_eq!(vm.string_concat_reserve(), 0);
+
+    // NB: The first call makes allocations which are only needed once.
+    vm.call(["main"], (FRAGMENT,))?;
+
+    let mut counting = CountingAlloc::new();
+    let output = vm.call(["main"], (FRAGMENT,))?;
+    let unreserved = counting.allocations();
+
+    let output: alloc::String = from_value(output)?;
+    assert_eq!(output.as_str(), EXPECTED);
+
+    vm.set_string_concat_reserve(FRAGMENT.len());
+    assert_eq!(vm.string_concat_reserve(), 10);
+
+    counting.reset();
+    let output = vm.call(["main"], (FRAGMENT,))?;
+    let reserved = counting.allocations();
+
+    let output: alloc::String = from_value(output)?;
+    assert_eq!(output.as_str(), EXPECTED);
+
+    // The static size hint plus 10 bytes for each of the seven operands is
+    // reserved up front, which covers the whole output without growing.
+    // Without it the buffer has to grow three times.
+    assert_eq!(output.capacity(), 4 + 10 * 7);
+    assert_eq!(unreserved - reserved, 3);
+    Ok(())
+}
@@ -0,0 +1,51 @@
+prelude!();
+
+use std::sync::Mutex;
+
+use rust_alloc::boxed::Box;
+
+#[test]
+fn trace_writer() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main(a, b) {
+                a + b
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+
+    // The function doesn't branch, so every instruction in the unit is
+    // executed once in order.
+    let expected = unit
+        .iter_instructions()
+        .map(|(ip, inst)| format!("{ip:04} = {inst}"))
+        .collect::<Vec<_>>();
+
+    assert!(!expected.is_empty());
+
+    let lines = std::sync::Arc::new(Mutex::new(Vec::new()));
+    let mut vm = Vm::new(runtime, unit);
+
+    vm.set_trace_writer(Some(Box::new({
+        let lines = lines.clone();
+        move |args| lines.lock().unwrap().push(args.to_string())
+    })));
+
+    let output: i64 = from_value(vm.call(["main"], (1i64, 2i64))?)?;
+    assert_eq!(output, 3);
+    assert_eq!(*lines.lock().unwrap(), expected);
+
+    vm.set_trace_writer(None);
+    lines.lock().unwrap().clear();
+
+    let output: i64 = from_value(vm.call(["main"], (3i64, 4i64))?)?;
+    assert_eq!(output, 7);
+    assert!(lines.lock().unwrap().is_empty());
+    Ok(())
+}
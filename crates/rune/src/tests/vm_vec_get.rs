@@ -0,0 +1,38 @@
+prelude!();
+
+#[test]
+fn vec_get() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn vec() {
+                [1, "two", 3]
+            }
+
+            pub fn tuple() {
+                (1, 2, 3)
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    let vec = vm.call(["vec"], ())?;
+
+    let first: i64 = from_value(vm.vec_get(&vec, 0)?.expect("in bounds"))?;
+    assert_eq!(first, 1);
+
+    let second: String = from_value(vm.vec_get(&vec, 1)?.expect("in bounds"))?;
+    assert_eq!(second, "two");
+
+    assert!(vm.vec_get(&vec, 3)?.is_none());
+    assert!(vm.vec_get(&vec, usize::MAX)?.is_none());
+
+    let tuple = vm.call(["tuple"], ())?;
+    assert!(vm.vec_get(&tuple, 0).is_err());
+    Ok(())
+}
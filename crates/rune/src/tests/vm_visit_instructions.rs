@@ -0,0 +1,88 @@
+prelude!();
+
+use core::ops::ControlFlow;
+
+use crate::runtime::inst;
+
+fn compile(mut sources: Sources) -> Result<Vm> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    Ok(Vm::new(runtime, unit))
+}
+
+fn find_await(vm: &Vm) -> Result<Option<usize>> {
+    let mut found = None;
+
+    vm.visit_instructions(|ip, inst| {
+        if let inst::Kind::Await { .. } = inst.kind {
+            found = Some(ip);
+            return ControlFlow::Break(());
+        }
+
+        ControlFlow::Continue(())
+    })?;
+
+    Ok(found)
+}
+
+#[test]
+fn detect_await() -> Result<()> {
+    let vm = compile(sources! {
+        entry => {
+            async fn value() {
+                42
+            }
+
+            pub async fn main() {
+                value().await
+            }
+        }
+    })?;
+
+    assert!(find_await(&vm)?.is_some());
+
+    let vm = compile(sources! {
+        entry => {
+            pub fn main() {
+                42
+            }
+        }
+    })?;
+
+    assert!(find_await(&vm)?.is_none());
+    Ok(())
+}
+
+#[test]
+fn visit_early_termination() -> Result<()> {
+    let vm = compile(sources! {
+        entry => {
+            pub fn main(a, b) {
+                let c = a + b;
+                c * 2
+            }
+        }
+    })?;
+
+    let mut all = Vec::new();
+
+    vm.visit_instructions(|ip, _| {
+        all.push(ip);
+        ControlFlow::Continue(())
+    })?;
+
+    assert!(all.len() > 1);
+    assert!(all.windows(2).all(|w| w[0] < w[1]));
+
+    let mut visited = 0;
+
+    vm.visit_instructions(|_, _| {
+        visited += 1;
+        ControlFlow::Break(())
+    })?;
+
+    assert_eq!(visited, 1);
+    Ok(())
+}
@@ -0,0 +1,90 @@
+prelude!();
+
+use std::sync::Mutex;
+
+use rust_alloc::boxed::Box;
+
+use crate::runtime::to_value;
+use crate::runtime::{VmError, VmHaltInfo};
+
+#[test]
+fn yield_handler() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main(n) {
+                let total = 0;
+
+                for i in 0..n {
+                    let value = yield i;
+                    total += value;
+                }
+
+                yield;
+                total
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let unit = Arc::try_new(unit)?;
+    let mut vm = Vm::new(runtime, unit);
+
+    let yielded = std::sync::Arc::new(Mutex::new(Vec::new()));
+    let handler_yielded = yielded.clone();
+
+    vm.set_yield_handler(Some(Box::new(move |value: Option<Value>| {
+        let value = match value {
+            Some(value) => Some(from_value::<i64>(value)?),
+            None => None,
+        };
+
+        handler_yielded.lock().unwrap().push(value);
+        Ok(to_value(10i64)?)
+    })));
+
+    let output: i64 = from_value(vm.call(["main"], (4i64,))?)?;
+    assert_eq!(output, 40);
+
+    let yielded = yielded.lock().unwrap();
+    assert_eq!(*yielded, [Some(0), Some(1), Some(2), Some(3), None]);
+    Ok(())
+}
+
+#[test]
+fn yield_handler_error() -> Result<()> {
+    let mut sources = sources! {
+        entry => {
+            pub fn main() {
+                yield 1;
+                yield 2;
+            }
+        }
+    };
+
+    let unit = prepare(&mut sources).build()?;
+    let mut vm = Vm::without_runtime(Arc::try_new(unit)?)?;
+
+    vm.set_yield_handler(Some(Box::new(|_: Option<Value>| {
+        Err(VmError::from(VmErrorKind::GeneratorComplete))
+    })));
+
+    let error = vm.call(["main"], ()).unwrap_err();
+    assert_matches!(error.into_kind(), VmErrorKind::GeneratorComplete);
+
+    // Without a handler, yielding halts the virtual machine.
+    vm.set_yield_handler(None);
+
+    let error = vm.call(["main"], ()).unwrap_err();
+
+    assert_matches!(
+        error.into_kind(),
+        VmErrorKind::Halted {
+            halt: VmHaltInfo::Yielded
+        }
+    );
+
+    Ok(())
+}